@@ -105,7 +105,7 @@ async function loadAppConfig() {
         }
 
     } catch (error) {
-        await logger.error('Failed to load application configuration', 'config', { error: error.message });
+        await logger.error('Failed to load application configuration', 'config', { error: error.message, code: error.code, detail: error.detail });
         // Continue with default behavior
     }
 }
@@ -141,7 +141,7 @@ document.addEventListener('DOMContentLoaded', async () => {
 
         await logger.info('Application initialization completed successfully', 'app');
     } catch (error) {
-        await logger.error('Application initialization failed', 'app', { error: error.message });
+        await logger.error('Application initialization failed', 'app', { error: error.message, code: error.code, detail: error.detail });
         showError(`Application initialization failed: ${error.message}`);
     }
 });
@@ -170,7 +170,7 @@ async function loadServers() {
 
     } catch (error) {
         console.error('Error loading servers:', error);
-        showError(`Failed to load server configuration: ${error.message || error}`);
+        showError(`Failed to load server configuration: ${error.message || error}`, error);
         renderEmptyServerList();
     }
 }
@@ -245,9 +245,59 @@ function updateConnectButton() {
         : 'Connect to Server';
 }
 
+// Operation ids for in-flight backend calls that the user can cancel
+let authOperationId = null;
+let targetDiscoveryOperationId = null;
+
+// Boundary filter expression applied server-side to target discovery (null = no filter)
+let targetFilter = null;
+// Favorite target ids and recently used targets of the selected server
+let targetShortcuts = { favorites: [], recent: [] };
+// Latest search request; answers to older keystrokes are dropped
+let targetSearchSequence = 0;
+// Listener for the lifecycle events of the connection being established or shown
+let unlistenConnectionEvents = null;
+// Session whose events are listened to, and its events received before the status view is shown
+let followedSessionId = null;
+let pendingConnectionEvents = null;
+
+function newOperationId(prefix) {
+    return `${prefix}-${Date.now()}-${Math.random().toString(36).slice(2, 8)}`;
+}
+
+// Cancel an in-flight backend operation (kills the underlying Boundary CLI process)
+async function cancelOperation(operationId) {
+    if (!operationId) {
+        return;
+    }
+
+    try {
+        await window.__TAURI__.core.invoke('cancel_operation_command', { operationId });
+        await logger.info('Operation cancelled', 'app', { operationId });
+    } catch (error) {
+        // The operation may already have finished
+        await logger.debug('Failed to cancel operation', 'app', { operationId, error: error.message || error });
+    }
+}
+
+// Cancel the authentication currently in progress
+async function cancelAuthentication() {
+    const operationId = authOperationId;
+    authOperationId = null;
+    await cancelOperation(operationId);
+    updateConnectButtonForAuth(false);
+}
+
 // Handle connect button click - starts OIDC authentication
 async function handleConnect() {
     console.log('handleConnect called!', { selectedServer });
+
+    // While authenticating, the connect button acts as a cancel button
+    if (authOperationId) {
+        await cancelAuthentication();
+        return;
+    }
+
     await logger.info('Connect button clicked', 'auth');
 
     if (!selectedServer) {
@@ -268,6 +318,7 @@ async function handleConnect() {
 
         // Update UI to show authentication in progress
         console.log('Calling updateConnectButtonForAuth...');
+        authOperationId = newOperationId('auth');
         updateConnectButtonForAuth(true);
 
         console.log('Calling hideError...');
@@ -285,12 +336,14 @@ async function handleConnect() {
         let authMethods;
         try {
             authMethods = await window.__TAURI__.core.invoke('discover_oidc_auth_methods_command', {
-                serverId: selectedServer.id
+                serverId: selectedServer.id,
+                operationId: authOperationId
             });
             console.log('OIDC discovery result:', authMethods);
         } catch (discoveryError) {
             console.error('OIDC discovery failed with error:', discoveryError);
-            throw new Error(`OIDC discovery failed: ${discoveryError.message || discoveryError}`);
+            // Keep structured backend errors intact so their hint is shown
+            throw discoveryError.code ? discoveryError : new Error(`OIDC discovery failed: ${discoveryError.message || discoveryError}`);
         }
 
         if (!authMethods || authMethods.length === 0) {
@@ -303,9 +356,10 @@ async function handleConnect() {
 
         // Start OIDC authentication
         const authRequest = {
-            serverId: selectedServer.id,
-            authMethodId: authMethod.id,
-            scopeId: null
+            server_id: selectedServer.id,
+            auth_method_id: authMethod.id,
+            scope_id: null,
+            operation_id: authOperationId
         };
 
         const authProgress = await window.__TAURI__.core.invoke('initiate_oidc_auth_command', {
@@ -318,8 +372,14 @@ async function handleConnect() {
         await monitorAuthenticationProgress(selectedServer.id, 'user'); // TODO: Get actual user ID
 
     } catch (error) {
-        await logger.error('Authentication failed', 'auth', { error: error.message });
-        showError(`Authentication failed: ${error.message || error}`);
+        if (!authOperationId) {
+            // Cancelled by the user
+            return;
+        }
+        await logger.error('Authentication failed', 'auth', { error: error.message, code: error.code, detail: error.detail });
+        showError(`Authentication failed: ${error.message || error}`, error);
+        offerServerDiagnostics(selectedServer);
+        authOperationId = null;
         updateConnectButtonForAuth(false);
     }
 }
@@ -330,6 +390,11 @@ async function monitorAuthenticationProgress(serverId, userId) {
     const checkInterval = 1000; // 1 second
 
     for (let attempt = 0; attempt < maxAttempts; attempt++) {
+        if (!authOperationId) {
+            await logger.info('Authentication cancelled by user', 'auth');
+            return;
+        }
+
         try {
             const authResult = await window.__TAURI__.core.invoke('check_oidc_auth_status_command', {
                 serverId: serverId,
@@ -338,10 +403,11 @@ async function monitorAuthenticationProgress(serverId, userId) {
 
             if (authResult.success && authResult.token) {
                 await logger.info('Authentication completed successfully', 'auth');
+                authOperationId = null;
 
                 // Check if scope selection is needed
                 if (authResult.scopes && authResult.scopes.length > 1) {
-                    await handleScopeSelection(serverId, userId, authResult.scopes);
+                    await handleScopeSelection(serverId, userId, authResult.scopes, authResult.scope_tree);
                 } else {
                     // Single scope or no scopes - complete authentication
                     const scopeId = authResult.scopes && authResult.scopes.length === 1
@@ -357,33 +423,49 @@ async function monitorAuthenticationProgress(serverId, userId) {
             await new Promise(resolve => setTimeout(resolve, checkInterval));
 
         } catch (error) {
-            await logger.error('Error checking authentication status', 'auth', { error: error.message });
+            await logger.error('Error checking authentication status', 'auth', { error: error.message, code: error.code, detail: error.detail });
             // Continue checking - might be temporary error
         }
     }
 
     // Authentication timed out
     await logger.warn('Authentication timed out', 'auth');
+    await cancelOperation(authOperationId);
+    authOperationId = null;
     showError('Authentication timed out. Please try again.');
     updateConnectButtonForAuth(false);
 }
 
+// Render one scope and, nested below it, its child scopes
+function renderScopeNode(node) {
+    const children = node.children || [];
+    return `
+        <div class="scope-node">
+            <div class="scope-item" data-scope-id="${escapeHtml(node.id)}">
+                <div class="scope-name">${escapeHtml(node.name)} <span class="scope-type">${escapeHtml(node.scope_type)}</span></div>
+                <div class="scope-description">${escapeHtml(node.description || '')}</div>
+            </div>
+            ${children.length > 0 ? `<div class="scope-children">${children.map(renderScopeNode).join('')}</div>` : ''}
+        </div>
+    `;
+}
+
 // Handle scope selection when multiple scopes are available
-async function handleScopeSelection(serverId, userId, scopes) {
+async function handleScopeSelection(serverId, userId, scopes, scopeTree = null) {
     await logger.info('Multiple scopes available, showing selection UI', 'auth', { scopeCount: scopes.length });
 
+    // Show the global -> org -> project hierarchy when the backend provided it
+    const scopeItems = scopeTree && scopeTree.children
+        ? scopeTree.children.map(renderScopeNode).join('')
+        : scopes.map(scope => renderScopeNode({ ...scope, children: [] })).join('');
+
     // Create scope selection UI
     const scopeSelectionHTML = `
         <div class="scope-selection">
             <h3>Select Scope</h3>
             <p>Multiple scopes are available. Please select one:</p>
             <div class="scope-list">
-                ${scopes.map(scope => `
-                    <div class="scope-item" data-scope-id="${scope.id}">
-                        <div class="scope-name">${escapeHtml(scope.name)}</div>
-                        <div class="scope-description">${escapeHtml(scope.description || '')}</div>
-                    </div>
-                `).join('')}
+                ${scopeItems}
             </div>
             <div class="scope-actions">
                 <button id="cancel-scope-selection">Cancel</button>
@@ -430,8 +512,8 @@ async function completeAuthentication(serverId, userId, scopeId) {
         }
 
     } catch (error) {
-        await logger.error('Failed to complete authentication', 'auth', { error: error.message });
-        showError(`Failed to complete authentication: ${error.message || error}`);
+        await logger.error('Failed to complete authentication', 'auth', { error: error.message, code: error.code, detail: error.detail });
+        showError(`Failed to complete authentication: ${error.message || error}`, error);
         updateConnectButtonForAuth(false);
     }
 }
@@ -439,8 +521,8 @@ async function completeAuthentication(serverId, userId, scopeId) {
 // Update connect button for authentication state
 function updateConnectButtonForAuth(isAuthenticating) {
     if (isAuthenticating) {
-        connectButton.disabled = true;
-        connectButton.textContent = 'Authenticating...';
+        connectButton.disabled = false;
+        connectButton.textContent = 'Authenticating... (click to cancel)';
     } else {
         connectButton.disabled = !selectedServer;
         connectButton.textContent = selectedServer
@@ -464,13 +546,16 @@ async function showAuthenticationSuccess() {
 async function showTargetSelection() {
     await logger.info('Loading target selection UI', 'targets');
 
+    stopFollowingConnection();
+
     const targetSelectionHTML = `
         <div class="target-selection">
             <div class="target-header">
                 <h2>Select Target</h2>
                 <p>Connected to <strong>${selectedServer.name}</strong></p>
                 <div class="target-actions">
-                    <input type="text" id="target-search" placeholder="Search targets..." class="target-search">
+                    <input type="text" id="target-search" placeholder="Search targets, or enter an alias to connect..." class="target-search">
+                    <input type="text" id="target-filter" placeholder='Boundary filter, e.g. "/item/type" == "tcp" (Enter)' class="target-search target-filter" value="${escapeAttribute(targetFilter || '')}">
                     <button id="refresh-targets" class="refresh-btn">↻ Refresh</button>
                     <button id="back-to-servers" class="back-btn">← Back to Servers</button>
                 </div>
@@ -479,7 +564,9 @@ async function showTargetSelection() {
                 <div id="target-loading" class="loading-state">
                     <div class="spinner"></div>
                     <p>Discovering available targets...</p>
+                    <button id="cancel-target-discovery" class="back-btn">Cancel</button>
                 </div>
+                <div id="target-progress" class="target-progress" style="display: none;"></div>
                 <div id="target-list" class="target-list" style="display: none;"></div>
                 <div id="target-error" class="error-state" style="display: none;"></div>
             </div>
@@ -490,8 +577,11 @@ async function showTargetSelection() {
 
     // Add event listeners
     document.getElementById('target-search').addEventListener('input', handleTargetSearch);
+    document.getElementById('target-search').addEventListener('keydown', handleTargetSearchKey);
+    document.getElementById('target-filter').addEventListener('keydown', handleTargetFilter);
     document.getElementById('refresh-targets').addEventListener('click', refreshTargets);
     document.getElementById('back-to-servers').addEventListener('click', backToServerSelection);
+    document.getElementById('cancel-target-discovery').addEventListener('click', cancelTargetDiscovery);
 
     // Start target discovery
     await discoverAndDisplayTargets();
@@ -502,25 +592,62 @@ async function discoverAndDisplayTargets() {
     const loadingElement = document.getElementById('target-loading');
     const targetListElement = document.getElementById('target-list');
     const errorElement = document.getElementById('target-error');
+    const progressElement = document.getElementById('target-progress');
+
+    // Projects listed so far when the backend falls back to per-scope discovery
+    const discovery = { operationId: newOperationId('targets'), targets: [], failedScopes: [], fromCache: false, changes: null };
+    let unlistenProgress = null;
+    let unlistenChanges = null;
 
     try {
         await logger.info('Discovering targets from Boundary server', 'targets');
 
-        // Show loading state
-        loadingElement.style.display = 'block';
-        targetListElement.style.display = 'none';
         errorElement.style.display = 'none';
+        progressElement.style.display = 'none';
+
+        await loadTargetShortcuts();
+
+        // Show the cached list right away; the discovery below refreshes it in the background
+        discovery.fromCache = !targetFilter && await showCachedTargets();
+        if (!discovery.fromCache) {
+            loadingElement.style.display = 'block';
+            targetListElement.style.display = 'none';
+        }
+
+        unlistenProgress = await window.__TAURI__.event.listen('target-discovery-progress', (event) => {
+            handleTargetDiscoveryProgress(discovery, event.payload);
+        });
+        unlistenChanges = await window.__TAURI__.event.listen('target-cache-diff', (event) => {
+            if (selectedServer && event.payload.server_id === selectedServer.id) {
+                discovery.changes = event.payload;
+            }
+        });
 
         // Discover all targets for the authenticated user
+        targetDiscoveryOperationId = discovery.operationId;
         const targets = await window.__TAURI__.core.invoke('discover_all_targets_command', {
-            serverId: selectedServer.id
+            serverId: selectedServer.id,
+            filter: targetFilter,
+            operationId: targetDiscoveryOperationId
         });
+        targetDiscoveryOperationId = null;
 
-        await logger.info('Targets discovered successfully', 'targets', { count: targets.length });
+        await logger.info('Targets discovered successfully', 'targets', {
+            count: targets.length,
+            filter: targetFilter,
+            failedScopes: discovery.failedScopes.length
+        });
+
+        showFailedScopes(discovery.failedScopes);
+        if (discovery.failedScopes.length === 0 && discovery.changes) {
+            showTargetListChanges(discovery.changes);
+        }
 
         if (targets.length === 0) {
             showNoTargetsMessage();
-        } else if (targets.length === 1) {
+        } else if (targets.length === 1 && !targetFilter && discovery.failedScopes.length === 0 && canConnect(targets[0])) {
+            // A filter narrowing the list to one target, or projects missing from it,
+            // is not a reason to connect without asking
             // Auto-connect for single target
             await handleSingleTargetAutoConnect(targets[0]);
         } else {
@@ -529,13 +656,144 @@ async function discoverAndDisplayTargets() {
         }
 
     } catch (error) {
-        await logger.error('Failed to discover targets', 'targets', { error: error.message });
-        showTargetError(error.message || error);
+        targetDiscoveryOperationId = null;
+        progressElement.style.display = 'none';
+        await logger.error('Failed to discover targets', 'targets', { error: error.message, code: error.code, detail: error.detail });
+        if (discovery.fromCache) {
+            // Keep the cached list usable and say it could not be refreshed
+            showProgressMessage(`Showing cached targets; refresh failed: ${error.message || error}`, true);
+        } else {
+            showTargetError(error);
+        }
+    } finally {
+        if (unlistenProgress) {
+            unlistenProgress();
+        }
+        if (unlistenChanges) {
+            unlistenChanges();
+        }
+    }
+}
+
+// Display targets cached by a previous discovery; returns whether anything was shown
+async function showCachedTargets() {
+    try {
+        const cached = await window.__TAURI__.core.invoke('get_cached_discovery_command', { serverId: selectedServer.id });
+        if (!cached || !cached.targets_fetched_at || cached.targets.length === 0) {
+            return false;
+        }
+
+        await logger.info('Showing cached targets while refreshing', 'targets', {
+            count: cached.targets.length,
+            fetchedAt: cached.targets_fetched_at
+        });
+        displayTargetList(cached.targets);
+        showProgressMessage('Showing cached targets, refreshing...', false);
+        return true;
+    } catch (error) {
+        await logger.debug('No cached targets available', 'targets', { error: error.message || error });
+        return false;
+    }
+}
+
+// One-line status above the target list
+function showProgressMessage(message, warning) {
+    const progressElement = document.getElementById('target-progress');
+    if (!progressElement) {
+        return;
+    }
+
+    progressElement.className = warning ? 'target-progress warning' : 'target-progress';
+    progressElement.style.display = 'flex';
+    progressElement.innerHTML = `<span>${escapeHtml(message)}</span>`;
+}
+
+// Summarize what the refresh changed compared to the cached list
+function showTargetListChanges(changes) {
+    const parts = [];
+    if (changes.added.length > 0) {
+        parts.push(`${changes.added.length} added`);
     }
+    if (changes.removed.length > 0) {
+        parts.push(`${changes.removed.length} removed`);
+    }
+    if (changes.changed.length > 0) {
+        parts.push(`${changes.changed.length} updated`);
+    }
+
+    logger.info('Target list changed since last visit', 'targets', {
+        added: changes.added.map(t => t.id),
+        removed: changes.removed.map(t => t.id),
+        changed: changes.changed.map(t => t.id)
+    });
+    showProgressMessage(`Target list refreshed: ${parts.join(', ')}`, false);
+}
+
+// Show each project's targets as soon as the backend has listed it
+function handleTargetDiscoveryProgress(discovery, progress) {
+    // Ignore stragglers from a discovery that was cancelled or replaced
+    if (progress.operation_id !== discovery.operationId || targetDiscoveryOperationId !== discovery.operationId) {
+        return;
+    }
+
+    if (progress.error) {
+        discovery.failedScopes.push({ name: progress.scope_name, error: progress.error });
+        logger.warn('Failed to list targets in scope', 'targets', { scope: progress.scope_id, error: progress.error });
+    } else {
+        discovery.targets.push(...progress.targets);
+    }
+
+    const progressElement = document.getElementById('target-progress');
+    if (!progressElement) {
+        return;
+    }
+
+    progressElement.className = 'target-progress';
+    progressElement.style.display = 'flex';
+    progressElement.innerHTML = `
+        <span>Listed ${progress.completed} of ${progress.total} projects...</span>
+        <button id="cancel-target-progress" class="back-btn">Cancel</button>
+    `;
+    document.getElementById('cancel-target-progress').addEventListener('click', cancelTargetDiscovery);
+
+    // A cached list stays in place until the complete fresh one arrives
+    if (discovery.targets.length > 0 && !discovery.fromCache) {
+        displayTargetList(discovery.targets);
+    }
+}
+
+// Warn about projects whose targets could not be listed, or clear the progress line
+function showFailedScopes(failedScopes) {
+    const progressElement = document.getElementById('target-progress');
+    if (!progressElement) {
+        return;
+    }
+
+    if (failedScopes.length === 0) {
+        progressElement.style.display = 'none';
+        return;
+    }
+
+    const names = failedScopes.map(scope => scope.name).join(', ');
+    const details = failedScopes.map(scope => `${scope.name}: ${scope.error}`).join('\n');
+
+    progressElement.className = 'target-progress warning';
+    progressElement.style.display = 'flex';
+    progressElement.innerHTML = `
+        <span title="${escapeAttribute(details)}">Targets could not be listed in ${failedScopes.length} project(s): ${escapeHtml(names)}</span>
+    `;
+}
+
+// Cancel the target discovery currently in progress
+async function cancelTargetDiscovery() {
+    const operationId = targetDiscoveryOperationId;
+    targetDiscoveryOperationId = null;
+    await cancelOperation(operationId);
 }
 
 // Display the list of targets
-function displayTargetList(targets) {
+// `searchMatches` ({target, highlights}, best first) replaces the grouped list while searching
+function displayTargetList(targets, searchMatches = null) {
     const loadingElement = document.getElementById('target-loading');
     const targetListElement = document.getElementById('target-list');
 
@@ -546,39 +804,35 @@ function displayTargetList(targets) {
     // Store targets globally for search functionality
     window.availableTargets = targets;
 
-    // Group targets by type for better organization
-    const groupedTargets = groupTargetsByType(targets);
+    // Favorites and recently used targets come first; each target is listed once
+    const byId = new Map(targets.map(target => [target.id, target]));
+    const favoriteTargets = targetShortcuts.favorites.map(id => byId.get(id)).filter(Boolean);
+    const listed = new Set(favoriteTargets.map(target => target.id));
+    const recentTargets = targetShortcuts.recent
+        .map(recent => byId.get(recent.target_id))
+        .filter(target => target && !listed.has(target.id));
+    recentTargets.forEach(target => listed.add(target.id));
 
     let targetsHTML = '';
 
-    Object.keys(groupedTargets).forEach(type => {
-        const typeTargets = groupedTargets[type];
-        const typeIcon = getTargetTypeIcon(type);
-
-        targetsHTML += `
-            <div class="target-group">
-                <h3 class="target-group-header">
-                    <span class="target-type-icon">${typeIcon}</span>
-                    ${escapeHtml(type)} (${typeTargets.length})
-                </h3>
-                <div class="target-group-list">
-                    ${typeTargets.map(target => `
-                        <div class="target-item" data-target-id="${target.id}" data-target-type="${target.type}">
-                            <div class="target-info">
-                                <div class="target-name">${escapeHtml(target.name)}</div>
-                                <div class="target-description">${escapeHtml(target.description || 'No description')}</div>
-                                <div class="target-details">
-                                    <span class="target-id">ID: ${escapeHtml(target.id)}</span>
-                                    <span class="target-address">${escapeHtml(target.address || 'Dynamic')}</span>
-                                </div>
-                            </div>
-                            <button class="target-connect-btn" data-target-id="${target.id}">Connect</button>
-                        </div>
-                    `).join('')}
-                </div>
-            </div>
-        `;
-    });
+    if (searchMatches) {
+        const highlights = new Map(searchMatches.map(match => [match.target.id, match.highlights]));
+        targetsHTML += renderTargetGroup('🔍', 'Search results', searchMatches.map(match => match.target), 'search', highlights);
+    } else {
+        if (favoriteTargets.length > 0) {
+            targetsHTML += renderTargetGroup('⭐', 'Favorites', favoriteTargets, 'favorites');
+        }
+        if (recentTargets.length > 0) {
+            targetsHTML += renderTargetGroup('🕘', 'Recent', recentTargets, 'recent');
+        }
+
+        // Group the remaining targets by type for better organization
+        const groupedTargets = groupTargetsByType(targets.filter(target => !listed.has(target.id)));
+
+        Object.keys(groupedTargets).forEach(type => {
+            targetsHTML += renderTargetGroup(getTargetTypeIcon(type), type, groupedTargets[type]);
+        });
+    }
 
     targetListElement.innerHTML = targetsHTML;
 
@@ -594,6 +848,27 @@ function displayTargetList(targets) {
         });
     });
 
+    // Add click handlers for target details
+    targetListElement.querySelectorAll('.target-info-btn').forEach(button => {
+        button.addEventListener('click', async (e) => {
+            e.stopPropagation();
+            await toggleTargetDetails(button.getAttribute('data-target-id'));
+        });
+    });
+
+    // Add click handlers for favorites
+    targetListElement.querySelectorAll('.target-favorite-btn').forEach(button => {
+        button.addEventListener('click', async (e) => {
+            e.stopPropagation();
+            await toggleFavoriteTarget(button.getAttribute('data-target-id'));
+        });
+    });
+
+    const clearRecentButton = targetListElement.querySelector('.clear-recent-btn');
+    if (clearRecentButton) {
+        clearRecentButton.addEventListener('click', clearRecentTargets);
+    }
+
     // Add click handlers for target items (select on click)
     targetListElement.querySelectorAll('.target-item').forEach(item => {
         item.addEventListener('click', () => {
@@ -605,12 +880,278 @@ function displayTargetList(targets) {
     });
 }
 
+// One titled group of targets; `shortcut` marks the favorites, recent and search groups
+function renderTargetGroup(icon, title, targets, shortcut = null, highlights = null) {
+    return `
+        <div class="target-group ${shortcut ? `target-group-${shortcut}` : ''}">
+            <h3 class="target-group-header">
+                <span class="target-type-icon">${icon}</span>
+                ${escapeHtml(title)} (${targets.length})
+                ${shortcut === 'recent' ? '<button class="clear-recent-btn">Clear</button>' : ''}
+            </h3>
+            <div class="target-group-list">
+                ${targets.map(target => renderTargetItem(target, highlights ? highlights.get(target.id) : null)).join('')}
+            </div>
+        </div>
+    `;
+}
+
+// `highlights` are the search matches of this target, if any
+function renderTargetItem(target, highlights = null) {
+    const favorite = targetShortcuts.favorites.includes(target.id);
+    const connectable = canConnect(target);
+    const positions = (field) => (highlights || []).find(h => h.field === field)?.positions;
+    return `
+        <div class="target-item ${connectable ? '' : 'not-connectable'}" data-target-id="${target.id}" data-target-type="${escapeAttribute(targetType(target))}">
+            <div class="target-info">
+                <div class="target-name">${highlightText(target.name, positions('name'))}</div>
+                ${target.aliases && target.aliases.length > 0 ? `<div class="target-aliases">${highlightText(target.aliases.join(', '), positions('alias'))}</div>` : ''}
+                <div class="target-description">${target.description ? highlightText(target.description, positions('description')) : 'No description'}</div>
+                <div class="target-details">
+                    <span class="target-id">ID: ${highlightText(target.id, positions('id'))}</span>
+                    <span class="target-address">${target.address ? highlightText(target.address, positions('address')) : 'Dynamic'}</span>
+                    ${target.scope_name ? `<span class="target-scope">${highlightText(target.scope_name, positions('scope_name'))}</span>` : ''}
+                    ${target.session_max_seconds ? `<span class="target-session">Session: ${formatDuration(target.session_max_seconds)}</span>` : ''}
+                </div>
+                <div class="target-detail-panel" data-target-id="${target.id}" style="display: none;"></div>
+            </div>
+            <div class="target-buttons">
+                <button class="target-favorite-btn ${favorite ? 'active' : ''}" data-target-id="${target.id}" title="${favorite ? 'Remove from favorites' : 'Add to favorites'}">${favorite ? '★' : '☆'}</button>
+                <button class="target-info-btn" data-target-id="${target.id}">Details</button>
+                <button class="target-connect-btn" data-target-id="${target.id}" ${connectable ? '' : 'disabled title="You are not permitted to connect to this target"'}>Connect</button>
+            </div>
+        </div>
+    `;
+}
+
+// Favorites and recently used targets of the selected server
+async function loadTargetShortcuts() {
+    try {
+        const [favorites, recent] = await Promise.all([
+            window.__TAURI__.core.invoke('list_favorite_targets_command', { serverId: selectedServer.id }),
+            window.__TAURI__.core.invoke('list_recent_targets_command', { serverId: selectedServer.id })
+        ]);
+        targetShortcuts = { favorites, recent };
+    } catch (error) {
+        targetShortcuts = { favorites: [], recent: [] };
+        await logger.warn('Failed to load favorite and recent targets', 'targets', { error: error.message || error });
+    }
+}
+
+// Redraw the list the user is looking at, keeping the current search
+function redisplayTargets() {
+    const searchInput = document.getElementById('target-search');
+    if (searchInput && searchInput.value) {
+        handleTargetSearch({ target: searchInput });
+    } else {
+        displayTargetList(window.availableTargets || []);
+    }
+}
+
+async function toggleFavoriteTarget(targetId) {
+    const favorite = targetShortcuts.favorites.includes(targetId);
+    const command = favorite ? 'unpin_favorite_target_command' : 'pin_favorite_target_command';
+
+    try {
+        targetShortcuts.favorites = await window.__TAURI__.core.invoke(command, { serverId: selectedServer.id, targetId });
+        await logger.info(favorite ? 'Target removed from favorites' : 'Target added to favorites', 'targets', { targetId });
+        redisplayTargets();
+    } catch (error) {
+        await logger.error('Failed to update favorite targets', 'targets', { targetId, error: error.message || error });
+    }
+}
+
+async function clearRecentTargets(event) {
+    event.stopPropagation();
+    try {
+        await window.__TAURI__.core.invoke('clear_recent_targets_command', { serverId: selectedServer.id });
+        targetShortcuts.recent = [];
+        await logger.info('Recently used targets cleared', 'targets');
+        redisplayTargets();
+    } catch (error) {
+        await logger.error('Failed to clear recently used targets', 'targets', { error: error.message || error });
+    }
+}
+
+// Mirrors BoundaryTarget::can_authorize_session: without any listed actions, assume the user may connect
+function canConnect(target) {
+    const actions = target.authorized_actions || [];
+    return actions.length === 0 || actions.includes('authorize-session');
+}
+
+// Target type as serialized by the backend
+function targetType(target) {
+    return target.target_type || target.type;
+}
+
+// Human-readable duration, e.g. 28800 -> "8h"
+function formatDuration(seconds) {
+    const hours = Math.floor(seconds / 3600);
+    const minutes = Math.floor((seconds % 3600) / 60);
+    if (hours > 0) {
+        return minutes > 0 ? `${hours}h ${minutes}m` : `${hours}h`;
+    }
+    return minutes > 0 ? `${minutes}m` : `${seconds}s`;
+}
+
+// Show or hide the full details of a target, fetched from the controller on first open
+async function toggleTargetDetails(targetId) {
+    const panel = document.querySelector(`.target-detail-panel[data-target-id="${targetId}"]`);
+    if (!panel) {
+        return;
+    }
+
+    if (panel.style.display !== 'none') {
+        panel.style.display = 'none';
+        return;
+    }
+
+    panel.style.display = 'block';
+    if (panel.dataset.loaded) {
+        return;
+    }
+
+    panel.innerHTML = '<p>Loading target details...</p>';
+    try {
+        const target = await window.__TAURI__.core.invoke('get_target_details_command', {
+            serverId: selectedServer.id,
+            targetId
+        });
+        panel.innerHTML = renderTargetDetails(target);
+        panel.dataset.loaded = 'true';
+        await logger.debug('Target details loaded', 'targets', { targetId });
+
+        if (target.host_source_ids && target.host_source_ids.length > 0) {
+            await showHostSelector(panel, targetId);
+        }
+        await showListenSettings(panel, targetId);
+    } catch (error) {
+        panel.innerHTML = `<p>Failed to load target details: ${escapeHtml(error.message || error)}</p>`;
+        await logger.error('Failed to load target details', 'targets', { targetId, error: error.message || error });
+    }
+}
+
+// Let the user pin one of the target's hosts instead of letting Boundary pick one at random
+async function showHostSelector(panel, targetId) {
+    const container = document.createElement('div');
+    container.className = 'target-host-selector';
+    container.innerHTML = '<p>Loading hosts...</p>';
+    panel.appendChild(container);
+
+    try {
+        const [hostSets, pinnedHost] = await Promise.all([
+            window.__TAURI__.core.invoke('list_target_hosts_command', { serverId: selectedServer.id, targetId }),
+            window.__TAURI__.core.invoke('get_pinned_host_command', { serverId: selectedServer.id, targetId })
+        ]);
+
+        const options = hostSets.flatMap(hostSet => hostSet.hosts.map(host => `
+            <option value="${escapeAttribute(host.id)}" ${host.id === pinnedHost ? 'selected' : ''}>
+                ${escapeHtml(host.name)}${host.address ? ` (${escapeHtml(host.address)})` : ''} - ${escapeHtml(hostSet.name)}
+            </option>
+        `));
+
+        container.innerHTML = `
+            <label for="host-select-${escapeAttribute(targetId)}">Host</label>
+            <select id="host-select-${escapeAttribute(targetId)}" class="target-host-select">
+                <option value="">Any host (chosen by Boundary)</option>
+                ${options.join('')}
+            </select>
+        `;
+
+        const select = container.querySelector('select');
+        select.addEventListener('click', (e) => e.stopPropagation());
+        select.addEventListener('change', async () => {
+            const hostId = select.value || null;
+            try {
+                await window.__TAURI__.core.invoke('set_pinned_host_command', { serverId: selectedServer.id, targetId, hostId });
+                await logger.info('Pinned host updated', 'targets', { targetId, hostId });
+            } catch (error) {
+                await logger.error('Failed to pin host', 'targets', { targetId, hostId, error: error.message || error });
+            }
+        });
+    } catch (error) {
+        container.innerHTML = `<p>Failed to load hosts: ${escapeHtml(error.message || error)}</p>`;
+        await logger.error('Failed to load target hosts', 'targets', { targetId, error: error.message || error });
+    }
+}
+
+// Let the user fix the local address and port the connection listens on, so saved client
+// profiles keep working across reconnects
+async function showListenSettings(panel, targetId) {
+    const container = document.createElement('div');
+    container.className = 'target-host-selector target-listen-settings';
+    panel.appendChild(container);
+
+    try {
+        const settings = await window.__TAURI__.core.invoke('get_listen_settings_command', { serverId: selectedServer.id, targetId });
+
+        container.innerHTML = `
+            <label>Local address</label>
+            <input type="text" class="target-host-select listen-addr" placeholder="${escapeAttribute(selectedServer.listenAddr || '127.0.0.1')}" value="${escapeAttribute(settings.listen_addr || '')}">
+            <label>Port</label>
+            <input type="number" min="1" max="65535" class="target-host-select listen-port" placeholder="${escapeAttribute(String(selectedServer.listenPort || 'Random'))}" value="${settings.listen_port || ''}">
+            <button class="refresh-btn save-listen-btn">Save</button>
+        `;
+
+        container.addEventListener('click', (e) => e.stopPropagation());
+        container.querySelector('.save-listen-btn').addEventListener('click', async () => {
+            const listenAddr = container.querySelector('.listen-addr').value.trim() || null;
+            const listenPort = parseInt(container.querySelector('.listen-port').value, 10) || null;
+            try {
+                await window.__TAURI__.core.invoke('set_listen_settings_command', {
+                    serverId: selectedServer.id,
+                    targetId,
+                    settings: { listen_addr: listenAddr, listen_port: listenPort }
+                });
+                await logger.info('Listen settings updated', 'targets', { targetId, listenAddr, listenPort });
+            } catch (error) {
+                await logger.error('Failed to save listen settings', 'targets', { targetId, error: error.message || error });
+                showError(`Failed to save listen settings: ${error.detail || error.message || error}`, error);
+            }
+        });
+    } catch (error) {
+        container.innerHTML = `<p>Failed to load listen settings: ${escapeHtml(error.message || error)}</p>`;
+        await logger.error('Failed to load listen settings', 'targets', { targetId, error: error.message || error });
+    }
+}
+
+// Session limits, credentials and routing of a target, as returned by get_target_details_command
+function renderTargetDetails(target) {
+    const list = (ids) => ids && ids.length > 0 ? ids.map(escapeHtml).join(', ') : 'None';
+    const connectionLimit = target.session_connection_limit === -1 || target.session_connection_limit == null
+        ? 'Unlimited'
+        : target.session_connection_limit;
+
+    const rows = [
+        ['Scope', target.scope_name ? `${target.scope_name} (${target.scope_id})` : (target.scope_id || 'Unknown')],
+        ['Session duration', target.session_max_seconds ? formatDuration(target.session_max_seconds) : 'Unknown'],
+        ['Connections per session', connectionLimit],
+        ['Default port', target.default_port || 'None'],
+        ['Default client port', target.default_client_port || 'Random'],
+        ['Brokered credentials', null, list(target.brokered_credential_source_ids)],
+        ['Injected credentials', null, list(target.injected_application_credential_source_ids)],
+        ['Host sources', null, list(target.host_source_ids)],
+        ['Egress worker filter', target.egress_worker_filter || 'None'],
+        ['Ingress worker filter', target.ingress_worker_filter || 'None'],
+        ['Allowed actions', (target.authorized_actions || []).join(', ') || 'None']
+    ];
+
+    return `
+        <dl>
+            ${rows.map(([label, value, html]) => `
+                <dt>${label}</dt>
+                <dd>${html !== undefined ? html : escapeHtml(String(value))}</dd>
+            `).join('')}
+        </dl>
+    `;
+}
+
 // Group targets by type for better organization
 function groupTargetsByType(targets) {
     const grouped = {};
 
     targets.forEach(target => {
-        const type = target.type || 'Unknown';
+        const type = targetType(target) || 'Unknown';
         if (!grouped[type]) {
             grouped[type] = [];
         }
@@ -634,23 +1175,91 @@ function getTargetTypeIcon(type) {
 }
 
 // Handle target search
-function handleTargetSearch(event) {
-    const searchTerm = event.target.value.toLowerCase();
+// Ranked in the backend from the target cache; plain substring filtering when nothing is cached
+async function handleTargetSearch(event) {
+    const query = event.target.value.trim();
     const targets = window.availableTargets || [];
+    const sequence = ++targetSearchSequence;
 
-    if (!searchTerm) {
+    if (!query) {
         displayTargetList(targets);
         return;
     }
 
-    const filteredTargets = targets.filter(target =>
-        target.name.toLowerCase().includes(searchTerm) ||
-        target.description?.toLowerCase().includes(searchTerm) ||
-        target.id.toLowerCase().includes(searchTerm) ||
-        target.address?.toLowerCase().includes(searchTerm)
+    let matches = null;
+    try {
+        matches = await window.__TAURI__.core.invoke('search_targets_command', { serverId: selectedServer.id, query });
+    } catch (error) {
+        await logger.warn('Target search failed, filtering locally', 'targets', { error: error.message || error });
+    }
+
+    // A newer keystroke has started its own search
+    if (sequence !== targetSearchSequence) {
+        return;
+    }
+
+    if (matches) {
+        // The cache holds every target; keep only those in the list being shown (e.g. with a filter)
+        const byId = new Map(targets.map(target => [target.id, target]));
+        matches = matches
+            .filter(match => byId.has(match.target.id))
+            .map(match => ({ ...match, target: byId.get(match.target.id) }));
+    } else {
+        const searchTerm = query.toLowerCase();
+        matches = targets
+            .filter(target =>
+                target.name.toLowerCase().includes(searchTerm) ||
+                target.description?.toLowerCase().includes(searchTerm) ||
+                target.id.toLowerCase().includes(searchTerm) ||
+                target.address?.toLowerCase().includes(searchTerm)
+            )
+            .map(target => ({ target, highlights: [] }));
+    }
+
+    displayTargetList(targets, matches);
+}
+
+// Enter connects directly when the search text is an alias such as "db.prod.internal"
+async function handleTargetSearchKey(event) {
+    if (event.key !== 'Enter') {
+        return;
+    }
+
+    const alias = event.target.value.trim();
+    if (!alias.includes('.')) {
+        return;
+    }
+
+    // The backend resolves the alias, including the host it may name
+    const target = (window.availableTargets || []).find(t =>
+        (t.aliases || []).some(a => a.toLowerCase() === alias.toLowerCase())
     );
+    await logger.info('Connecting by alias', 'connection', { alias, targetId: target?.id });
+    await handleTargetConnection(target || { id: alias, name: alias }, alias);
+}
+
+// Wrap the characters at `positions` (character indexes from search_targets_command) in <mark>
+function highlightText(text, positions) {
+    if (!positions || positions.length === 0) {
+        return escapeHtml(text);
+    }
 
-    displayTargetList(filteredTargets);
+    const marked = new Set(positions);
+    return Array.from(text)
+        .map((c, i) => marked.has(i) ? `<mark>${escapeHtml(c)}</mark>` : escapeHtml(c))
+        .join('');
+}
+
+// Apply a Boundary filter expression on Enter; an empty filter lists everything again
+async function handleTargetFilter(event) {
+    if (event.key !== 'Enter') {
+        return;
+    }
+
+    const filter = event.target.value.trim();
+    targetFilter = filter || null;
+    await logger.info('Applying target filter', 'targets', { filter: targetFilter });
+    await discoverAndDisplayTargets();
 }
 
 // Refresh targets
@@ -699,7 +1308,7 @@ function showNoTargetsMessage() {
 }
 
 // Show target error
-function showTargetError(errorMessage) {
+function showTargetError(error) {
     const loadingElement = document.getElementById('target-loading');
     const errorElement = document.getElementById('target-error');
 
@@ -708,7 +1317,8 @@ function showTargetError(errorMessage) {
     errorElement.innerHTML = `
         <div class="error-content">
             <h3>Failed to Load Targets</h3>
-            <p>${escapeHtml(errorMessage)}</p>
+            <p>${escapeHtml(error.message || error)}</p>
+            ${renderErrorDiagnostics(error)}
             <button id="retry-targets" class="retry-btn">Retry</button>
         </div>
     `;
@@ -717,7 +1327,8 @@ function showTargetError(errorMessage) {
 }
 
 // Handle target connection
-async function handleTargetConnection(target) {
+// `reference` is what authorization is requested for: the target id, or an alias
+async function handleTargetConnection(target, reference = target.id) {
     await logger.info('Initiating connection to target', 'connection', {
         targetName: target.name,
         targetId: target.id
@@ -735,9 +1346,12 @@ async function handleTargetConnection(target) {
         await logger.info('Authorizing session for target', 'connection');
         const authorization = await window.__TAURI__.core.invoke('authorize_session_command', {
             serverId: selectedServer.id,
-            targetId: target.id
+            targetId: reference
         });
 
+        // Listen before the proxy starts so its first events are not missed
+        await startFollowingConnection(authorization.session_id);
+
         // Establish connection
         await logger.info('Establishing connection', 'connection');
         const connection = await window.__TAURI__.core.invoke('establish_connection_command', {
@@ -756,7 +1370,8 @@ async function handleTargetConnection(target) {
         await showConnectionSuccess(target, connection);
 
     } catch (error) {
-        await logger.error('Connection failed', 'connection', { error: error.message });
+        stopFollowingConnection();
+        await logger.error('Connection failed', 'connection', { error: error.message, code: error.code, detail: error.detail });
 
         // Reset button state
         const connectBtn = document.querySelector(`[data-target-id="${target.id}"]`);
@@ -765,7 +1380,7 @@ async function handleTargetConnection(target) {
             connectBtn.textContent = 'Connect';
         }
 
-        showError(`Connection failed: ${error.message || error}`);
+        showError(`Connection failed: ${error.message || error}`, error);
     }
 }
 
@@ -774,7 +1389,7 @@ async function showConnectionSuccess(target, connection) {
     await logger.info('Connection successful, handling post-connection actions', 'connection');
 
     // Check if this is an RDP target and launch client if available
-    if (target.type === 'rdp' || target.name.toLowerCase().includes('rdp')) {
+    if (targetType(target) === 'rdp' || target.name.toLowerCase().includes('rdp')) {
         await handleRDPClientLaunch(target, connection);
     }
 
@@ -808,7 +1423,7 @@ async function handleRDPClientLaunch(target, connection) {
         }
 
     } catch (error) {
-        await logger.error('RDP client launch failed', 'rdp', { error: error.message });
+        await logger.error('RDP client launch failed', 'rdp', { error: error.message, code: error.code, detail: error.detail });
         showManualConnectionInfo(target, connection);
     }
 }
@@ -861,7 +1476,15 @@ function showConnectionStatus(target, connection) {
                     </div>
                     <div class="detail-item">
                         <span class="label">Status:</span>
-                        <span class="value status-active">Active</span>
+                        <span id="connection-status" class="value status-active">Active</span>
+                    </div>
+                    <div class="detail-item">
+                        <span class="label">Connections Left:</span>
+                        <span id="connection-limit" class="value">${connection.connection_limit < 0 ? 'Unlimited' : connection.connection_limit}</span>
+                    </div>
+                    <div class="detail-item">
+                        <span class="label">Expires:</span>
+                        <span id="connection-expiry" class="value">${connection.expiration_time ? escapeHtml(new Date(connection.expiration_time).toLocaleString()) : 'Never'}</span>
                     </div>
                 </div>
                 <div class="connection-actions">
@@ -879,6 +1502,91 @@ function showConnectionStatus(target, connection) {
     document.getElementById('terminate-connection').addEventListener('click', () => terminateConnection(connection));
     document.getElementById('back-to-targets').addEventListener('click', showTargetSelection);
     document.getElementById('monitor-session').addEventListener('click', () => showSessionMonitoring(connection));
+
+    showPendingConnectionEvents();
+}
+
+// Keep the status view in step with the proxy: clients connecting, expiry, cancellation, exit.
+// Events are buffered until the status view is shown.
+async function startFollowingConnection(sessionId) {
+    stopFollowingConnection();
+    followedSessionId = sessionId;
+    pendingConnectionEvents = [];
+
+    const unlisten = await window.__TAURI__.event.listen('connection-event', (event) => {
+        const update = event.payload;
+        if (update.session_id !== sessionId) {
+            return;
+        }
+        if (pendingConnectionEvents) {
+            pendingConnectionEvents.push(update);
+        } else {
+            showConnectionEvent(update);
+        }
+    });
+
+    // Following may have been stopped while the listener was being registered
+    if (followedSessionId === sessionId) {
+        unlistenConnectionEvents = unlisten;
+    } else {
+        unlisten();
+    }
+}
+
+function showPendingConnectionEvents() {
+    const pending = pendingConnectionEvents || [];
+    pendingConnectionEvents = null;
+    pending.forEach(showConnectionEvent);
+}
+
+function stopFollowingConnection() {
+    followedSessionId = null;
+    pendingConnectionEvents = null;
+    if (unlistenConnectionEvents) {
+        unlistenConnectionEvents();
+        unlistenConnectionEvents = null;
+    }
+}
+
+function showConnectionEvent(update) {
+    const status = document.getElementById('connection-status');
+    if (!status) {
+        return;
+    }
+    logger.debug('Connection event', 'connection', update);
+
+    const setStatus = (text, active) => {
+        status.textContent = text;
+        status.classList.toggle('status-active', active);
+        status.classList.toggle('status-ended', !active);
+    };
+
+    switch (update.event) {
+        case 'proxy_ready':
+            setStatus('Active', true);
+            break;
+        case 'client_connected':
+            document.getElementById('connection-limit').textContent = update.connections_left < 0 ? 'Unlimited' : update.connections_left;
+            break;
+        case 'session_expiring':
+            setStatus(`Expiring in ${Math.ceil(update.seconds_left / 60)} min`, true);
+            break;
+        case 'session_expired':
+            setStatus('Session expired', false);
+            break;
+        case 'session_cancelled':
+            setStatus('Cancelled on the server', false);
+            break;
+        case 'session_terminated':
+            setStatus(`Session ended (${update.reason})`, false);
+            break;
+        case 'proxy_exited':
+            // Keep the reason the session ended, when it ended first
+            if (status.classList.contains('status-active')) {
+                setStatus(update.exit_code === null ? 'Proxy stopped' : `Proxy exited (code ${update.exit_code})`, false);
+            }
+            break;
+    }
 }
 
 // Terminate connection
@@ -886,18 +1594,24 @@ async function terminateConnection(connection) {
     try {
         await logger.info('Terminating connection', 'connection', { sessionId: connection.session_id });
 
-        await window.__TAURI__.core.invoke('terminate_connection_command', {
-            session_id: connection.session_id
+        const termination = await window.__TAURI__.core.invoke('terminate_connection_command', {
+            sessionId: connection.session_id
         });
 
-        await logger.info('Connection terminated successfully', 'connection');
-
         // Return to target selection
         await showTargetSelection();
 
+        // The proxy is stopped and the session cancelled independently; either can fail alone
+        if (termination.errors.length > 0) {
+            await logger.warn('Connection only partly terminated', 'connection', termination);
+            showError(`Connection closed, but not cleanly: ${termination.errors.join('; ')}`);
+        } else {
+            await logger.info('Connection terminated and session cancelled', 'connection', { sessionId: termination.session_id });
+        }
+
     } catch (error) {
-        await logger.error('Failed to terminate connection', 'connection', { error: error.message });
-        showError(`Failed to terminate connection: ${error.message || error}`);
+        await logger.error('Failed to terminate connection', 'connection', { error: error.message, code: error.code, detail: error.detail });
+        showError(`Failed to terminate connection: ${error.message || error}`, error);
     }
 }
 
@@ -908,6 +1622,11 @@ function showSessionMonitoring(connection) {
 
 // Back to server selection
 function backToServerSelection() {
+    // Stop any target discovery still running for the previous server
+    cancelTargetDiscovery();
+
+    stopFollowingConnection();
+
     // Reset state
     selectedServer = null;
 
@@ -919,12 +1638,63 @@ function backToServerSelection() {
     loadServers();
 }
 
-// Show error message
-function showError(message) {
-    errorMessage.textContent = message;
+// Show error message, with help-desk diagnostics when the backend returned a structured error
+function showError(message, error = null) {
+    errorMessage.innerHTML = `<div>${escapeHtml(message)}</div>${renderErrorDiagnostics(error)}`;
     errorMessage.style.display = 'block';
 }
 
+// Add a "Diagnose connection" button under the current error message
+function offerServerDiagnostics(server) {
+    if (!server) {
+        return;
+    }
+
+    const button = document.createElement('button');
+    button.className = 'retry-btn';
+    button.textContent = 'Diagnose connection';
+    button.addEventListener('click', () => runServerDiagnostics(server, button));
+    errorMessage.appendChild(button);
+}
+
+// Run the staged DNS/TCP/TLS/HTTP probe and show the report for support staff
+async function runServerDiagnostics(server, button) {
+    button.disabled = true;
+    button.textContent = 'Diagnosing...';
+
+    try {
+        await logger.info('Running server diagnostics', 'diagnostics', { serverId: server.id });
+        const report = await window.__TAURI__.core.invoke('diagnose_server_command', { serverId: server.id });
+        await logger.info('Server diagnostics completed', 'diagnostics', { status: report.status });
+
+        const reportElement = document.createElement('pre');
+        reportElement.className = 'diagnostics-report';
+        reportElement.textContent = report.report;
+        button.replaceWith(reportElement);
+    } catch (error) {
+        await logger.error('Server diagnostics failed', 'diagnostics', { error: error.message, code: error.code, detail: error.detail });
+        button.disabled = false;
+        button.textContent = 'Diagnose connection';
+    }
+}
+
+// Render the hint, error code and technical detail of a backend error ({code, message, detail, hint})
+function renderErrorDiagnostics(error) {
+    if (!error || !error.code) {
+        return '';
+    }
+
+    return `
+        <div class="error-diagnostics">
+            <p class="error-hint">${escapeHtml(error.hint || '')}</p>
+            <details class="error-detail">
+                <summary>Technical details (error code ${escapeHtml(error.code)})</summary>
+                <pre>${escapeHtml(error.detail || '')}</pre>
+            </details>
+        </div>
+    `;
+}
+
 // Hide error message
 function hideError() {
     errorMessage.style.display = 'none';
@@ -935,4 +1705,9 @@ function escapeHtml(text) {
     const div = document.createElement('div');
     div.textContent = text;
     return div.innerHTML;
+}
+
+// Escape text for use inside a double-quoted HTML attribute
+function escapeAttribute(text) {
+    return escapeHtml(text).replace(/"/g, '&quot;');
 }
\ No newline at end of file
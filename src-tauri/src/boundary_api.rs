@@ -0,0 +1,260 @@
+// Native client for the Boundary controller REST API (/v1/...)
+//
+// Discovery and session authorization talk to the controller directly through this
+// client; the Boundary CLI is only used when the controller cannot be reached and for
+// `boundary connect`.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use tracing::{debug, instrument};
+use url::Url;
+
+use crate::models::{self, AliasItem, AuthMethodItem, HostItem, HostSetItem, ScopeItem, SessionAuthorizationItem, SessionItem, TargetItem};
+use crate::{BoundaryAlias, BoundaryAuthMethod, BoundaryHost, BoundaryHostSet, BoundaryScope, BoundarySessionAuthorization, BoundaryTarget};
+
+// Errors returned by the controller API client
+#[derive(Debug)]
+pub enum ApiError {
+    InvalidUrl(String),
+    MissingToken,
+    Request(reqwest::Error),
+    Status { status: u16, kind: String, message: String },
+    Decode(String),
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::InvalidUrl(e) => write!(f, "Invalid controller URL: {}", e),
+            ApiError::MissingToken => write!(f, "No authentication token available for controller API request"),
+            ApiError::Request(e) => write!(f, "Controller API request failed: {}", e),
+            ApiError::Status { status, kind, message } => {
+                write!(f, "Controller API returned HTTP {} ({}): {}", status, kind, message)
+            }
+            ApiError::Decode(e) => write!(f, "Failed to decode controller API response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl ApiError {
+    // True when the request never left this machine, so the controller cannot have acted on it
+    pub fn is_unsent(&self) -> bool {
+        match self {
+            ApiError::InvalidUrl(_) | ApiError::MissingToken => true,
+            ApiError::Request(e) => e.is_connect() || e.is_builder(),
+            ApiError::Status { .. } | ApiError::Decode(_) => false,
+        }
+    }
+}
+
+// Cheap to clone: clones share the underlying HTTP connection pool
+#[derive(Clone)]
+pub struct BoundaryApiClient {
+    base_url: Url,
+    http: reqwest::Client,
+    token: Option<String>,
+}
+
+impl std::fmt::Debug for BoundaryApiClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundaryApiClient")
+            .field("base_url", &self.base_url.as_str())
+            .field("has_token", &self.token.is_some())
+            .finish()
+    }
+}
+
+impl BoundaryApiClient {
+    pub fn new(
+        server_addr: &str,
+        token: Option<String>,
+        ssl_verify: bool,
+        timeout_seconds: u32,
+    ) -> Result<Self, ApiError> {
+        let base_url = Url::parse(server_addr).map_err(|e| ApiError::InvalidUrl(e.to_string()))?;
+
+        let http = reqwest::Client::builder()
+            .danger_accept_invalid_certs(!ssl_verify)
+            .timeout(Duration::from_secs(timeout_seconds as u64))
+            .build()
+            .map_err(ApiError::Request)?;
+
+        Ok(BoundaryApiClient { base_url, http, token })
+    }
+
+    // The same client authenticating with another token, keeping its connection pool
+    pub fn with_token(&self, token: Option<String>) -> Self {
+        BoundaryApiClient { token, ..self.clone() }
+    }
+
+    // Build an absolute URL for a /v1/... path with query parameters
+    fn endpoint(&self, path: &str, query: &[(&str, &str)]) -> Result<Url, ApiError> {
+        let mut url = self
+            .base_url
+            .join(path)
+            .map_err(|e| ApiError::InvalidUrl(e.to_string()))?;
+
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query.iter());
+        }
+
+        Ok(url)
+    }
+
+    fn bearer_token(&self) -> Result<&str, ApiError> {
+        self.token.as_deref().ok_or(ApiError::MissingToken)
+    }
+
+    // Send a request and decode the JSON body, mapping controller errors to ApiError::Status
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Value, ApiError> {
+        let response = request.send().await.map_err(ApiError::Request)?;
+        let status = response.status();
+        let body = response.text().await.map_err(ApiError::Request)?;
+
+        if !status.is_success() {
+            // Controller errors look like {"kind": "PermissionDenied", "message": "..."}
+            let error_json: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+            return Err(ApiError::Status {
+                status: status.as_u16(),
+                kind: error_json["kind"].as_str().unwrap_or("Unknown").to_string(),
+                message: error_json["message"].as_str().unwrap_or(body.trim()).to_string(),
+            });
+        }
+
+        serde_json::from_str(&body).map_err(|e| ApiError::Decode(e.to_string()))
+    }
+
+    async fn get(&self, path: &str, query: &[(&str, &str)], authenticated: bool) -> Result<Value, ApiError> {
+        let url = self.endpoint(path, query)?;
+        debug!("Controller API GET {}", url);
+
+        let mut request = self.http.get(url);
+        if authenticated {
+            request = request.bearer_auth(self.bearer_token()?);
+        } else if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        self.send(request).await
+    }
+
+    async fn post(&self, path: &str, body: Value) -> Result<Value, ApiError> {
+        let url = self.endpoint(path, &[])?;
+        debug!("Controller API POST {}", url);
+
+        let request = self.http.post(url).bearer_auth(self.bearer_token()?).json(&body);
+        self.send(request).await
+    }
+
+    // GET /v1/auth-methods (anonymous listing is allowed on the global scope)
+    #[instrument]
+    pub async fn list_auth_methods(&self, scope_id: &str) -> Result<Vec<BoundaryAuthMethod>, ApiError> {
+        let json = self.get("/v1/auth-methods", &[("scope_id", scope_id)], false).await?;
+        let items = models::parse_items::<AuthMethodItem>(&json).map_err(ApiError::Decode)?;
+        Ok(items.into_iter().map(BoundaryAuthMethod::from).collect())
+    }
+
+    // GET /v1/scopes, optionally including every descendant scope
+    #[instrument]
+    pub async fn list_scopes(&self, scope_id: &str, recursive: bool) -> Result<Vec<BoundaryScope>, ApiError> {
+        let recursive = if recursive { "true" } else { "false" };
+        let json = self
+            .get("/v1/scopes", &[("scope_id", scope_id), ("recursive", recursive)], true)
+            .await?;
+        let items = models::parse_items::<ScopeItem>(&json).map_err(ApiError::Decode)?;
+        Ok(items.into_iter().map(BoundaryScope::from).collect())
+    }
+
+    // GET /v1/targets, optionally recursive and narrowed by a Boundary filter expression
+    #[instrument]
+    pub async fn list_targets(
+        &self,
+        scope_id: &str,
+        recursive: bool,
+        filter: Option<&str>,
+    ) -> Result<Vec<BoundaryTarget>, ApiError> {
+        let mut query = vec![("scope_id", scope_id)];
+        if recursive {
+            query.push(("recursive", "true"));
+        }
+        if let Some(filter) = filter {
+            query.push(("filter", filter));
+        }
+
+        let json = self.get("/v1/targets", &query, true).await?;
+        let items = models::parse_items::<TargetItem>(&json).map_err(ApiError::Decode)?;
+        Ok(items.into_iter().map(BoundaryTarget::from).collect())
+    }
+
+    // GET /v1/targets/{id}: the full target, including fields list responses may omit
+    #[instrument]
+    pub async fn read_target(&self, target_id: &str) -> Result<BoundaryTarget, ApiError> {
+        let json = self.get(&format!("/v1/targets/{}", target_id), &[], true).await?;
+        let item = models::parse_item::<TargetItem>(&json).map_err(ApiError::Decode)?;
+        Ok(BoundaryTarget::from(item))
+    }
+
+    // GET /v1/host-sets/{id}
+    #[instrument]
+    pub async fn read_host_set(&self, host_set_id: &str) -> Result<BoundaryHostSet, ApiError> {
+        let json = self.get(&format!("/v1/host-sets/{}", host_set_id), &[], true).await?;
+        let item = models::parse_item::<HostSetItem>(&json).map_err(ApiError::Decode)?;
+        Ok(BoundaryHostSet::from(item))
+    }
+
+    // GET /v1/hosts in one host catalog
+    #[instrument]
+    pub async fn list_hosts(&self, host_catalog_id: &str) -> Result<Vec<BoundaryHost>, ApiError> {
+        let json = self.get("/v1/hosts", &[("host_catalog_id", host_catalog_id)], true).await?;
+        let items = models::parse_items::<HostItem>(&json).map_err(ApiError::Decode)?;
+        Ok(items.into_iter().map(BoundaryHost::from).collect())
+    }
+
+    // GET /v1/aliases; aliases only live in the global scope
+    #[instrument]
+    pub async fn list_aliases(&self) -> Result<Vec<BoundaryAlias>, ApiError> {
+        let json = self
+            .get("/v1/aliases", &[("scope_id", "global"), ("recursive", "true")], true)
+            .await?;
+        let items = models::parse_items::<AliasItem>(&json).map_err(ApiError::Decode)?;
+        Ok(items.into_iter().map(BoundaryAlias::from).collect())
+    }
+
+    // POST /v1/targets/{id}:authorize-session
+    #[instrument]
+    pub async fn authorize_session(
+        &self,
+        target_id: &str,
+        host_id: Option<&str>,
+    ) -> Result<BoundarySessionAuthorization, ApiError> {
+        let mut body = json!({});
+        if let Some(host) = host_id {
+            body["host_id"] = json!(host);
+        }
+
+        let json = self
+            .post(&format!("/v1/targets/{}:authorize-session", target_id), body)
+            .await?;
+        let item = models::parse_item::<SessionAuthorizationItem>(&json).map_err(ApiError::Decode)?;
+        Ok(BoundarySessionAuthorization::from(item))
+    }
+
+    // GET /v1/sessions/{id}
+    #[instrument]
+    pub async fn read_session(&self, session_id: &str) -> Result<SessionItem, ApiError> {
+        let json = self.get(&format!("/v1/sessions/{}", session_id), &[], true).await?;
+        models::parse_item(&json).map_err(ApiError::Decode)
+    }
+
+    // POST /v1/sessions/{id}:cancel, which needs the session's current version
+    #[instrument]
+    pub async fn cancel_session(&self, session_id: &str) -> Result<SessionItem, ApiError> {
+        let session = self.read_session(session_id).await?;
+        let json = self
+            .post(&format!("/v1/sessions/{}:cancel", session_id), json!({ "version": session.version }))
+            .await?;
+        models::parse_item(&json).map_err(ApiError::Decode)
+    }
+}
@@ -0,0 +1,261 @@
+// Discovery cache in ~/.regis/cache/<server>/<user>.json
+//
+// Lets the target list appear instantly after login: the cached entry is shown while a
+// fresh discovery runs in the background, and the difference between the two is
+// reported to the frontend. Entries older than the configured TTL are ignored and
+// removed; logging out deletes the user's entry.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::{BoundaryScope, BoundaryTarget};
+
+pub const CACHE_DIR: &str = "cache";
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CachedDiscovery {
+    #[serde(default)]
+    pub scopes: Vec<BoundaryScope>,
+    #[serde(default)]
+    pub scopes_fetched_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub target_scope_id: Option<String>, // Scope the cached targets were discovered from
+    #[serde(default)]
+    pub targets: Vec<BoundaryTarget>,
+    #[serde(default)]
+    pub targets_fetched_at: Option<DateTime<Utc>>,
+}
+
+// What a refresh changed compared to the cached target list
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetDiff {
+    pub added: Vec<BoundaryTarget>,
+    pub removed: Vec<BoundaryTarget>,
+    pub changed: Vec<BoundaryTarget>, // New version of targets whose details differ
+}
+
+impl TargetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+// Compare two target lists by id
+pub fn diff_targets(cached: &[BoundaryTarget], fresh: &[BoundaryTarget]) -> TargetDiff {
+    let cached_by_id: HashMap<&str, &BoundaryTarget> = cached.iter().map(|t| (t.id.as_str(), t)).collect();
+    let fresh_by_id: HashMap<&str, &BoundaryTarget> = fresh.iter().map(|t| (t.id.as_str(), t)).collect();
+
+    let mut diff = TargetDiff::default();
+    for target in fresh {
+        match cached_by_id.get(target.id.as_str()) {
+            None => diff.added.push(target.clone()),
+            Some(previous) if *previous != target => diff.changed.push(target.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = cached
+        .iter()
+        .filter(|t| !fresh_by_id.contains_key(t.id.as_str()))
+        .cloned()
+        .collect();
+    diff
+}
+
+pub struct DiscoveryCache {
+    dir: Option<PathBuf>, // None disables caching
+    ttl: Duration,
+    write_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for DiscoveryCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiscoveryCache")
+            .field("dir", &self.dir)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl DiscoveryCache {
+    pub fn new(dir: PathBuf, ttl: Duration) -> Self {
+        DiscoveryCache { dir: Some(dir), ttl, write_lock: Mutex::new(()) }
+    }
+
+    pub fn disabled() -> Self {
+        DiscoveryCache { dir: None, ttl: Duration::ZERO, write_lock: Mutex::new(()) }
+    }
+
+    // Server and user ids become path components; keep them from escaping the cache directory
+    fn entry_path(&self, server_id: &str, user_id: &str) -> Option<PathBuf> {
+        let sanitize = |id: &str| -> String {
+            id.chars()
+                .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+                .collect()
+        };
+        let dir = self.dir.as_ref()?;
+        Some(dir.join(sanitize(server_id)).join(format!("{}.json", sanitize(user_id))))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    pub fn is_fresh(&self, fetched_at: Option<DateTime<Utc>>) -> bool {
+        let Some(fetched_at) = fetched_at else {
+            return false;
+        };
+        let age = Utc::now().signed_duration_since(fetched_at);
+        age.to_std().map(|age| age <= self.ttl).unwrap_or(true)
+    }
+
+    // The cached entry with expired sections emptied, or None when nothing usable is cached
+    pub fn load(&self, server_id: &str, user_id: &str) -> Option<CachedDiscovery> {
+        let path = self.entry_path(server_id, user_id)?;
+        let content = fs::read_to_string(&path).ok()?;
+
+        let mut entry: CachedDiscovery = match serde_json::from_str(&content) {
+            Ok(entry) => entry,
+            Err(e) => {
+                warn!("Discarding unreadable discovery cache {:?}: {}", path, e);
+                let _ = fs::remove_file(&path);
+                return None;
+            }
+        };
+
+        if !self.is_fresh(entry.scopes_fetched_at) {
+            entry.scopes.clear();
+            entry.scopes_fetched_at = None;
+        }
+        if !self.is_fresh(entry.targets_fetched_at) {
+            entry.targets.clear();
+            entry.targets_fetched_at = None;
+            entry.target_scope_id = None;
+        }
+
+        if entry.scopes_fetched_at.is_none() && entry.targets_fetched_at.is_none() {
+            debug!("Discovery cache for {} on {} has expired", user_id, server_id);
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        Some(entry)
+    }
+
+    // Apply `change` to the user's entry and write it back
+    pub fn update(
+        &self,
+        server_id: &str,
+        user_id: &str,
+        change: impl FnOnce(&mut CachedDiscovery),
+    ) -> Result<(), String> {
+        let Some(path) = self.entry_path(server_id, user_id) else {
+            return Ok(());
+        };
+
+        let _guard = self.write_lock.lock().unwrap();
+        let mut entry = self.load(server_id, user_id).unwrap_or_default();
+        change(&mut entry);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        let content = serde_json::to_string(&entry).map_err(|e| format!("Failed to serialize discovery cache: {}", e))?;
+        let temp_path = path.with_extension("json.tmp");
+        fs::write(&temp_path, content).map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))?;
+        fs::rename(&temp_path, &path).map_err(|e| format!("Failed to replace {:?}: {}", path, e))?;
+
+        debug!("Updated discovery cache {:?}", path);
+        Ok(())
+    }
+
+    pub fn remove(&self, server_id: &str, user_id: &str) {
+        let Some(path) = self.entry_path(server_id, user_id) else {
+            return;
+        };
+
+        let _guard = self.write_lock.lock().unwrap();
+        match fs::remove_file(&path) {
+            Ok(()) => info!("Removed discovery cache for {} on {}", user_id, server_id),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove discovery cache {:?}: {}", path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(id: &str, name: &str) -> BoundaryTarget {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "target_type": "tcp",
+            "description": "",
+            "address": null,
+            "default_port": 22,
+        }))
+        .unwrap()
+    }
+
+    fn temp_cache(name: &str, ttl: Duration) -> (DiscoveryCache, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("regis-cache-{}-{}", std::process::id(), name));
+        (DiscoveryCache::new(dir.clone(), ttl), dir)
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_targets() {
+        let cached = vec![target("ttcp_1", "db"), target("ttcp_2", "web"), target("ttcp_3", "old")];
+        let fresh = vec![target("ttcp_1", "db"), target("ttcp_2", "web-renamed"), target("ttcp_4", "new")];
+
+        let diff = diff_targets(&cached, &fresh);
+
+        assert_eq!(diff.added.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec!["ttcp_4"]);
+        assert_eq!(diff.removed.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), vec!["ttcp_3"]);
+        assert_eq!(diff.changed[0].name, "web-renamed");
+        assert!(diff_targets(&fresh, &fresh).is_empty());
+    }
+
+    #[test]
+    fn entries_round_trip_per_server_and_user() {
+        let (cache, dir) = temp_cache("round-trip", Duration::from_secs(3600));
+
+        cache
+            .update("dev/../prod", "u_1", |entry| {
+                entry.targets = vec![target("ttcp_1", "db")];
+                entry.targets_fetched_at = Some(Utc::now());
+            })
+            .unwrap();
+
+        assert_eq!(cache.load("dev/../prod", "u_1").unwrap().targets.len(), 1);
+        assert!(cache.load("dev/../prod", "u_2").is_none());
+        assert!(dir.join("dev____prod").join("u_1.json").is_file());
+
+        cache.remove("dev/../prod", "u_1");
+        assert!(cache.load("dev/../prod", "u_1").is_none());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn expired_entries_are_dropped() {
+        let (cache, dir) = temp_cache("expired", Duration::from_secs(60));
+
+        cache
+            .update("dev", "u_1", |entry| {
+                entry.targets = vec![target("ttcp_1", "db")];
+                entry.targets_fetched_at = Some(Utc::now() - chrono::Duration::minutes(5));
+            })
+            .unwrap();
+
+        assert!(cache.load("dev", "u_1").is_none());
+        assert!(!dir.join("dev").join("u_1.json").exists());
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
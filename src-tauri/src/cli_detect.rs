@@ -0,0 +1,415 @@
+// Boundary CLI discovery and version compatibility checks
+//
+// Candidates are tried in order: the server's explicit override, the configured
+// `boundary.cli_path`, then (when `boundary.auto_detect` is on) PATH, the bundled
+// resources and well-known install directories. The first candidate that runs and
+// meets `boundary.min_version` is chosen.
+
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::{debug, info, instrument, warn};
+
+use crate::{execute_boundary_command, BoundaryCommandOptions, BoundaryConfig};
+
+#[cfg(windows)]
+const CLI_BINARY_NAME: &str = "boundary.exe";
+#[cfg(not(windows))]
+const CLI_BINARY_NAME: &str = "boundary";
+
+// Directory (under the app resources) holding bundled CLI builds, one subdirectory per build
+const BUNDLED_CLI_DIR: &str = "boundary-cli";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CliSource {
+    ServerOverride,
+    Configured,
+    Path,
+    Bundled,
+    WellKnown,
+}
+
+impl fmt::Display for CliSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            CliSource::ServerOverride => "server-specific boundary_cli_path",
+            CliSource::Configured => "boundary.cli_path in config.json",
+            CliSource::Path => "PATH",
+            CliSource::Bundled => "bundled with Regis",
+            CliSource::WellKnown => "well-known install directory",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CliCandidate {
+    pub path: PathBuf,
+    pub source: CliSource,
+}
+
+// Outcome of checking one candidate, kept so the UI can explain the choice
+#[derive(Debug, Serialize, Clone)]
+pub struct CandidateCheck {
+    pub path: String,
+    pub source: CliSource,
+    pub version: Option<String>,
+    pub outcome: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BoundaryCliInfo {
+    pub path: String,
+    pub source: CliSource,
+    pub version: String,
+    pub revision: Option<String>,
+    pub minimum_version: String,
+    pub auto_detect: bool,
+    pub reason: String,
+    pub checked: Vec<CandidateCheck>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CliVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl CliVersion {
+    // Accepts "0.19.3", "v0.19.3", "0.19.3+ent" and "0.19" (patch defaults to 0)
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_start_matches('v');
+        let core = text.split(|c: char| c == '-' || c == '+' || c.is_whitespace()).next()?;
+        let mut parts = core.split('.');
+
+        Some(CliVersion {
+            major: parts.next()?.parse().ok()?,
+            minor: parts.next()?.parse().ok()?,
+            patch: parts.next().map(|p| p.parse().ok()).unwrap_or(Some(0))?,
+        })
+    }
+}
+
+impl fmt::Display for CliVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// Build the ordered candidate list for a server (or for the global default when no override)
+pub fn candidate_paths(
+    server_override: Option<&str>,
+    config: &BoundaryConfig,
+    resource_dir: Option<&Path>,
+) -> Vec<CliCandidate> {
+    let mut candidates = Vec::new();
+
+    // An explicit per-server override is authoritative: don't silently pick another binary
+    if let Some(path) = server_override {
+        candidates.push(CliCandidate {
+            path: PathBuf::from(path),
+            source: CliSource::ServerOverride,
+        });
+        return candidates;
+    }
+
+    // Relative configured paths are looked up in the resources first, then the working directory
+    let configured = PathBuf::from(&config.cli_path);
+    if configured.is_relative() {
+        if let Some(dir) = resource_dir {
+            candidates.push(CliCandidate {
+                path: dir.join(&configured),
+                source: CliSource::Configured,
+            });
+        }
+    }
+    candidates.push(CliCandidate {
+        path: configured,
+        source: CliSource::Configured,
+    });
+
+    if !config.auto_detect {
+        return candidates;
+    }
+
+    if let Some(path_var) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path_var) {
+            candidates.push(CliCandidate {
+                path: dir.join(CLI_BINARY_NAME),
+                source: CliSource::Path,
+            });
+        }
+    }
+
+    if let Some(dir) = resource_dir {
+        candidates.extend(bundled_candidates(&dir.join(BUNDLED_CLI_DIR)));
+    }
+
+    candidates.extend(well_known_dirs().into_iter().map(|dir| CliCandidate {
+        path: dir.join(CLI_BINARY_NAME),
+        source: CliSource::WellKnown,
+    }));
+
+    // Keep the first occurrence of each path
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|c| seen.insert(c.path.clone()));
+    candidates
+}
+
+// Bundled builds live in boundary-cli/<build>/boundary; newest version in the build name
+// first, builds without a recognisable version last
+fn bundled_candidates(bundle_dir: &Path) -> Vec<CliCandidate> {
+    let mut builds: Vec<(Option<CliVersion>, PathBuf)> = match std::fs::read_dir(bundle_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| (build_version(&entry.path()), entry.path().join(CLI_BINARY_NAME)))
+            .filter(|(_, path)| path.is_file())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    builds.sort_by(|a, b| b.cmp(a));
+    let mut builds: Vec<PathBuf> = builds.into_iter().map(|(_, path)| path).collect();
+
+    let direct = bundle_dir.join(CLI_BINARY_NAME);
+    if direct.is_file() {
+        builds.insert(0, direct);
+    }
+
+    builds
+        .into_iter()
+        .map(|path| CliCandidate {
+            path,
+            source: CliSource::Bundled,
+        })
+        .collect()
+}
+
+// Version in a build directory name such as "boundary_0.19.3_darwin_arm64"
+fn build_version(build_dir: &Path) -> Option<CliVersion> {
+    let name = build_dir.file_name()?.to_str()?;
+    name.split(['_', '-']).find_map(CliVersion::parse)
+}
+
+fn well_known_dirs() -> Vec<PathBuf> {
+    let mut dirs_list = Vec::new();
+
+    if cfg!(target_os = "macos") {
+        dirs_list.push(PathBuf::from("/opt/homebrew/bin"));
+        dirs_list.push(PathBuf::from("/usr/local/bin"));
+    } else if cfg!(target_os = "windows") {
+        for var in ["ProgramFiles", "ProgramFiles(x86)"] {
+            if let Some(dir) = std::env::var_os(var).map(PathBuf::from) {
+                dirs_list.push(dir.join("Boundary"));
+                dirs_list.push(dir.join("HashiCorp").join("Boundary"));
+            }
+        }
+        if let Some(dir) = std::env::var_os("ProgramData") {
+            dirs_list.push(PathBuf::from(dir).join("chocolatey").join("bin"));
+        }
+        if let Some(dir) = dirs::data_local_dir() {
+            dirs_list.push(dir.join("Programs").join("Boundary"));
+        }
+        if let Some(home) = dirs::home_dir() {
+            dirs_list.push(home.join("scoop").join("shims"));
+        }
+    } else {
+        dirs_list.push(PathBuf::from("/usr/local/bin"));
+        dirs_list.push(PathBuf::from("/usr/bin"));
+        dirs_list.push(PathBuf::from("/snap/bin"));
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        dirs_list.push(home.join(".local").join("bin"));
+        dirs_list.push(home.join("bin"));
+    }
+
+    dirs_list
+}
+
+// Parse `boundary version -format json`, falling back to the plain-text "Version Number: x.y.z"
+pub fn parse_version_output(output: &str) -> Option<(CliVersion, Option<String>)> {
+    if let Ok(json) = serde_json::from_str::<serde_json::Value>(output) {
+        let version = json["version"].as_str().or_else(|| json["Version"].as_str());
+        if let Some(version) = version.and_then(CliVersion::parse) {
+            let revision = json["revision"]
+                .as_str()
+                .or_else(|| json["Revision"].as_str())
+                .map(|r| r.to_string());
+            return Some((version, revision));
+        }
+    }
+
+    output
+        .lines()
+        .filter(|line| line.to_lowercase().contains("version"))
+        .find_map(|line| line.split_whitespace().find_map(CliVersion::parse))
+        .map(|version| (version, None))
+}
+
+// Run a candidate's `version` command and parse the result
+async fn probe_version(
+    path: &str,
+    cli_options: &BoundaryCommandOptions,
+) -> Result<(CliVersion, Option<String>), String> {
+    let result = execute_boundary_command(path, vec!["version", "-format", "json"], None, cli_options).await?;
+    if result.success {
+        if let Some(parsed) = parse_version_output(&result.stdout) {
+            return Ok(parsed);
+        }
+    }
+
+    // Older CLIs may not support -format json on `version`
+    let result = execute_boundary_command(path, vec!["version"], None, cli_options).await?;
+    if !result.success {
+        return Err(format!("`version` exited with an error: {}", result.stderr.trim()));
+    }
+
+    parse_version_output(&result.stdout)
+        .ok_or_else(|| format!("Could not parse version from output: {}", result.stdout.trim()))
+}
+
+// Check candidates in order and return the first usable CLI, with a record of every check
+#[instrument(skip(candidates, cli_options))]
+pub async fn detect_boundary_cli(
+    candidates: Vec<CliCandidate>,
+    minimum_version: &str,
+    auto_detect: bool,
+    cli_options: &BoundaryCommandOptions,
+) -> Result<BoundaryCliInfo, String> {
+    let minimum = CliVersion::parse(minimum_version)
+        .ok_or_else(|| format!("Invalid boundary.min_version '{}' in configuration", minimum_version))?;
+    info!("Looking for a Boundary CLI >= {} among {} candidate(s)", minimum, candidates.len());
+
+    let mut checked = Vec::new();
+
+    for candidate in candidates {
+        let path = candidate.path.to_string_lossy().to_string();
+
+        // Bare names (e.g. "boundary") are resolved by the OS; anything else must exist
+        let is_bare_name = candidate.path.components().count() == 1;
+        if !is_bare_name && !candidate.path.is_file() {
+            debug!("Boundary CLI candidate not found: {}", path);
+            checked.push(CandidateCheck {
+                path,
+                source: candidate.source,
+                version: None,
+                outcome: "not found".to_string(),
+            });
+            continue;
+        }
+
+        match probe_version(&path, cli_options).await {
+            Ok((version, revision)) if version >= minimum => {
+                let reason = format!(
+                    "Using Boundary CLI {} from {} ({}); minimum required is {}",
+                    version, candidate.source, path, minimum
+                );
+                info!("{}", reason);
+                checked.push(CandidateCheck {
+                    path: path.clone(),
+                    source: candidate.source,
+                    version: Some(version.to_string()),
+                    outcome: "selected".to_string(),
+                });
+                return Ok(BoundaryCliInfo {
+                    path,
+                    source: candidate.source,
+                    version: version.to_string(),
+                    revision,
+                    minimum_version: minimum.to_string(),
+                    auto_detect,
+                    reason,
+                    checked,
+                });
+            }
+            Ok((version, _)) => {
+                warn!("Boundary CLI at {} is version {}, older than required {}", path, version, minimum);
+                checked.push(CandidateCheck {
+                    path,
+                    source: candidate.source,
+                    version: Some(version.to_string()),
+                    outcome: format!("too old (requires >= {})", minimum),
+                });
+            }
+            Err(e) => {
+                warn!("Boundary CLI candidate {} is not usable: {}", path, e);
+                checked.push(CandidateCheck {
+                    path,
+                    source: candidate.source,
+                    version: None,
+                    outcome: format!("not usable: {}", e),
+                });
+            }
+        }
+    }
+
+    let summary: Vec<String> = checked
+        .iter()
+        .filter(|c| c.outcome != "not found")
+        .map(|c| format!("{} ({}): {}", c.path, c.source, c.outcome))
+        .collect();
+
+    Err(format!(
+        "No usable Boundary CLI >= {} found (auto-detect {}). {}",
+        minimum,
+        if auto_detect { "on" } else { "off" },
+        if summary.is_empty() {
+            "No candidate binary exists.".to_string()
+        } else {
+            format!("Checked: {}", summary.join("; "))
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(major: u64, minor: u64, patch: u64) -> CliVersion {
+        CliVersion { major, minor, patch }
+    }
+
+    #[test]
+    fn parses_versions() {
+        assert_eq!(CliVersion::parse("0.19.3"), Some(version(0, 19, 3)));
+        assert_eq!(CliVersion::parse(" v0.13.2+ent "), Some(version(0, 13, 2)));
+        assert_eq!(CliVersion::parse("0.9-dev"), Some(version(0, 9, 0)));
+        assert_eq!(CliVersion::parse("darwin"), None);
+        assert_eq!(CliVersion::parse("0.x.1"), None);
+        assert!(version(0, 9, 0) < version(0, 19, 3));
+    }
+
+    #[test]
+    fn parses_version_output() {
+        let json = r#"{"revision": "a1b2c3", "version": "0.19.3", "version_metadata": ""}"#;
+        assert_eq!(parse_version_output(json), Some((version(0, 19, 3), Some("a1b2c3".to_string()))));
+
+        let text = "\nVersion information:\n  Git Revision:        d8fa6c9\n  Version Number:      0.13.2\n";
+        assert_eq!(parse_version_output(text), Some((version(0, 13, 2), None)));
+
+        assert_eq!(parse_version_output("command not found"), None);
+    }
+
+    #[test]
+    fn orders_bundled_builds_by_version() {
+        let dir = std::env::temp_dir().join(format!("regis-bundled-cli-{}", std::process::id()));
+        for build in ["boundary_0.9.0_linux_amd64", "boundary_0.19.3_linux_amd64", "custom", "boundary_0.13.2_linux_amd64"] {
+            std::fs::create_dir_all(dir.join(build)).unwrap();
+            std::fs::write(dir.join(build).join(CLI_BINARY_NAME), "").unwrap();
+        }
+        std::fs::create_dir_all(dir.join("boundary_0.20.0_empty")).unwrap();
+
+        let builds: Vec<String> = bundled_candidates(&dir)
+            .iter()
+            .map(|candidate| candidate.path.parent().unwrap().file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(
+            builds,
+            vec!["boundary_0.19.3_linux_amd64", "boundary_0.13.2_linux_amd64", "boundary_0.9.0_linux_amd64", "custom"]
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
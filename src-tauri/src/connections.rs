@@ -0,0 +1,550 @@
+// Long-running `boundary connect` proxies
+//
+// `boundary connect` prints where its local listener is and then keeps proxying until the
+// session ends or the process is killed, so it can't be awaited like other CLI commands.
+// A ConnectionProcess owns one running proxy: it is handed out as soon as the startup
+// record has been read, kept in AppState.active_connections for as long as the connection
+// is in use, and kills the proxy's process tree when terminated.
+//
+// The proxy runs with `-format json`, so its stdout is one JSON object per line: the
+// startup record first, then an event whenever a connection is made or the session ends.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+use crate::{kill_process_tree, read_pipe, BoundaryCommandError, BoundaryConnection};
+
+// How long a proxy that closed its output gets to exit before it is killed
+const EXIT_GRACE: Duration = Duration::from_secs(2);
+// How long a stopped proxy gets to go away before terminating reports a failure
+const STOP_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Where `boundary connect` listens unless told otherwise
+const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1";
+
+// Local address and port for a proxy. Whatever is unset is left to the CLI, which listens
+// on 127.0.0.1 and picks a free port (or the target's default client port).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListenSettings {
+    pub listen_addr: Option<String>,
+    pub listen_port: Option<u16>,
+}
+
+impl ListenSettings {
+    pub(crate) fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(addr) = &self.listen_addr {
+            args.extend(["-listen-addr".to_string(), addr.clone()]);
+        }
+        if let Some(port) = self.listen_port {
+            args.extend(["-listen-port".to_string(), port.to_string()]);
+        }
+        args
+    }
+}
+
+// The record the proxy prints once it accepts local connections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProxyStartup {
+    pub address: String,
+    pub port: u16,
+    pub protocol: String,
+    #[serde(default)]
+    pub expiration: Option<String>,
+    #[serde(default = "unlimited")]
+    pub connection_limit: i32, // -1 for no limit
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+fn unlimited() -> i32 {
+    -1
+}
+
+// What the proxy reports after startup
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProxyEvent {
+    ConnectionsLeft { connections_left: i32 }, // After each new connection; -1 for no limit
+    SessionTerminated { termination_reason: String },
+}
+
+// A proxy that has reported its listener, before it is tied to a connection
+pub(crate) struct StartedProxy {
+    pub startup: ProxyStartup,
+    child: Child,
+    stderr: JoinHandle<String>,
+    events: mpsc::UnboundedReceiver<ProxyEvent>,
+}
+
+// What happens to an established connection over its lifetime, as told to the frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ConnectionEvent {
+    ProxyReady {
+        local_address: String,
+        local_port: u16,
+        expiration_time: Option<String>,
+        connection_limit: i32,
+    },
+    ClientConnected { connections_left: i32 }, // -1 when the session has no connection limit
+    SessionExpiring { seconds_left: i64 },
+    SessionExpired,                       // The session reached its maximum duration
+    SessionCancelled { reason: String },  // Cancelled on the controller, by an admin or another client
+    SessionTerminated { reason: String }, // Any other end, such as an exhausted connection limit
+    ProxyExited { exit_code: Option<i32> },
+}
+
+impl ConnectionEvent {
+    // BoundaryConnection.status after this event, if it changes
+    pub fn status(&self) -> Option<&'static str> {
+        match self {
+            ConnectionEvent::ProxyReady { .. } => Some("active"),
+            ConnectionEvent::ClientConnected { .. } => None,
+            ConnectionEvent::SessionExpiring { .. } => Some("expiring"),
+            ConnectionEvent::SessionExpired => Some("expired"),
+            ConnectionEvent::SessionCancelled { .. } => Some("cancelled"),
+            ConnectionEvent::SessionTerminated { .. } => Some("terminated"),
+            ConnectionEvent::ProxyExited { .. } => Some("exited"),
+        }
+    }
+}
+
+// Boundary's termination reasons: "timed out" when the session expires, "canceled" when it
+// is cancelled on the controller, others such as "connection limit" for the rest
+fn termination_event(reason: String) -> ConnectionEvent {
+    match reason.as_str() {
+        "timed out" => ConnectionEvent::SessionExpired,
+        "canceled" | "cancelled" => ConnectionEvent::SessionCancelled { reason },
+        _ => ConnectionEvent::SessionTerminated { reason },
+    }
+}
+
+// Whether a proxy is still running, and how it ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProxyState {
+    Running,
+    Exited(Option<i32>), // Exit code; None when it was killed by a signal
+}
+
+pub struct ConnectionProcess {
+    pub connection: BoundaryConnection,
+    pid: Option<u32>,
+    stop: Option<oneshot::Sender<()>>, // Dropping it stops the proxy as well
+    state: watch::Receiver<ProxyState>,
+    events: Option<mpsc::UnboundedReceiver<ProxyEvent>>,
+}
+
+impl std::fmt::Debug for ConnectionProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionProcess")
+            .field("connection", &self.connection)
+            .field("pid", &self.pid)
+            .field("state", &*self.state.borrow())
+            .finish()
+    }
+}
+
+impl ConnectionProcess {
+    // Hand the proxy to a task that waits for it to exit, or kills it once asked to stop
+    pub(crate) fn new(connection: BoundaryConnection, proxy: StartedProxy) -> Self {
+        let StartedProxy { mut child, stderr, events, .. } = proxy;
+        let pid = child.id();
+        let (stop, stopped) = oneshot::channel::<()>();
+        let (state_sender, state) = watch::channel(ProxyState::Running);
+        let session_id = connection.session_id.clone();
+
+        tokio::spawn(async move {
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = stopped => {
+                    kill_process_tree(&mut child).await;
+                    child.wait().await
+                }
+            };
+
+            let exit_code = match status {
+                Ok(status) => {
+                    info!("Proxy for session {} exited with {}", session_id, status);
+                    status.code()
+                }
+                Err(e) => {
+                    warn!("Failed to wait for the proxy of session {}: {}", session_id, e);
+                    None
+                }
+            };
+            let stderr = stderr.await.unwrap_or_default();
+            if !stderr.trim().is_empty() {
+                debug!("Proxy for session {} wrote to stderr: {}", session_id, stderr.trim());
+            }
+            let _ = state_sender.send(ProxyState::Exited(exit_code));
+        });
+
+        ConnectionProcess { connection, pid, stop: Some(stop), state, events: Some(events) }
+    }
+
+    pub fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    // The proxy's events from startup on; can be taken once
+    pub fn take_events(&mut self) -> Option<mpsc::UnboundedReceiver<ProxyEvent>> {
+        self.events.take()
+    }
+
+    // Follows the proxy until it exits, even once this ConnectionProcess is gone
+    pub fn watch_state(&self) -> watch::Receiver<ProxyState> {
+        self.state.clone()
+    }
+
+    // Whether the proxy has stopped on its own, e.g. because the session ended
+    pub fn has_exited(&self) -> bool {
+        matches!(*self.state.borrow(), ProxyState::Exited(_))
+    }
+
+    // Stop the proxy and anything it spawned
+    pub async fn terminate(mut self) -> Result<(), String> {
+        info!("Stopping proxy for session {}", self.connection.session_id);
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+
+        let exited = self.state.wait_for(|state| matches!(state, ProxyState::Exited(_)));
+        match tokio::time::timeout(STOP_TIMEOUT, exited).await {
+            Ok(Ok(_)) => Ok(()),
+            _ => Err(format!("Proxy for session {} is still running", self.connection.session_id)),
+        }
+    }
+}
+
+// Report a connection's life from ProxyReady to ProxyExited: the proxy's own events, a
+// warning `expiry_warning` before the session expires, and the proxy's exit. Uses the
+// process's events, so it returns None when they were already taken.
+pub fn follow_connection(
+    process: &mut ConnectionProcess,
+    expiry_warning: Duration,
+    on_event: impl Fn(ConnectionEvent) + Send + Sync + 'static,
+) -> Option<JoinHandle<()>> {
+    let mut events = process.take_events()?;
+    let mut state = process.watch_state();
+    let connection = process.connection.clone();
+
+    let expiration = connection
+        .expiration_time
+        .as_deref()
+        .and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&chrono::Utc));
+    let warn_at = expiration.map(|expiration| {
+        let until_warning = (expiration - chrono::Utc::now()).to_std().unwrap_or_default().saturating_sub(expiry_warning);
+        tokio::time::Instant::now() + until_warning
+    });
+
+    let report = move |event: ProxyEvent| match event {
+        ProxyEvent::ConnectionsLeft { connections_left } => ConnectionEvent::ClientConnected { connections_left },
+        ProxyEvent::SessionTerminated { termination_reason } => termination_event(termination_reason),
+    };
+
+    Some(tokio::spawn(async move {
+        on_event(ConnectionEvent::ProxyReady {
+            local_address: connection.local_address.clone(),
+            local_port: connection.local_port,
+            expiration_time: connection.expiration_time.clone(),
+            connection_limit: connection.connection_limit,
+        });
+
+        let mut events_open = true;
+        let mut warned = warn_at.is_none();
+        loop {
+            tokio::select! {
+                event = events.recv(), if events_open => match event {
+                    Some(event) => on_event(report(event)),
+                    None => events_open = false,
+                },
+                _ = tokio::time::sleep_until(warn_at.unwrap_or_else(tokio::time::Instant::now)), if !warned => {
+                    warned = true;
+                    let seconds_left = expiration.map(|e| (e - chrono::Utc::now()).num_seconds().max(0)).unwrap_or(0);
+                    on_event(ConnectionEvent::SessionExpiring { seconds_left });
+                }
+                exit_code = exited(&mut state) => {
+                    // What the proxy printed before exiting comes first
+                    if events_open {
+                        let _ = tokio::time::timeout(EXIT_GRACE, async {
+                            while let Some(event) = events.recv().await {
+                                on_event(report(event));
+                            }
+                        })
+                        .await;
+                    }
+                    on_event(ConnectionEvent::ProxyExited { exit_code });
+                    break;
+                }
+            }
+        }
+    }))
+}
+
+// The proxy's exit code, once it has exited
+async fn exited(state: &mut watch::Receiver<ProxyState>) -> Option<i32> {
+    match state.wait_for(|state| matches!(state, ProxyState::Exited(_))).await.as_deref() {
+        Ok(ProxyState::Exited(code)) => *code,
+        _ => None,
+    }
+}
+
+// Spawn the proxy and wait for its startup record; the proxy is killed when the deadline
+// expires or `cancel` fires first
+pub(crate) async fn start_proxy(
+    mut cmd: Command,
+    command_str: &str,
+    timeout: Duration,
+    cancel: Option<CancellationToken>,
+) -> Result<StartedProxy, String> {
+    let mut child = cmd.spawn().map_err(|e| {
+        let error = BoundaryCommandError::Spawn {
+            command: command_str.to_string(),
+            message: e.to_string(),
+        };
+        error!("{}", error);
+        error.to_string()
+    })?;
+    debug!("Proxy started (pid {:?}), waiting up to {:?} for it to listen", child.id(), timeout);
+
+    let stderr = read_pipe(child.stderr.take());
+    let mut lines = BufReader::new(child.stdout.take().expect("proxy stdout is piped")).lines();
+    let mut output = String::new();
+
+    let cancel = cancel.unwrap_or_default();
+    let deadline = tokio::time::sleep(timeout);
+    tokio::pin!(deadline);
+
+    let startup = loop {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => {
+                    debug!("boundary connect: {}", line);
+                    if let Some(startup) = parse_startup(&line) {
+                        break startup;
+                    }
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+                // Output closed: the proxy is exiting without having listened
+                _ => {
+                    let status = match tokio::time::timeout(EXIT_GRACE, child.wait()).await {
+                        Ok(Ok(status)) => status.to_string(),
+                        _ => {
+                            kill_process_tree(&mut child).await;
+                            "killed".to_string()
+                        }
+                    };
+                    let stderr = stderr.await.unwrap_or_default();
+                    let reason = if stderr.trim().is_empty() { output.trim() } else { stderr.trim() };
+                    let error_msg = format!("boundary connect exited ({}) before listening: {}", status, reason);
+                    error!("{}", error_msg);
+                    return Err(error_msg);
+                }
+            },
+            _ = &mut deadline => {
+                kill_process_tree(&mut child).await;
+                let error = BoundaryCommandError::TimedOut {
+                    command: command_str.to_string(),
+                    timeout_seconds: timeout.as_secs(),
+                };
+                error!("{}", error);
+                return Err(error.to_string());
+            }
+            _ = cancel.cancelled() => {
+                kill_process_tree(&mut child).await;
+                let error = BoundaryCommandError::Cancelled { command: command_str.to_string() };
+                warn!("{}", error);
+                return Err(error.to_string());
+            }
+        }
+    };
+
+    info!("Proxy listening on {}:{} ({})", startup.address, startup.port, startup.protocol);
+
+    // Keep draining stdout so the proxy never blocks on a full pipe; nobody listening for
+    // events is fine, they are logged either way
+    let (sender, events) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok(Some(line)) = lines.next_line().await {
+            match parse_event(&line) {
+                Some(event) => {
+                    info!("Proxy event: {:?}", event);
+                    let _ = sender.send(event);
+                }
+                None => debug!("boundary connect: {}", line),
+            }
+        }
+    });
+
+    Ok(StartedProxy { startup, child, stderr, events })
+}
+
+// Fail early when a fixed listen port is taken, naming the process that holds it when the
+// platform tells us; a random port needs no check
+pub async fn check_listen_port(listen: &ListenSettings) -> Result<(), String> {
+    let Some(port) = listen.listen_port else {
+        return Ok(());
+    };
+    let addr = listen.listen_addr.as_deref().unwrap_or(DEFAULT_LISTEN_ADDR);
+
+    match std::net::TcpListener::bind((addr, port)) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => {
+            let owner = match port_owner(port).await {
+                Some(owner) => format!(" by {}", owner),
+                None => String::new(),
+            };
+            let error_msg = format!("Local port {}:{} is already in use{}", addr, port, owner);
+            warn!("{}", error_msg);
+            Err(error_msg)
+        }
+        Err(e) => Err(format!("Cannot listen on {}:{}: {}", addr, port, e)),
+    }
+}
+
+// "name (pid N)" of the process listening on `port`
+#[cfg(unix)]
+async fn port_owner(port: u16) -> Option<String> {
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-Fpc"])
+        .output()
+        .await
+        .map_err(|e| debug!("lsof unavailable: {}", e))
+        .ok()?;
+    parse_lsof(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(windows)]
+async fn port_owner(port: u16) -> Option<String> {
+    let netstat = Command::new("netstat").args(["-ano", "-p", "TCP"]).output().await.ok()?;
+    let pid = parse_netstat(&String::from_utf8_lossy(&netstat.stdout), port)?;
+
+    // tasklist prints "name.exe","pid",... as CSV
+    let tasklist = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .await
+        .ok()?;
+    let name = String::from_utf8_lossy(&tasklist.stdout)
+        .split(',')
+        .next()
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .filter(|name| !name.is_empty() && !name.starts_with("INFO:"));
+    Some(match name {
+        Some(name) => format!("{} (pid {})", name, pid),
+        None => format!("pid {}", pid),
+    })
+}
+
+#[cfg(not(any(unix, windows)))]
+async fn port_owner(_port: u16) -> Option<String> {
+    None
+}
+
+// `lsof -F pc` prints one field per line: "p<pid>" then "c<command>"
+#[cfg(any(unix, test))]
+fn parse_lsof(output: &str) -> Option<String> {
+    let pid = output.lines().find_map(|line| line.strip_prefix('p'))?;
+    Some(match output.lines().find_map(|line| line.strip_prefix('c')) {
+        Some(name) => format!("{} (pid {})", name, pid),
+        None => format!("pid {}", pid),
+    })
+}
+
+// Pid from the LISTENING row for the port: "  TCP  127.0.0.1:3389  0.0.0.0:0  LISTENING  1234"
+#[cfg(any(windows, test))]
+fn parse_netstat(output: &str, port: u16) -> Option<u32> {
+    let suffix = format!(":{}", port);
+    output.lines().find_map(|line| {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        match columns.as_slice() {
+            [_, local, _, "LISTENING", pid] if local.ends_with(&suffix) => pid.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+// The startup record, or None for any other line (warnings, older CLI banners)
+fn parse_startup(line: &str) -> Option<ProxyStartup> {
+    serde_json::from_str::<ProxyStartup>(line.trim())
+        .ok()
+        .filter(|startup| startup.port != 0)
+}
+
+fn parse_event(line: &str) -> Option<ProxyEvent> {
+    serde_json::from_str(line.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_startup_record() {
+        let startup = parse_startup(
+            r#"{"address":"::1","port":55389,"protocol":"tcp","expiration":"2025-03-01T18:05:00Z","connection_limit":1,"session_id":"s_1"}"#,
+        )
+        .unwrap();
+        assert_eq!(startup.address, "::1");
+        assert_eq!(startup.port, 55389);
+        assert_eq!(startup.connection_limit, 1);
+        assert_eq!(startup.expiration.as_deref(), Some("2025-03-01T18:05:00Z"));
+
+        let minimal = parse_startup(r#"{"address":"127.0.0.1","port":61991,"protocol":"tcp"}"#).unwrap();
+        assert_eq!(minimal.connection_limit, -1);
+
+        assert_eq!(parse_startup(r#"{"address":"127.0.0.1","port":0,"protocol":"tcp"}"#), None);
+        assert_eq!(parse_startup("Proxy listening information:"), None);
+        assert_eq!(parse_startup(r#"{"connections_left":0}"#), None);
+    }
+
+    #[test]
+    fn names_the_process_holding_a_port() {
+        assert_eq!(parse_lsof("p4242\ncsshd\nf3\n").as_deref(), Some("sshd (pid 4242)"));
+        assert_eq!(parse_lsof("p4242\n").as_deref(), Some("pid 4242"));
+        assert_eq!(parse_lsof(""), None);
+
+        let netstat = "\n  Proto  Local Address          Foreign Address        State           PID\n  \
+                       TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1024\n  \
+                       TCP    127.0.0.1:33890        127.0.0.1:50000        ESTABLISHED     77\n  \
+                       TCP    127.0.0.1:3389         0.0.0.0:0              LISTENING       5150\n";
+        assert_eq!(parse_netstat(netstat, 3389), Some(5150));
+        assert_eq!(parse_netstat(netstat, 33890), None);
+    }
+
+    #[test]
+    fn parses_follow_up_events() {
+        assert_eq!(parse_event(r#"{"connections_left":2}"#), Some(ProxyEvent::ConnectionsLeft { connections_left: 2 }));
+        assert_eq!(
+            parse_event(r#"{"termination_reason":"canceled"}"#),
+            Some(ProxyEvent::SessionTerminated { termination_reason: "canceled".to_string() })
+        );
+        assert_eq!(parse_event("  Port:  55389"), None);
+    }
+
+    #[test]
+    fn tells_expiry_and_cancellation_apart() {
+        assert_eq!(termination_event("timed out".to_string()), ConnectionEvent::SessionExpired);
+        assert_eq!(termination_event("canceled".to_string()).status(), Some("cancelled"));
+        assert_eq!(
+            termination_event("connection limit".to_string()),
+            ConnectionEvent::SessionTerminated { reason: "connection limit".to_string() }
+        );
+    }
+
+    #[test]
+    fn connection_events_are_tagged_for_the_frontend() {
+        let event = serde_json::to_value(ConnectionEvent::ProxyExited { exit_code: Some(1) }).unwrap();
+        assert_eq!(event, serde_json::json!({"event": "proxy_exited", "exit_code": 1}));
+        assert_eq!(ConnectionEvent::ClientConnected { connections_left: 0 }.status(), None);
+    }
+}
@@ -0,0 +1,655 @@
+// Staged reachability diagnostics for a Boundary server
+//
+// Probes the server URL one layer at a time (DNS, TCP, TLS, controller HTTP API) so
+// support staff can tell which layer is failing. A failed stage skips the stages after it.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{
+    self,
+    client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName,
+};
+use tokio_rustls::TlsConnector;
+use tracing::{debug, info, instrument, warn};
+use url::Url;
+use x509_parser::extensions::GeneralName;
+
+use crate::boundary_api::{ApiError, BoundaryApiClient};
+use crate::{Config, Server};
+
+// Certificates expiring sooner than this are reported as a warning
+const CERT_EXPIRY_WARNING_DAYS: i64 = 14;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiagnosticStep {
+    pub name: String,
+    pub status: StepStatus,
+    pub duration_ms: u64,
+    pub summary: String,
+    pub details: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiagnosticReport {
+    pub server_id: String,
+    pub server_name: String,
+    pub url: String,
+    pub started_at: String,
+    pub status: StepStatus, // Worst status across all steps
+    pub steps: Vec<DiagnosticStep>,
+    pub report: String,     // Plain-text rendering for copy/paste into a support ticket
+}
+
+impl DiagnosticStep {
+    fn new(name: &str, status: StepStatus, started: Instant, summary: String, details: Vec<String>) -> Self {
+        DiagnosticStep {
+            name: name.to_string(),
+            status,
+            duration_ms: started.elapsed().as_millis() as u64,
+            summary,
+            details,
+        }
+    }
+
+    fn skipped(name: &str) -> Self {
+        DiagnosticStep {
+            name: name.to_string(),
+            status: StepStatus::Skipped,
+            duration_ms: 0,
+            summary: "Skipped because a previous step failed".to_string(),
+            details: Vec::new(),
+        }
+    }
+}
+
+// Run every diagnostic stage against the server and build the report
+#[instrument(skip(config))]
+pub async fn diagnose_server(server: &Server, config: &Config) -> DiagnosticReport {
+    info!("Running reachability diagnostics for server {} ({})", server.name, server.url);
+
+    let started_at = Utc::now().to_rfc3339();
+    let probe_timeout = Duration::from_secs(config.connection.connection_timeout_seconds.max(1) as u64);
+    let mut steps = Vec::new();
+
+    // Stage 0: the URL itself
+    let started = Instant::now();
+    let target = match parse_server_url(&server.url) {
+        Ok(target) => {
+            steps.push(DiagnosticStep::new(
+                "URL",
+                StepStatus::Ok,
+                started,
+                format!("{}://{}:{}", target.scheme, target.host, target.port),
+                Vec::new(),
+            ));
+            Some(target)
+        }
+        Err(e) => {
+            steps.push(DiagnosticStep::new("URL", StepStatus::Failed, started, e, Vec::new()));
+            None
+        }
+    };
+
+    // Stage 1: DNS
+    let addresses = match &target {
+        Some(target) => {
+            let (step, addresses) = resolve_dns(target, probe_timeout).await;
+            steps.push(step);
+            addresses
+        }
+        None => {
+            steps.push(DiagnosticStep::skipped("DNS resolution"));
+            Vec::new()
+        }
+    };
+
+    // Stage 2: TCP
+    let tcp_stream = match (&target, addresses.is_empty()) {
+        (Some(_), false) => {
+            let (step, stream) = connect_tcp(&addresses, probe_timeout).await;
+            steps.push(step);
+            stream
+        }
+        _ => {
+            steps.push(DiagnosticStep::skipped("TCP connection"));
+            None
+        }
+    };
+
+    // Stage 3: TLS (only meaningful for https URLs)
+    let tls_ok = match (&target, tcp_stream) {
+        (Some(target), Some(stream)) if target.scheme == "https" => {
+            let step = handshake_tls(target, stream, config.security.ssl_verify, probe_timeout).await;
+            let ok = step.status != StepStatus::Failed;
+            steps.push(step);
+            ok
+        }
+        (Some(_), Some(_)) => {
+            steps.push(DiagnosticStep::new(
+                "TLS handshake",
+                StepStatus::Warning,
+                Instant::now(),
+                "Server URL uses plain HTTP; traffic to the controller is not encrypted".to_string(),
+                Vec::new(),
+            ));
+            true
+        }
+        _ => {
+            steps.push(DiagnosticStep::skipped("TLS handshake"));
+            false
+        }
+    };
+
+    // Stage 4: controller API
+    if tls_ok {
+        steps.push(probe_controller(server, config).await);
+    } else {
+        steps.push(DiagnosticStep::skipped("Controller API"));
+    }
+
+    let status = steps
+        .iter()
+        .map(|s| s.status)
+        .max_by_key(|s| severity(*s))
+        .unwrap_or(StepStatus::Ok);
+
+    let mut report = DiagnosticReport {
+        server_id: server.id.clone(),
+        server_name: server.name.clone(),
+        url: server.url.clone(),
+        started_at,
+        status,
+        steps,
+        report: String::new(),
+    };
+    report.report = render_text(&report);
+
+    info!("Diagnostics for server {} finished with status {:?}", server.name, report.status);
+    debug!("Diagnostic report:\n{}", report.report);
+    report
+}
+
+fn severity(status: StepStatus) -> u8 {
+    match status {
+        StepStatus::Ok => 0,
+        StepStatus::Skipped => 1,
+        StepStatus::Warning => 2,
+        StepStatus::Failed => 3,
+    }
+}
+
+struct ProbeTarget {
+    scheme: String,
+    host: String,
+    port: u16,
+}
+
+fn parse_server_url(server_url: &str) -> Result<ProbeTarget, String> {
+    let url = Url::parse(server_url).map_err(|e| format!("Invalid server URL '{}': {}", server_url, e))?;
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("Server URL '{}' has no host", server_url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format!("Server URL '{}' has no port and an unknown scheme", server_url))?;
+
+    Ok(ProbeTarget {
+        scheme: url.scheme().to_string(),
+        host,
+        port,
+    })
+}
+
+async fn resolve_dns(target: &ProbeTarget, timeout: Duration) -> (DiagnosticStep, Vec<SocketAddr>) {
+    let name = "DNS resolution";
+    let started = Instant::now();
+
+    let lookup = tokio::net::lookup_host((target.host.as_str(), target.port));
+    match tokio::time::timeout(timeout, lookup).await {
+        Ok(Ok(addresses)) => {
+            let addresses: Vec<SocketAddr> = addresses.collect();
+            if addresses.is_empty() {
+                let step = DiagnosticStep::new(
+                    name,
+                    StepStatus::Failed,
+                    started,
+                    format!("{} resolved to no addresses", target.host),
+                    Vec::new(),
+                );
+                return (step, addresses);
+            }
+
+            let details = addresses.iter().map(|a| format!("Address: {}", a.ip())).collect();
+            let step = DiagnosticStep::new(
+                name,
+                StepStatus::Ok,
+                started,
+                format!("{} resolved to {} address(es)", target.host, addresses.len()),
+                details,
+            );
+            (step, addresses)
+        }
+        Ok(Err(e)) => (
+            DiagnosticStep::new(
+                name,
+                StepStatus::Failed,
+                started,
+                format!("Could not resolve {}: {}", target.host, e),
+                vec!["Check VPN/DNS settings and that the server name is spelled correctly".to_string()],
+            ),
+            Vec::new(),
+        ),
+        Err(_) => (
+            DiagnosticStep::new(
+                name,
+                StepStatus::Failed,
+                started,
+                format!("DNS lookup for {} timed out after {}s", target.host, timeout.as_secs()),
+                Vec::new(),
+            ),
+            Vec::new(),
+        ),
+    }
+}
+
+// Try each resolved address in turn and keep the first connection that succeeds
+async fn connect_tcp(addresses: &[SocketAddr], timeout: Duration) -> (DiagnosticStep, Option<TcpStream>) {
+    let name = "TCP connection";
+    let started = Instant::now();
+    let mut details = Vec::new();
+
+    for address in addresses {
+        let attempt = Instant::now();
+        match tokio::time::timeout(timeout, TcpStream::connect(address)).await {
+            Ok(Ok(stream)) => {
+                let latency = attempt.elapsed().as_millis();
+                details.push(format!("Connected to {} in {} ms", address, latency));
+                let step = DiagnosticStep::new(
+                    name,
+                    StepStatus::Ok,
+                    started,
+                    format!("Port {} is open on {} ({} ms)", address.port(), address.ip(), latency),
+                    details,
+                );
+                return (step, Some(stream));
+            }
+            Ok(Err(e)) => details.push(format!("{}: {}", address, e)),
+            Err(_) => details.push(format!("{}: no answer within {}s", address, timeout.as_secs())),
+        }
+    }
+
+    let step = DiagnosticStep::new(
+        name,
+        StepStatus::Failed,
+        started,
+        "Could not open a TCP connection to any resolved address".to_string(),
+        details,
+    );
+    (step, None)
+}
+
+// Verifies certificates with the normal WebPKI rules but records the outcome instead of
+// aborting the handshake, so certificate details can be reported even when they're invalid.
+// Trust is checked against the bundled WebPKI roots and the OS trust store, so controllers
+// behind a corporate or private CA the system already trusts are reported as trusted.
+struct RecordingVerifier {
+    inner: WebPkiVerifier,
+    outcome: Mutex<Option<Result<(), String>>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now)
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        *self.outcome.lock().unwrap() = Some(result);
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+fn trusted_roots() -> RootCertStore {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(ta.subject, ta.spki, ta.name_constraints)
+    }));
+
+    match rustls_native_certs::load_native_certs() {
+        Ok(certs) => {
+            let ders: Vec<&[u8]> = certs.iter().map(|cert| cert.0.as_slice()).collect();
+            let (added, ignored) = roots.add_parsable_certificates(&ders);
+            debug!("Loaded {} root certificates from the OS trust store ({} unusable)", added, ignored);
+        }
+        Err(e) => warn!("Could not load the OS trust store, using bundled roots only: {}", e),
+    }
+    roots
+}
+
+async fn handshake_tls(target: &ProbeTarget, stream: TcpStream, ssl_verify: bool, timeout: Duration) -> DiagnosticStep {
+    let name = "TLS handshake";
+    let started = Instant::now();
+
+    let server_name = match ServerName::try_from(target.host.as_str()) {
+        Ok(server_name) => server_name,
+        Err(e) => {
+            return DiagnosticStep::new(
+                name,
+                StepStatus::Failed,
+                started,
+                format!("'{}' is not a valid TLS server name: {}", target.host, e),
+                Vec::new(),
+            );
+        }
+    };
+
+    let verifier = Arc::new(RecordingVerifier {
+        inner: WebPkiVerifier::new(trusted_roots(), None),
+        outcome: Mutex::new(None),
+    });
+    let mut tls_config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(RootCertStore::empty())
+        .with_no_client_auth();
+    tls_config.dangerous().set_certificate_verifier(verifier.clone());
+
+    let connector = TlsConnector::from(Arc::new(tls_config));
+    let tls_stream = match tokio::time::timeout(timeout, connector.connect(server_name, stream)).await {
+        Ok(Ok(tls_stream)) => tls_stream,
+        Ok(Err(e)) => {
+            return DiagnosticStep::new(
+                name,
+                StepStatus::Failed,
+                started,
+                format!("TLS handshake failed: {}", e),
+                vec!["A proxy or firewall may be intercepting the connection".to_string()],
+            );
+        }
+        Err(_) => {
+            return DiagnosticStep::new(
+                name,
+                StepStatus::Failed,
+                started,
+                format!("TCP port is open but the TLS handshake got no answer within {}s", timeout.as_secs()),
+                Vec::new(),
+            );
+        }
+    };
+
+    let (_, connection) = tls_stream.get_ref();
+    let mut details = Vec::new();
+    if let Some(version) = connection.protocol_version() {
+        details.push(format!("Protocol: {:?}", version));
+    }
+    if let Some(suite) = connection.negotiated_cipher_suite() {
+        details.push(format!("Cipher suite: {:?}", suite.suite()));
+    }
+
+    let mut status = StepStatus::Ok;
+    let mut summary = None;
+
+    if let Some(leaf) = connection.peer_certificates().and_then(|certs| certs.first()) {
+        let (cert_status, cert_summary, mut cert_details) = describe_certificate(&leaf.0);
+        details.append(&mut cert_details);
+        if cert_status != StepStatus::Ok {
+            status = cert_status;
+            summary = Some(cert_summary);
+        }
+    }
+
+    // Trust verification outcome, reported after the certificate details
+    let summary = match verifier.outcome.lock().unwrap().clone() {
+        Some(Ok(())) => {
+            details.push("Certificate chain: trusted".to_string());
+            summary.unwrap_or_else(|| "TLS handshake succeeded and the certificate is trusted".to_string())
+        }
+        Some(Err(e)) => {
+            details.push(format!("Certificate chain: NOT trusted ({})", e));
+            if ssl_verify {
+                status = StepStatus::Failed;
+                format!("Certificate is not trusted: {}", e)
+            } else if status == StepStatus::Ok {
+                status = StepStatus::Warning;
+                format!("Certificate is not trusted (ssl_verify is disabled): {}", e)
+            } else {
+                summary.unwrap_or_default()
+            }
+        }
+        None => {
+            details.push("Certificate chain: not checked".to_string());
+            if status == StepStatus::Ok {
+                status = StepStatus::Warning;
+            }
+            summary.unwrap_or_else(|| "TLS handshake succeeded but the certificate could not be verified".to_string())
+        }
+    };
+
+    DiagnosticStep::new(name, status, started, summary, details)
+}
+
+// Subject, issuer, names and validity of the server's leaf certificate
+fn describe_certificate(der: &[u8]) -> (StepStatus, String, Vec<String>) {
+    let cert = match x509_parser::parse_x509_certificate(der) {
+        Ok((_, cert)) => cert,
+        Err(e) => {
+            return (
+                StepStatus::Warning,
+                "Could not parse the server certificate".to_string(),
+                vec![format!("Certificate parse error: {}", e)],
+            );
+        }
+    };
+
+    let mut details = vec![
+        format!("Subject: {}", cert.subject()),
+        format!("Issuer: {}", cert.issuer()),
+    ];
+
+    if let Ok(Some(san)) = cert.subject_alternative_name() {
+        let names: Vec<String> = san
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                GeneralName::IPAddress(ip) => Some(format_ip(ip)),
+                _ => None,
+            })
+            .collect();
+        details.push(format!("Subject alternative names: {}", names.join(", ")));
+    }
+
+    let validity = cert.validity();
+    let not_before = format_timestamp(validity.not_before.timestamp());
+    let not_after = format_timestamp(validity.not_after.timestamp());
+    details.push(format!("Valid from: {}", not_before));
+    details.push(format!("Valid until: {}", not_after));
+
+    let days_left = (validity.not_after.timestamp() - Utc::now().timestamp()) / 86_400;
+    if !validity.is_valid() {
+        (
+            StepStatus::Failed,
+            format!("Server certificate is outside its validity period ({} to {})", not_before, not_after),
+            details,
+        )
+    } else if days_left < CERT_EXPIRY_WARNING_DAYS {
+        details.push(format!("Expires in {} day(s)", days_left));
+        (
+            StepStatus::Warning,
+            format!("Server certificate expires in {} day(s), on {}", days_left, not_after),
+            details,
+        )
+    } else {
+        details.push(format!("Expires in {} day(s)", days_left));
+        (StepStatus::Ok, String::new(), details)
+    }
+}
+
+// IP SANs are raw network-order bytes: 4 for IPv4, 16 for IPv6
+fn format_ip(bytes: &[u8]) -> String {
+    match bytes.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(bytes).unwrap()).to_string(),
+        16 => IpAddr::from(<[u8; 16]>::try_from(bytes).unwrap()).to_string(),
+        _ => format!("{:02x?}", bytes),
+    }
+}
+
+fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_else(|| timestamp.to_string())
+}
+
+// Anonymous auth-method listing: proves the controller API is up without needing a token
+async fn probe_controller(server: &Server, config: &Config) -> DiagnosticStep {
+    let name = "Controller API";
+    let started = Instant::now();
+
+    let client = match BoundaryApiClient::new(
+        &server.url,
+        None,
+        config.security.ssl_verify,
+        config.security.timeout_seconds,
+    ) {
+        Ok(client) => client,
+        Err(e) => return DiagnosticStep::new(name, StepStatus::Failed, started, e.to_string(), Vec::new()),
+    };
+
+    match client.list_auth_methods("global").await {
+        Ok(auth_methods) => {
+            let details = auth_methods
+                .iter()
+                .map(|m| format!("Auth method: {} ({}, {})", m.name, m.method_type, m.id))
+                .collect();
+            DiagnosticStep::new(
+                name,
+                StepStatus::Ok,
+                started,
+                format!(
+                    "Controller answered in {} ms with {} auth method(s)",
+                    started.elapsed().as_millis(),
+                    auth_methods.len()
+                ),
+                details,
+            )
+        }
+        Err(ApiError::Status { status, kind, message }) => DiagnosticStep::new(
+            name,
+            if status >= 500 { StepStatus::Failed } else { StepStatus::Warning },
+            started,
+            format!("Controller answered HTTP {} ({})", status, kind),
+            vec![message],
+        ),
+        Err(e) => DiagnosticStep::new(name, StepStatus::Failed, started, e.to_string(), Vec::new()),
+    }
+}
+
+fn render_text(report: &DiagnosticReport) -> String {
+    let mut lines = vec![
+        format!("Regis server diagnostics: {} ({})", report.server_name, report.server_id),
+        format!("URL: {}", report.url),
+        format!("Started: {}", report.started_at),
+        format!("Overall: {}", format!("{:?}", report.status).to_uppercase()),
+        String::new(),
+    ];
+
+    for (index, step) in report.steps.iter().enumerate() {
+        lines.push(format!(
+            "{}. {} [{}] ({} ms) - {}",
+            index + 1,
+            step.name,
+            format!("{:?}", step.status).to_uppercase(),
+            step.duration_ms,
+            step.summary
+        ));
+        for detail in &step.details {
+            lines.push(format!("     {}", detail));
+        }
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(url: &str) -> Server {
+        serde_json::from_value(serde_json::json!({
+            "id": "dev",
+            "name": "Development",
+            "url": url,
+            "description": "",
+            "environment": "dev",
+            "region": "local",
+            "boundary_cli_path": null,
+            "authScope": null,
+            "secondaryHost": null,
+            "targetScope": null,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn reads_host_and_port_from_the_url() {
+        let target = parse_server_url("https://[::1]/").unwrap();
+        assert_eq!((target.scheme.as_str(), target.host.as_str(), target.port), ("https", "::1", 443));
+        assert_eq!(parse_server_url("http://boundary.internal:9200").unwrap().port, 9200);
+        assert!(parse_server_url("boundary.internal").is_err());
+    }
+
+    #[tokio::test]
+    async fn skips_every_stage_after_a_bad_url() {
+        let report = diagnose_server(&server("not a url"), &Config::default()).await;
+
+        let statuses: Vec<StepStatus> = report.steps.iter().map(|step| step.status).collect();
+        assert_eq!(statuses, vec![StepStatus::Failed, StepStatus::Skipped, StepStatus::Skipped, StepStatus::Skipped, StepStatus::Skipped]);
+        assert_eq!(report.status, StepStatus::Failed);
+        assert!(report.report.contains("1. URL [FAILED]"), "{}", report.report);
+    }
+
+    #[tokio::test]
+    async fn reports_refused_tcp_connections() {
+        // Nothing listens on a port we just released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+
+        let (step, stream) = connect_tcp(&[address], Duration::from_secs(5)).await;
+
+        assert!(stream.is_none());
+        assert_eq!(step.status, StepStatus::Failed);
+        assert!(step.details[0].starts_with(&address.to_string()), "{:?}", step.details);
+    }
+
+    #[test]
+    fn formats_ip_alternative_names() {
+        assert_eq!(format_ip(&[10, 0, 0, 1]), "10.0.0.1");
+        assert_eq!(format_ip(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]), "::1");
+    }
+}
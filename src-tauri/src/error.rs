@@ -0,0 +1,340 @@
+// User-facing error type returned by every Tauri command
+//
+// Internal helpers keep returning `Result<_, String>`; commands convert those strings
+// into a RegisError with a stable code, a short message for the user, the technical
+// detail (usually CLI stderr or the controller error) and a help-desk hint.
+
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use crate::retry;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisError {
+    ServerUnreachable(String),    // DNS failure, connection refused, no route to host
+    ServerNotResponding(String),  // TCP reachable but no (or no healthy) response
+    TimedOut(String),             // Our own operation deadline expired
+    Cancelled(String),            // Cancelled by the user
+    AuthenticationFailed(String), // OIDC flow failed or the token was rejected
+    PermissionDenied(String),     // Authenticated but not allowed
+    CliNotFound(String),          // Boundary CLI missing or not executable
+    CliError(String),             // Boundary CLI reported an error
+    TargetFailed(String),         // Authorizing or connecting to a target failed
+    PortInUse(String),            // The fixed local listen port is taken
+    RdpClient(String),            // No usable RDP client or launch failure
+    CredentialStore(String),      // OS keychain errors
+    Config(String),               // Missing server, unreadable configuration
+    Internal(String),
+}
+
+impl RegisError {
+    // Stable identifier the frontend and help desk can rely on
+    pub fn code(&self) -> &'static str {
+        match self {
+            RegisError::ServerUnreachable(_) => "SERVER_UNREACHABLE",
+            RegisError::ServerNotResponding(_) => "SERVER_NOT_RESPONDING",
+            RegisError::TimedOut(_) => "OPERATION_TIMED_OUT",
+            RegisError::Cancelled(_) => "OPERATION_CANCELLED",
+            RegisError::AuthenticationFailed(_) => "AUTHENTICATION_FAILED",
+            RegisError::PermissionDenied(_) => "PERMISSION_DENIED",
+            RegisError::CliNotFound(_) => "BOUNDARY_CLI_NOT_FOUND",
+            RegisError::CliError(_) => "BOUNDARY_CLI_ERROR",
+            RegisError::TargetFailed(_) => "TARGET_CONNECTION_FAILED",
+            RegisError::PortInUse(_) => "LOCAL_PORT_IN_USE",
+            RegisError::RdpClient(_) => "RDP_CLIENT_ERROR",
+            RegisError::CredentialStore(_) => "CREDENTIAL_STORE_ERROR",
+            RegisError::Config(_) => "CONFIGURATION_ERROR",
+            RegisError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            RegisError::ServerUnreachable(_) => "The Boundary server could not be reached",
+            RegisError::ServerNotResponding(_) => "The Boundary server is reachable but did not respond correctly",
+            RegisError::TimedOut(_) => "The operation took too long and was stopped",
+            RegisError::Cancelled(_) => "The operation was cancelled",
+            RegisError::AuthenticationFailed(_) => "Authentication with the Boundary server failed",
+            RegisError::PermissionDenied(_) => "You are not allowed to perform this action",
+            RegisError::CliNotFound(_) => "The Boundary CLI could not be started",
+            RegisError::CliError(_) => "The Boundary CLI reported an error",
+            RegisError::TargetFailed(_) => "Could not connect to the selected target",
+            RegisError::PortInUse(_) => "The local port for this connection is already in use",
+            RegisError::RdpClient(_) => "The remote desktop client could not be started",
+            RegisError::CredentialStore(_) => "Could not access the system credential store",
+            RegisError::Config(_) => "The application configuration is invalid or incomplete",
+            RegisError::Internal(_) => "An unexpected error occurred",
+        }
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self {
+            RegisError::ServerUnreachable(_) => {
+                "Check your network or VPN connection and that the server address is correct. \
+                 If other users are affected, contact the help desk with the error details."
+            }
+            RegisError::ServerNotResponding(_) => {
+                "The server accepted the connection but did not answer. It may be overloaded or \
+                 under maintenance; retry in a few minutes and contact the help desk if it persists."
+            }
+            RegisError::TimedOut(_) => {
+                "Retry the operation. If it keeps timing out, check your network connection or \
+                 increase the timeout settings in config.json."
+            }
+            RegisError::Cancelled(_) => "No action needed. Start the operation again when ready.",
+            RegisError::AuthenticationFailed(_) => {
+                "Sign in again. If the identity provider page did not open or rejected you, \
+                 contact the help desk to verify your account."
+            }
+            RegisError::PermissionDenied(_) => {
+                "Your account does not have access to this resource. Ask the help desk to check \
+                 your Boundary roles and grants."
+            }
+            RegisError::CliNotFound(_) => {
+                "Install the Boundary CLI or set boundary.cli_path in config.json (or \
+                 boundary_cli_path on the server entry) to its location."
+            }
+            RegisError::CliError(_) => {
+                "Retry the operation. If the error persists, send the technical details to the help desk."
+            }
+            RegisError::TargetFailed(_) => {
+                "The target may be offline or at its session limit. Retry, or pick another target; \
+                 contact the help desk with the target name if it persists."
+            }
+            RegisError::PortInUse(_) => {
+                "Close the program using the port, or choose another local port for this target \
+                 in its details."
+            }
+            RegisError::RdpClient(_) => {
+                "Install a supported RDP client, or connect manually using the address and port shown."
+            }
+            RegisError::CredentialStore(_) => {
+                "Unlock your system keychain or credential manager and try again."
+            }
+            RegisError::Config(_) => {
+                "Check config.json and servers.json, or restart the application. Contact the help \
+                 desk if the server list is wrong."
+            }
+            RegisError::Internal(_) => {
+                "Restart the application. If the problem persists, send the logs to the help desk."
+            }
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            RegisError::ServerUnreachable(d)
+            | RegisError::ServerNotResponding(d)
+            | RegisError::TimedOut(d)
+            | RegisError::Cancelled(d)
+            | RegisError::AuthenticationFailed(d)
+            | RegisError::PermissionDenied(d)
+            | RegisError::CliNotFound(d)
+            | RegisError::CliError(d)
+            | RegisError::TargetFailed(d)
+            | RegisError::PortInUse(d)
+            | RegisError::RdpClient(d)
+            | RegisError::CredentialStore(d)
+            | RegisError::Config(d)
+            | RegisError::Internal(d) => d,
+        }
+    }
+
+    // Classify a Boundary CLI/API failure, falling back to a generic CLI error
+    pub fn cli(detail: String) -> Self {
+        classify(detail, RegisError::CliError)
+    }
+
+    // Classify a failure during authentication
+    pub fn auth(detail: String) -> Self {
+        classify(detail, RegisError::AuthenticationFailed)
+    }
+
+    // Classify a failure while authorizing or connecting to a target
+    pub fn target(detail: String) -> Self {
+        classify(detail, RegisError::TargetFailed)
+    }
+
+    // Classify a keychain failure; a missing or expired token means the user must sign in
+    pub fn credentials(detail: String) -> Self {
+        classify(detail, RegisError::CredentialStore)
+    }
+}
+
+// Whether a CLI/API failure means the user lacks permission. The HTTP status decides when
+// there is one, so wording elsewhere in the message ("timeout") can't hide a 403.
+pub fn is_permission_denied(detail: &str) -> bool {
+    match retry::status_code(detail) {
+        Some(status) => status == 403,
+        None => {
+            let lower = detail.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+            ["permissiondenied", "permission denied", "forbidden"].iter().any(|p| lower.contains(p))
+        }
+    }
+}
+
+// Recognise network, deadline and permission failures common to every operation;
+// anything else is attributed to the operation's own category. An HTTP status, when the
+// controller sent one, says more than words elsewhere in the message ("403 ... timeout").
+fn classify(detail: String, fallback: fn(String) -> RegisError) -> RegisError {
+    // Collapse whitespace so phrases split across the CLI's aligned output still match
+    let lower = detail.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let has = |patterns: &[&str]| patterns.iter().any(|p| lower.contains(p));
+
+    if has(&["operation cancelled"]) {
+        RegisError::Cancelled(detail)
+    } else if has(&["operation timed out after"]) {
+        RegisError::TimedOut(detail)
+    } else if has(&["failed to execute boundary cli command"]) {
+        RegisError::CliNotFound(detail)
+    } else if let Some(status) = retry::status_code(&lower) {
+        match status {
+            401 => RegisError::AuthenticationFailed(detail),
+            403 => RegisError::PermissionDenied(detail),
+            500..=599 => RegisError::ServerNotResponding(detail),
+            _ => fallback(detail),
+        }
+    } else if has(&["already in use"]) {
+        RegisError::PortInUse(detail)
+    } else if has(&[
+        "no such host",
+        "connection refused",
+        "network is unreachable",
+        "no route to host",
+        "dial tcp",
+        "dns error",
+        "failed to lookup address",
+    ]) {
+        RegisError::ServerUnreachable(detail)
+    } else if has(&[
+        "deadline exceeded",
+        "timed out",
+        "timeout",
+        "connection reset",
+        "unexpected eof",
+        "bad gateway",
+        "unavailable",
+    ]) {
+        RegisError::ServerNotResponding(detail)
+    } else if is_permission_denied(&lower) {
+        RegisError::PermissionDenied(detail)
+    } else if has(&[
+        "unauthenticated",
+        "unauthorized",
+        "invalid token",
+        "token expired",
+        "token has expired",
+        "no matching entry",
+    ]) {
+        RegisError::AuthenticationFailed(detail)
+    } else {
+        fallback(detail)
+    }
+}
+
+impl std::fmt::Display for RegisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.code(), self.message(), self.detail())
+    }
+}
+
+impl std::error::Error for RegisError {}
+
+// Sent to the frontend as {"code", "message", "detail", "hint"}
+impl Serialize for RegisError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("RegisError", 4)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", self.message())?;
+        s.serialize_field("detail", self.detail())?;
+        s.serialize_field("hint", self.hint())?;
+        s.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_failures_by_kind() {
+        let cases: &[(&str, &str)] = &[
+            ("Operation cancelled: 'boundary targets list'", "OPERATION_CANCELLED"),
+            ("Operation timed out after 30s: 'boundary scopes list'", "OPERATION_TIMED_OUT"),
+            ("Failed to execute Boundary CLI command 'boundary': No such file or directory", "BOUNDARY_CLI_NOT_FOUND"),
+            ("Error trying to list targets: dial tcp 10.0.0.1:9200: connect: connection refused", "SERVER_UNREACHABLE"),
+            ("error sending request: dns error: failed to lookup address information", "SERVER_UNREACHABLE"),
+            ("Error from controller when performing list on targets: Status:      503, Kind: Unavailable", "SERVER_NOT_RESPONDING"),
+            ("context deadline exceeded", "SERVER_NOT_RESPONDING"),
+            ("Error from controller when performing authorize-session on target:\n  Status:      403\n  Kind:        PermissionDenied", "PERMISSION_DENIED"),
+            ("Controller API returned HTTP 403 (PermissionDenied): request timeout exceeded grant check", "PERMISSION_DENIED"),
+            ("Controller API returned HTTP 401 (Unauthenticated): Unauthenticated, or invalid token.", "AUTHENTICATION_FAILED"),
+            ("Error reading token: token has expired", "AUTHENTICATION_FAILED"),
+            ("Error from controller: Status: 400, Kind: InvalidArgument", "BOUNDARY_CLI_ERROR"),
+            ("Failed to parse targets JSON: expected value", "BOUNDARY_CLI_ERROR"),
+        ];
+        for (detail, code) in cases {
+            assert_eq!(RegisError::cli(detail.to_string()).code(), *code, "{}", detail);
+        }
+
+        // Unrecognised failures keep the operation's own category
+        assert_eq!(RegisError::target("No free worker for the target".to_string()).code(), "TARGET_CONNECTION_FAILED");
+        assert_eq!(
+            RegisError::target("Local port 3389 is already in use: the proxy listens on 127.0.0.1:50123 instead".to_string()).code(),
+            "LOCAL_PORT_IN_USE"
+        );
+        assert_eq!(RegisError::auth("OIDC callback was rejected".to_string()).code(), "AUTHENTICATION_FAILED");
+        assert_eq!(RegisError::credentials("Keychain is locked".to_string()).code(), "CREDENTIAL_STORE_ERROR");
+    }
+
+    #[test]
+    fn recognises_permission_failures() {
+        assert!(is_permission_denied("Error from controller: Status:      403\n Kind: PermissionDenied"));
+        assert!(is_permission_denied("Controller API returned HTTP 403 (PermissionDenied): request timeout"));
+        assert!(is_permission_denied("Forbidden."));
+        assert!(!is_permission_denied("Controller API returned HTTP 503 (Unavailable): grants forbidden lookup"));
+        assert!(!is_permission_denied("Operation timed out after 30s: 'boundary targets list'"));
+    }
+
+    #[test]
+    fn serializes_for_the_frontend() {
+        let error = RegisError::PortInUse("Local port 127.0.0.1:5432 is already in use by postgres".to_string());
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "code": "LOCAL_PORT_IN_USE",
+                "message": "The local port for this connection is already in use",
+                "detail": "Local port 127.0.0.1:5432 is already in use by postgres",
+                "hint": error.hint(),
+            })
+        );
+
+        let variants: [fn(String) -> RegisError; 14] = [
+            RegisError::ServerUnreachable,
+            RegisError::ServerNotResponding,
+            RegisError::TimedOut,
+            RegisError::Cancelled,
+            RegisError::AuthenticationFailed,
+            RegisError::PermissionDenied,
+            RegisError::CliNotFound,
+            RegisError::CliError,
+            RegisError::TargetFailed,
+            RegisError::PortInUse,
+            RegisError::RdpClient,
+            RegisError::CredentialStore,
+            RegisError::Config,
+            RegisError::Internal,
+        ];
+        let mut codes: Vec<&str> = variants.iter().map(|variant| variant("detail".to_string()).code()).collect();
+        for variant in variants {
+            let json = serde_json::to_value(variant("detail".to_string())).unwrap();
+            assert_eq!(json["detail"], "detail");
+            assert!(!json["message"].as_str().unwrap().is_empty() && !json["hint"].as_str().unwrap().is_empty(), "{}", json);
+        }
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), variants.len());
+    }
+}
@@ -0,0 +1,5387 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::collections::HashMap;
+use std::process::Stdio;
+use tauri::{command, Emitter, Manager, AppHandle};
+use tracing::{debug, info, warn, error, instrument, Instrument};
+use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use chrono;
+use keyring::Entry;
+
+mod boundary_api;
+mod cache;
+mod cli_detect;
+mod connections;
+mod diagnostics;
+mod error;
+mod models;
+mod preferences;
+mod redact;
+mod retry;
+mod search;
+
+pub use boundary_api::BoundaryApiClient;
+use cache::{CachedDiscovery, DiscoveryCache, TargetDiff};
+use cli_detect::BoundaryCliInfo;
+pub use connections::{check_listen_port, follow_connection, ConnectionEvent, ConnectionProcess, ListenSettings, ProxyEvent, ProxyState, ProxyStartup};
+use diagnostics::DiagnosticReport;
+use error::RegisError;
+use models::{AliasItem, AuthMethodItem, HostItem, HostSetItem, ScopeItem, SessionAuthorizationItem, SessionItem, TargetItem};
+use preferences::{PreferenceStore, RecentTarget};
+use search::TargetMatch;
+use redact::RedactingMakeWriter;
+pub use retry::RetryPolicy;
+
+// Configuration structures
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub enabled: bool,
+    pub console_output: bool,
+    pub file_output: bool,
+    pub log_directory: String,
+    pub max_file_size_mb: u64,
+    pub max_files: usize,
+    pub log_format: String,
+    pub component_levels: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UIConfig {
+    pub theme: String,
+    pub startup_behavior: String,
+    pub minimize_to_tray: bool,
+    pub confirm_exit: bool,
+    pub remember_window_state: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SecurityConfig {
+    pub auto_logout_minutes: u32,
+    pub remember_auth: bool,
+    pub ssl_verify: bool,
+    pub timeout_seconds: u32,
+    #[serde(default = "default_auth_timeout_seconds")]
+    pub auth_timeout_seconds: u32, // Deadline for the interactive OIDC browser flow
+}
+
+fn default_auth_timeout_seconds() -> u32 {
+    300
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionConfig {
+    pub auto_connect_single_target: bool,
+    pub connection_timeout_seconds: u32,
+    pub retry_attempts: u32,
+    pub retry_delay_seconds: u32,
+    #[serde(default = "default_discovery_concurrency")]
+    pub discovery_concurrency: usize, // Projects listed at once when targets are discovered scope by scope
+    #[serde(default = "default_target_cache_ttl_seconds")]
+    pub target_cache_ttl_seconds: u64, // How long discovered scopes/targets are shown from cache, 0 disables
+}
+
+fn default_discovery_concurrency() -> usize {
+    4
+}
+
+fn default_target_cache_ttl_seconds() -> u64 {
+    86400
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RdpConfig {
+    pub auto_launch: bool,
+    pub preferred_client: String,
+    pub fullscreen: bool,
+    pub resolution: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdvancedConfig {
+    pub debug_mode: bool,
+    pub developer_tools: bool,
+    pub crash_reporting: bool,
+    pub telemetry: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryConfig {
+    pub cli_path: String,
+    pub auto_detect: bool, // Also search PATH, bundled resources and well-known install directories
+    #[serde(default = "default_min_cli_version")]
+    pub min_version: String, // Oldest Boundary CLI version Regis will use
+}
+
+fn default_min_cli_version() -> String {
+    "0.13.0".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Config {
+    pub logging: LoggingConfig,
+    pub ui: UIConfig,
+    pub security: SecurityConfig,
+    pub connection: ConnectionConfig,
+    pub rdp: RdpConfig,
+    pub advanced: AdvancedConfig,
+    pub boundary: BoundaryConfig,
+}
+
+// Server structures (existing)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Server {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub description: String,
+    pub environment: String,
+    pub region: String,
+    pub boundary_cli_path: Option<String>, // Optional per-server CLI path override
+    #[serde(rename = "authScope")]
+    pub auth_scope: Option<String>, // Optional auth scope for the server
+    #[serde(rename = "secondaryHost")]
+    pub secondary_host: Option<String>, // Optional secondary host
+    #[serde(rename = "targetScope")]
+    pub target_scope: Option<String>, // Scope target discovery starts from (default: global)
+    #[serde(rename = "listenAddr", default)]
+    pub listen_addr: Option<String>, // Local address for connect proxies, unless set per target
+    #[serde(rename = "listenPort", default)]
+    pub listen_port: Option<u16>, // Fixed local port, unless set per target
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub servers: Vec<Server>,
+}
+
+// Boundary CLI execution structures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryCommandResult {
+    pub success: bool,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+    pub command: String,
+}
+
+// Environment variable the CLI reads the per-invocation token from
+const BOUNDARY_TOKEN_ENV: &str = "BOUNDARY_TOKEN";
+
+// Deadline applied to CLI invocations that don't specify one
+const DEFAULT_CLI_TIMEOUT_SECONDS: u64 = 30;
+
+// Boundary session status once a session is over and its connections are closed
+const SESSION_TERMINATED: &str = "terminated";
+// How long to wait for a cancelled session to reach SESSION_TERMINATED
+const SESSION_CANCEL_CONFIRM_ATTEMPTS: u32 = 10;
+const SESSION_CANCEL_CONFIRM_INTERVAL: Duration = Duration::from_millis(500);
+
+// Per-invocation options for a Boundary CLI call
+#[derive(Clone, Default)]
+pub struct BoundaryCommandOptions {
+    pub token: Option<String>,         // Passed to the child as -token env://BOUNDARY_TOKEN
+    pub env: HashMap<String, String>,  // Extra environment for the child process only
+    pub keyring_type: Option<String>,  // e.g. "none" to bypass the CLI's own keyring
+    pub timeout: Option<Duration>,     // Kill the process tree after this long
+    pub cancel: Option<CancellationToken>, // Kill the process tree when cancelled
+    pub retry: RetryPolicy,            // Applied by discovery/authorization calls, not per process
+}
+
+impl BoundaryCommandOptions {
+    // Run with an explicit token and without touching the CLI keyring
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self.keyring_type = Some("none".to_string());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_cancel(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+}
+
+impl std::fmt::Debug for BoundaryCommandOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundaryCommandOptions")
+            .field("token", &self.token.as_deref().map(redact::masked))
+            .field("env", &self.env.keys().collect::<Vec<_>>())
+            .field("keyring_type", &self.keyring_type)
+            .field("timeout", &self.timeout)
+            .field("cancellable", &self.cancel.is_some())
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+// Failures running a Boundary CLI process, as opposed to the CLI reporting an error
+#[derive(Debug, Clone)]
+pub enum BoundaryCommandError {
+    Spawn { command: String, message: String },
+    TimedOut { command: String, timeout_seconds: u64 },
+    Cancelled { command: String },
+}
+
+impl std::fmt::Display for BoundaryCommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoundaryCommandError::Spawn { command, message } => {
+                write!(f, "Failed to execute Boundary CLI command '{}': {}", command, message)
+            }
+            BoundaryCommandError::TimedOut { command, timeout_seconds } => {
+                write!(f, "Operation timed out after {}s: '{}'", timeout_seconds, command)
+            }
+            BoundaryCommandError::Cancelled { command } => {
+                write!(f, "Operation cancelled: '{}'", command)
+            }
+        }
+    }
+}
+
+impl From<BoundaryCommandError> for String {
+    fn from(e: BoundaryCommandError) -> Self {
+        e.to_string()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryAuthMethod {
+    pub id: String,
+    pub name: String,
+    pub method_type: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryScope {
+    pub id: String,
+    pub name: String,
+    pub scope_type: String,
+    pub description: String,
+    #[serde(default)]
+    pub parent_scope_id: Option<String>, // None for global
+}
+
+// A scope with its child scopes, as shown in the scope selection tree
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryScopeNode {
+    #[serde(flatten)]
+    pub scope: BoundaryScope,
+    pub children: Vec<BoundaryScopeNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BoundaryTarget {
+    pub id: String,
+    pub name: String,
+    pub target_type: String,
+    pub description: String,
+    pub address: Option<String>,
+    pub default_port: Option<u16>,
+    #[serde(default)]
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub scope_name: Option<String>,
+    #[serde(default)]
+    pub session_max_seconds: Option<u32>, // Longest a session may last
+    #[serde(default)]
+    pub session_connection_limit: Option<i32>, // Connections per session, -1 for unlimited
+    #[serde(default)]
+    pub authorized_actions: Vec<String>, // What the current user may do, e.g. "authorize-session"
+    #[serde(default)]
+    pub default_client_port: Option<u16>, // Local port `boundary connect` listens on by default
+    #[serde(default)]
+    pub egress_worker_filter: Option<String>, // Also the single worker filter of older releases
+    #[serde(default)]
+    pub ingress_worker_filter: Option<String>,
+    #[serde(default)]
+    pub host_source_ids: Vec<String>,
+    #[serde(default)]
+    pub brokered_credential_source_ids: Vec<String>, // Returned to the user with the session
+    #[serde(default)]
+    pub injected_application_credential_source_ids: Vec<String>, // Injected by the worker, never seen by the user
+    #[serde(default)]
+    pub aliases: Vec<String>, // Friendly names such as "db.prod.internal" (0.16+)
+}
+
+// Action a user needs on a target to connect to it
+pub const AUTHORIZE_SESSION_ACTION: &str = "authorize-session";
+
+impl BoundaryTarget {
+    // Listings that carry no authorized_actions at all are assumed connectable
+    pub fn can_authorize_session(&self) -> bool {
+        self.authorized_actions.is_empty() || self.authorized_actions.iter().any(|a| a == AUTHORIZE_SESSION_ACTION)
+    }
+}
+
+// A Boundary alias: a globally unique name that stands for a target
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BoundaryAlias {
+    pub id: String,
+    pub value: String,
+    pub alias_type: String, // Only "target" exists today
+    pub destination_id: Option<String>,
+    pub host_id: Option<String>, // Host the alias authorizes sessions on, if it names one
+    pub name: String,
+    pub description: String,
+}
+
+// A host set, called a "host source" on targets
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryHostSet {
+    pub id: String,
+    pub name: String,
+    pub host_catalog_id: String,
+    pub host_set_type: String, // "static" or "plugin"
+    pub description: String,
+    pub host_ids: Vec<String>,
+    pub hosts: Vec<BoundaryHost>, // Filled in by discover_target_hosts
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryHost {
+    pub id: String,
+    pub name: String,
+    pub host_catalog_id: String,
+    pub description: String,
+    pub address: Option<String>, // Static address, or the first synced DNS name / IP of plugin hosts
+    pub ip_addresses: Vec<String>,
+    pub dns_names: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BoundarySessionAuthorization {
+    pub authorization_token: String,
+    pub session_id: String,
+    pub target_id: String,
+    pub user_id: String,
+    pub host_id: Option<String>,
+    pub scope_id: String,
+    pub created_time: String,
+    pub expiration_time: Option<String>,
+    pub connection_limit: i32,
+}
+
+impl std::fmt::Debug for BoundarySessionAuthorization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoundarySessionAuthorization")
+            .field("authorization_token", &redact::masked(&self.authorization_token))
+            .field("session_id", &self.session_id)
+            .field("target_id", &self.target_id)
+            .field("user_id", &self.user_id)
+            .field("host_id", &self.host_id)
+            .field("scope_id", &self.scope_id)
+            .field("created_time", &self.created_time)
+            .field("expiration_time", &self.expiration_time)
+            .field("connection_limit", &self.connection_limit)
+            .finish()
+    }
+}
+
+// What to list in a `targets list` call
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TargetQuery {
+    pub scope_id: Option<String>, // Defaults to global
+    #[serde(default)]
+    pub recursive: bool,          // Include every scope below scope_id
+    pub filter: Option<String>,   // Boundary filter expression, e.g. "/item/type" == "tcp"
+}
+
+// Event carrying each project's targets while per-scope discovery is still running
+const TARGET_DISCOVERY_PROGRESS_EVENT: &str = "target-discovery-progress";
+
+// One project's targets, reported as soon as that project has been listed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScopeTargets {
+    pub scope_id: String,
+    pub scope_name: String,
+    pub targets: Vec<BoundaryTarget>,
+    pub error: Option<String>, // Listing this project failed; the others are unaffected
+    pub completed: usize,      // Projects finished so far, including this one
+    pub total: usize,
+}
+
+// Called with each project's result as it arrives
+pub type ScopeTargetsCallback = Arc<dyn Fn(&ScopeTargets) + Send + Sync>;
+
+// How per-scope target discovery runs when a single recursive listing is not permitted
+#[derive(Clone)]
+pub struct TargetDiscoveryOptions {
+    pub concurrency: usize,                     // Projects listed at the same time
+    pub on_scope: Option<ScopeTargetsCallback>, // Progress callback, e.g. emitting a Tauri event
+}
+
+impl Default for TargetDiscoveryOptions {
+    fn default() -> Self {
+        TargetDiscoveryOptions {
+            concurrency: default_discovery_concurrency(),
+            on_scope: None,
+        }
+    }
+}
+
+impl TargetDiscoveryOptions {
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn on_scope(mut self, callback: impl Fn(&ScopeTargets) + Send + Sync + 'static) -> Self {
+        self.on_scope = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl std::fmt::Debug for TargetDiscoveryOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TargetDiscoveryOptions")
+            .field("concurrency", &self.concurrency)
+            .field("on_scope", &self.on_scope.is_some())
+            .finish()
+    }
+}
+
+// Event reporting how a refresh changed the cached target list
+const TARGET_CACHE_DIFF_EVENT: &str = "target-cache-diff";
+
+#[derive(Debug, Serialize, Clone)]
+struct TargetCacheDiff {
+    server_id: String,
+    #[serde(flatten)]
+    diff: TargetDiff,
+}
+
+// Payload of TARGET_DISCOVERY_PROGRESS_EVENT, tagged so the frontend can ignore stale discoveries
+#[derive(Debug, Serialize, Clone)]
+struct TargetDiscoveryProgress {
+    operation_id: Option<String>,
+    #[serde(flatten)]
+    scope: ScopeTargets,
+}
+
+// Event reporting what happens to an established connection, from proxy ready to exit
+const CONNECTION_EVENT: &str = "connection-event";
+
+// How long before a session expires the frontend is warned
+const SESSION_EXPIRY_WARNING: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Serialize, Clone)]
+struct ConnectionUpdate {
+    session_id: String,
+    server_id: String,
+    #[serde(flatten)]
+    event: ConnectionEvent,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoundaryConnection {
+    pub session_id: String,
+    pub server_id: String, // Server the session was authorized on
+    pub target_id: String,
+    pub target_name: String,
+    pub connection_type: String,
+    pub local_address: String,
+    pub local_port: u16,
+    pub status: String,
+    pub created_time: String,
+    pub expiration_time: Option<String>,
+    pub connection_limit: i32, // Connections the session allows, -1 for no limit
+    pub fixed_port: bool, // local_port came from the listen settings rather than the CLI
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum ConnectionType {
+    SSH,
+    RDP,
+    TCP,
+    HTTP,
+}
+
+// RDP client detection structures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RdpClientInfo {
+    pub name: String,
+    pub executable_path: String,
+    pub client_type: String,
+    pub platform: String,
+    pub version: Option<String>,
+    pub supports_fullscreen: bool,
+    pub supports_resolution: bool,
+    pub supports_credentials: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DetectedRdpClients {
+    pub clients: Vec<RdpClientInfo>,
+    pub default_client: Option<String>,
+    pub platform: String,
+}
+
+// Token storage structures
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StoredToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at: Option<String>,
+    pub server_id: String,
+    pub user_id: String,
+    pub scope_id: String,
+    pub created_at: String,
+}
+
+impl std::fmt::Debug for StoredToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredToken")
+            .field("access_token", &redact::masked(&self.access_token))
+            .field("refresh_token", &self.refresh_token.as_deref().map(redact::masked))
+            .field("expires_at", &self.expires_at)
+            .field("server_id", &self.server_id)
+            .field("user_id", &self.user_id)
+            .field("scope_id", &self.scope_id)
+            .field("created_at", &self.created_at)
+            .finish()
+    }
+}
+
+// OIDC Authentication Structures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OIDCAuthRequest {
+    pub server_id: String,
+    pub auth_method_id: String,
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub operation_id: Option<String>, // Lets the frontend cancel the flow
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OIDCAuthProgress {
+    pub status: String, // "started", "waiting_for_browser", "completing", "completed", "failed"
+    pub message: String,
+    pub auth_url: Option<String>,
+    pub progress_percent: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OIDCAuthResult {
+    pub success: bool,
+    pub token: Option<StoredToken>,
+    pub error: Option<String>,
+    pub scopes: Option<Vec<BoundaryScope>>,
+    #[serde(default)]
+    pub scope_tree: Option<BoundaryScopeNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TokenMetadata {
+    pub server_id: String,
+    pub user_id: String,
+    pub scope_id: String,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+}
+
+// Session monitoring structures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionHealth {
+    pub session_id: String,
+    pub status: String,
+    pub last_check: String,
+    pub response_time_ms: Option<u64>,
+    pub error_count: u32,
+    pub consecutive_failures: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionMonitoringStats {
+    pub total_sessions: usize,
+    pub active_sessions: usize,
+    pub failed_sessions: usize,
+    pub monitoring_enabled: bool,
+    pub last_check: String,
+}
+
+// A server's cached target list kept in memory, so searching as the user types neither
+// reloads the server list nor re-reads the cache file
+#[derive(Debug, Clone)]
+pub struct SearchableTargets {
+    pub user_id: String,
+    pub fetched_at: chrono::DateTime<chrono::Utc>,
+    pub targets: Arc<Vec<BoundaryTarget>>,
+}
+
+// Global state for configuration and connections
+pub struct AppState {
+    pub config: Config,
+    pub active_connections: Arc<Mutex<Vec<ConnectionProcess>>>, // Running `boundary connect` proxies
+    pub session_health: Arc<Mutex<HashMap<String, SessionHealth>>>,
+    pub monitoring_enabled: Arc<Mutex<bool>>,
+    pub auth_tokens: Arc<Mutex<HashMap<String, StoredToken>>>, // Current token per server_id
+    pub operations: Arc<Mutex<HashMap<String, CancellationToken>>>, // In-flight cancellable operations
+    pub boundary_cli: Arc<Mutex<Option<BoundaryCliInfo>>>, // Auto-detected CLI, when detection has run
+    pub preferences: Arc<PreferenceStore>, // ~/.regis/preferences.json
+    pub discovery_cache: Arc<DiscoveryCache>, // ~/.regis/cache/
+    pub searchable_targets: Arc<Mutex<HashMap<String, SearchableTargets>>>, // Cached targets per server_id
+    pub api_clients: Arc<Mutex<HashMap<String, (String, BoundaryApiClient)>>>, // Controller API client and its URL per server_id
+}
+
+impl std::fmt::Debug for AppState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AppState")
+            .field("config", &"Config{...}")
+            .field("active_connections", &"Arc<Mutex<Vec<ConnectionProcess>>>")
+            .field("session_health", &"Arc<Mutex<HashMap<String, SessionHealth>>>")
+            .field("monitoring_enabled", &"Arc<Mutex<bool>>")
+            .field("auth_tokens", &"Arc<Mutex<HashMap<String, StoredToken>>>")
+            .field("operations", &"Arc<Mutex<HashMap<String, CancellationToken>>>")
+            .field("boundary_cli", &"Arc<Mutex<Option<BoundaryCliInfo>>>")
+            .field("preferences", &self.preferences)
+            .field("discovery_cache", &self.discovery_cache)
+            .field("searchable_targets", &"Arc<Mutex<HashMap<String, SearchableTargets>>>")
+            .field("api_clients", &"Arc<Mutex<HashMap<String, (String, BoundaryApiClient)>>>")
+            .finish()
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            logging: LoggingConfig {
+                level: "info".to_string(),
+                enabled: true,
+                console_output: true,
+                file_output: true,
+                log_directory: "auto".to_string(),
+                max_file_size_mb: 10,
+                max_files: 5,
+                log_format: "structured".to_string(),
+                component_levels: HashMap::new(),
+            },
+            ui: UIConfig {
+                theme: "system".to_string(),
+                startup_behavior: "show_window".to_string(),
+                minimize_to_tray: true,
+                confirm_exit: false,
+                remember_window_state: true,
+            },
+            security: SecurityConfig {
+                auto_logout_minutes: 60,
+                remember_auth: true,
+                ssl_verify: true,
+                timeout_seconds: 30,
+                auth_timeout_seconds: default_auth_timeout_seconds(),
+            },
+            connection: ConnectionConfig {
+                auto_connect_single_target: true,
+                connection_timeout_seconds: 10,
+                retry_attempts: 3,
+                retry_delay_seconds: 2,
+                discovery_concurrency: default_discovery_concurrency(),
+                target_cache_ttl_seconds: default_target_cache_ttl_seconds(),
+            },
+            rdp: RdpConfig {
+                auto_launch: true,
+                preferred_client: "auto".to_string(),
+                fullscreen: false,
+                resolution: "auto".to_string(),
+            },
+            advanced: AdvancedConfig {
+                debug_mode: false,
+                developer_tools: false,
+                crash_reporting: true,
+                telemetry: false,
+            },
+            boundary: BoundaryConfig {
+                cli_path: "boundary-cli/boundary_0.19.3_darwin_arm64/boundary".to_string(),
+                auto_detect: true,
+                min_version: default_min_cli_version(),
+            },
+        }
+    }
+}
+
+// Kinds of Boundary operations, each with its own deadline
+#[derive(Debug, Clone, Copy)]
+pub enum OperationKind {
+    Request,      // Discovery, authorization and other controller requests
+    Connect,      // Bringing up the local `boundary connect` proxy
+    Authenticate, // Interactive OIDC authentication
+}
+
+impl Config {
+    // Deadline for a given kind of Boundary operation
+    pub fn operation_timeout(&self, kind: OperationKind) -> Duration {
+        let seconds = match kind {
+            OperationKind::Request => self.security.timeout_seconds,
+            OperationKind::Connect => self.connection.connection_timeout_seconds,
+            OperationKind::Authenticate => self.security.auth_timeout_seconds,
+        };
+        Duration::from_secs(seconds as u64)
+    }
+
+    // Merge user config over system config
+    fn merge_with_user_config(&mut self, user_config: Config) {
+        debug!("Merging user configuration overrides");
+
+        // Merge logging config
+        if user_config.logging.level != self.logging.level {
+            debug!("User override: logging.level = {}", user_config.logging.level);
+            self.logging.level = user_config.logging.level;
+        }
+        if user_config.logging.enabled != self.logging.enabled {
+            debug!("User override: logging.enabled = {}", user_config.logging.enabled);
+            self.logging.enabled = user_config.logging.enabled;
+        }
+        if user_config.logging.console_output != self.logging.console_output {
+            debug!("User override: logging.console_output = {}", user_config.logging.console_output);
+            self.logging.console_output = user_config.logging.console_output;
+        }
+        if user_config.logging.file_output != self.logging.file_output {
+            debug!("User override: logging.file_output = {}", user_config.logging.file_output);
+            self.logging.file_output = user_config.logging.file_output;
+        }
+        if user_config.logging.log_directory != self.logging.log_directory {
+            debug!("User override: logging.log_directory = {}", user_config.logging.log_directory);
+            self.logging.log_directory = user_config.logging.log_directory;
+        }
+        if user_config.logging.max_file_size_mb != self.logging.max_file_size_mb {
+            debug!("User override: logging.max_file_size_mb = {}", user_config.logging.max_file_size_mb);
+            self.logging.max_file_size_mb = user_config.logging.max_file_size_mb;
+        }
+        if user_config.logging.max_files != self.logging.max_files {
+            debug!("User override: logging.max_files = {}", user_config.logging.max_files);
+            self.logging.max_files = user_config.logging.max_files;
+        }
+        if user_config.logging.log_format != self.logging.log_format {
+            debug!("User override: logging.log_format = {}", user_config.logging.log_format);
+            self.logging.log_format = user_config.logging.log_format;
+        }
+        // Merge component_levels (extend the HashMap)
+        for (component, level) in user_config.logging.component_levels {
+            debug!("User override: logging.component_levels.{} = {}", component, level);
+            self.logging.component_levels.insert(component, level);
+        }
+
+        // Merge UI config
+        if user_config.ui.theme != self.ui.theme {
+            debug!("User override: ui.theme = {}", user_config.ui.theme);
+            self.ui.theme = user_config.ui.theme;
+        }
+        if user_config.ui.startup_behavior != self.ui.startup_behavior {
+            debug!("User override: ui.startup_behavior = {}", user_config.ui.startup_behavior);
+            self.ui.startup_behavior = user_config.ui.startup_behavior;
+        }
+        if user_config.ui.minimize_to_tray != self.ui.minimize_to_tray {
+            debug!("User override: ui.minimize_to_tray = {}", user_config.ui.minimize_to_tray);
+            self.ui.minimize_to_tray = user_config.ui.minimize_to_tray;
+        }
+        if user_config.ui.confirm_exit != self.ui.confirm_exit {
+            debug!("User override: ui.confirm_exit = {}", user_config.ui.confirm_exit);
+            self.ui.confirm_exit = user_config.ui.confirm_exit;
+        }
+        if user_config.ui.remember_window_state != self.ui.remember_window_state {
+            debug!("User override: ui.remember_window_state = {}", user_config.ui.remember_window_state);
+            self.ui.remember_window_state = user_config.ui.remember_window_state;
+        }
+
+        // Merge security config
+        if user_config.security.auto_logout_minutes != self.security.auto_logout_minutes {
+            debug!("User override: security.auto_logout_minutes = {}", user_config.security.auto_logout_minutes);
+            self.security.auto_logout_minutes = user_config.security.auto_logout_minutes;
+        }
+        if user_config.security.remember_auth != self.security.remember_auth {
+            debug!("User override: security.remember_auth = {}", user_config.security.remember_auth);
+            self.security.remember_auth = user_config.security.remember_auth;
+        }
+        if user_config.security.ssl_verify != self.security.ssl_verify {
+            debug!("User override: security.ssl_verify = {}", user_config.security.ssl_verify);
+            self.security.ssl_verify = user_config.security.ssl_verify;
+        }
+        if user_config.security.timeout_seconds != self.security.timeout_seconds {
+            debug!("User override: security.timeout_seconds = {}", user_config.security.timeout_seconds);
+            self.security.timeout_seconds = user_config.security.timeout_seconds;
+        }
+        if user_config.security.auth_timeout_seconds != self.security.auth_timeout_seconds {
+            debug!("User override: security.auth_timeout_seconds = {}", user_config.security.auth_timeout_seconds);
+            self.security.auth_timeout_seconds = user_config.security.auth_timeout_seconds;
+        }
+
+        // Merge connection config
+        if user_config.connection.auto_connect_single_target != self.connection.auto_connect_single_target {
+            debug!("User override: connection.auto_connect_single_target = {}", user_config.connection.auto_connect_single_target);
+            self.connection.auto_connect_single_target = user_config.connection.auto_connect_single_target;
+        }
+        if user_config.connection.connection_timeout_seconds != self.connection.connection_timeout_seconds {
+            debug!("User override: connection.connection_timeout_seconds = {}", user_config.connection.connection_timeout_seconds);
+            self.connection.connection_timeout_seconds = user_config.connection.connection_timeout_seconds;
+        }
+        if user_config.connection.retry_attempts != self.connection.retry_attempts {
+            debug!("User override: connection.retry_attempts = {}", user_config.connection.retry_attempts);
+            self.connection.retry_attempts = user_config.connection.retry_attempts;
+        }
+        if user_config.connection.retry_delay_seconds != self.connection.retry_delay_seconds {
+            debug!("User override: connection.retry_delay_seconds = {}", user_config.connection.retry_delay_seconds);
+            self.connection.retry_delay_seconds = user_config.connection.retry_delay_seconds;
+        }
+        if user_config.connection.discovery_concurrency != self.connection.discovery_concurrency {
+            debug!("User override: connection.discovery_concurrency = {}", user_config.connection.discovery_concurrency);
+            self.connection.discovery_concurrency = user_config.connection.discovery_concurrency;
+        }
+        if user_config.connection.target_cache_ttl_seconds != self.connection.target_cache_ttl_seconds {
+            debug!("User override: connection.target_cache_ttl_seconds = {}", user_config.connection.target_cache_ttl_seconds);
+            self.connection.target_cache_ttl_seconds = user_config.connection.target_cache_ttl_seconds;
+        }
+
+        // Merge RDP config
+        if user_config.rdp.auto_launch != self.rdp.auto_launch {
+            debug!("User override: rdp.auto_launch = {}", user_config.rdp.auto_launch);
+            self.rdp.auto_launch = user_config.rdp.auto_launch;
+        }
+        if user_config.rdp.preferred_client != self.rdp.preferred_client {
+            debug!("User override: rdp.preferred_client = {}", user_config.rdp.preferred_client);
+            self.rdp.preferred_client = user_config.rdp.preferred_client;
+        }
+        if user_config.rdp.fullscreen != self.rdp.fullscreen {
+            debug!("User override: rdp.fullscreen = {}", user_config.rdp.fullscreen);
+            self.rdp.fullscreen = user_config.rdp.fullscreen;
+        }
+        if user_config.rdp.resolution != self.rdp.resolution {
+            debug!("User override: rdp.resolution = {}", user_config.rdp.resolution);
+            self.rdp.resolution = user_config.rdp.resolution;
+        }
+
+        // Merge advanced config
+        if user_config.advanced.debug_mode != self.advanced.debug_mode {
+            debug!("User override: advanced.debug_mode = {}", user_config.advanced.debug_mode);
+            self.advanced.debug_mode = user_config.advanced.debug_mode;
+        }
+        if user_config.advanced.developer_tools != self.advanced.developer_tools {
+            debug!("User override: advanced.developer_tools = {}", user_config.advanced.developer_tools);
+            self.advanced.developer_tools = user_config.advanced.developer_tools;
+        }
+        if user_config.advanced.crash_reporting != self.advanced.crash_reporting {
+            debug!("User override: advanced.crash_reporting = {}", user_config.advanced.crash_reporting);
+            self.advanced.crash_reporting = user_config.advanced.crash_reporting;
+        }
+        if user_config.advanced.telemetry != self.advanced.telemetry {
+            debug!("User override: advanced.telemetry = {}", user_config.advanced.telemetry);
+            self.advanced.telemetry = user_config.advanced.telemetry;
+        }
+
+        // Merge boundary config
+        if user_config.boundary.cli_path != self.boundary.cli_path {
+            debug!("User override: boundary.cli_path = {}", user_config.boundary.cli_path);
+            self.boundary.cli_path = user_config.boundary.cli_path;
+        }
+        if user_config.boundary.auto_detect != self.boundary.auto_detect {
+            debug!("User override: boundary.auto_detect = {}", user_config.boundary.auto_detect);
+            self.boundary.auto_detect = user_config.boundary.auto_detect;
+        }
+        if user_config.boundary.min_version != self.boundary.min_version {
+            debug!("User override: boundary.min_version = {}", user_config.boundary.min_version);
+            self.boundary.min_version = user_config.boundary.min_version;
+        }
+    }
+}
+
+// Platform-specific user profile directory resolution
+fn get_user_profile_directory() -> Result<PathBuf, String> {
+    let user_dir = if let Some(mut dir) = dirs::home_dir() {
+        // All platforms: ~/.regis/
+        dir.push(".regis");
+        dir
+    } else {
+        return Err("Failed to determine user home directory".to_string());
+    };
+
+    // Create directory if it doesn't exist
+    if let Err(e) = fs::create_dir_all(&user_dir) {
+        return Err(format!("Failed to create user profile directory {:?}: {}", user_dir, e));
+    }
+
+    info!("User profile directory resolved: {:?}", user_dir);
+    Ok(user_dir)
+}
+
+// User preferences from the profile directory, kept in memory only when it is unavailable
+fn load_preferences() -> PreferenceStore {
+    match get_user_profile_directory() {
+        Ok(dir) => PreferenceStore::load(dir.join(preferences::PREFERENCES_FILE)),
+        Err(e) => {
+            warn!("User preferences will not be saved: {}", e);
+            PreferenceStore::in_memory()
+        }
+    }
+}
+
+// Discovery cache in the profile directory, disabled when the TTL is 0 or there is no profile
+fn open_discovery_cache(config: &ConnectionConfig) -> DiscoveryCache {
+    if config.target_cache_ttl_seconds == 0 {
+        info!("Target cache disabled");
+        return DiscoveryCache::disabled();
+    }
+
+    match get_user_profile_directory() {
+        Ok(dir) => DiscoveryCache::new(dir.join(cache::CACHE_DIR), Duration::from_secs(config.target_cache_ttl_seconds)),
+        Err(e) => {
+            warn!("Target cache disabled: {}", e);
+            DiscoveryCache::disabled()
+        }
+    }
+}
+
+// Get user configuration file path
+fn get_user_config_path() -> Result<PathBuf, String> {
+    let mut user_dir = get_user_profile_directory()?;
+    user_dir.push("config.json");
+    Ok(user_dir)
+}
+
+// Get user servers file path
+fn get_user_servers_path() -> Result<PathBuf, String> {
+    let mut user_dir = get_user_profile_directory()?;
+    user_dir.push("servers.json");
+    Ok(user_dir)
+}
+
+// Platform-specific log directory resolution
+fn get_log_directory(config_dir: &str) -> Result<PathBuf, String> {
+    let log_dir = if config_dir == "auto" {
+        if let Some(mut dir) = dirs::data_local_dir() {
+            // Windows: C:\Users\{user}\AppData\Local\Regis\logs
+            dir.push("Regis");
+            dir.push("logs");
+            dir
+        } else if let Some(mut dir) = dirs::home_dir() {
+            // macOS/Linux: ~/.regis/logs
+            dir.push(".regis");
+            dir.push("logs");
+            dir
+        } else {
+            // Fallback to temp directory
+            let mut temp_dir = std::env::temp_dir();
+            temp_dir.push("regis");
+            temp_dir.push("logs");
+            temp_dir
+        }
+    } else {
+        PathBuf::from(config_dir)
+    };
+
+    // Create directory if it doesn't exist
+    if let Err(e) = fs::create_dir_all(&log_dir) {
+        return Err(format!("Failed to create log directory {:?}: {}", log_dir, e));
+    }
+
+    Ok(log_dir)
+}
+
+// Initialize logging system
+#[instrument]
+fn init_logging(config: &LoggingConfig) -> Result<(), String> {
+    if !config.enabled {
+        println!("Logging disabled by configuration");
+        return Ok(());
+    }
+
+    // Every layer writes through RedactingMakeWriter so tokens never reach the console or log files.
+    // ANSI colors stay off everywhere: escape codes between a field name and its value would
+    // keep the key=value redaction rules from matching.
+    let filter = match config.level.as_str() {
+        "debug" => EnvFilter::new("debug"),
+        "info" => EnvFilter::new("info"),
+        "warn" => EnvFilter::new("warn"),
+        "error" => EnvFilter::new("error"),
+        _ => EnvFilter::new("info"),
+    };
+
+    let registry = tracing_subscriber::registry().with(filter);
+
+    // Build the subscriber with optional layers
+    if config.console_output && config.file_output {
+        // Both console and file logging
+        let log_dir = get_log_directory(&config.log_directory)?;
+        let file_appender = RollingFileAppender::new(Rotation::DAILY, &log_dir, "regis.log");
+
+        let console_layer = fmt::layer()
+            .with_writer(RedactingMakeWriter::new(std::io::stdout))
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_level(true)
+            .with_ansi(false);
+
+        let file_layer = fmt::layer()
+            .with_writer(RedactingMakeWriter::new(file_appender))
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_level(true)
+            .with_ansi(false)
+            .compact();
+
+        registry.with(console_layer).with(file_layer).init();
+        info!("File logging initialized in: {:?}", log_dir);
+    } else if config.console_output {
+        // Console only
+        let console_layer = fmt::layer()
+            .with_writer(RedactingMakeWriter::new(std::io::stdout))
+            .with_target(true)
+            .with_thread_ids(true)
+            .with_level(true)
+            .with_ansi(false);
+
+        registry.with(console_layer).init();
+    } else if config.file_output {
+        // File only
+        let log_dir = get_log_directory(&config.log_directory)?;
+        let file_appender = RollingFileAppender::new(Rotation::DAILY, &log_dir, "regis.log");
+
+        let file_layer = fmt::layer()
+            .with_writer(RedactingMakeWriter::new(file_appender))
+            .with_target(true)
+            .with_thread_ids(false)
+            .with_level(true)
+            .with_ansi(false)
+            .compact();
+
+        registry.with(file_layer).init();
+        info!("File logging initialized in: {:?}", log_dir);
+    } else {
+        // No logging layers, just the registry
+        registry.init();
+    }
+
+    info!("Logging system initialized");
+    info!("Log level: {}", config.level);
+    info!("Console output: {}", config.console_output);
+    info!("File output: {}", config.file_output);
+
+    Ok(())
+}
+
+// Resolve the correct Boundary CLI path for a server
+fn get_boundary_cli_path(server: &Server, state: &AppState) -> String {
+    // Use server-specific path if provided, then the auto-detected CLI, then the global config
+    if let Some(path) = &server.boundary_cli_path {
+        return path.clone();
+    }
+
+    if state.config.boundary.auto_detect {
+        if let Some(info) = state.boundary_cli.lock().unwrap().as_ref() {
+            return info.path.clone();
+        }
+    }
+
+    state.config.boundary.cli_path.clone()
+}
+
+// Find a compatible Boundary CLI for a server (or the global default when `server` is None).
+// The global result is cached in AppState for get_boundary_cli_path.
+#[instrument(skip(app))]
+async fn resolve_boundary_cli(app: &AppHandle, server: Option<&Server>) -> Result<BoundaryCliInfo, String> {
+    let state = app.state::<AppState>();
+    let server_override = server.and_then(|s| s.boundary_cli_path.as_deref());
+    let resource_dir = app.path().resource_dir().ok();
+
+    let candidates = cli_detect::candidate_paths(server_override, &state.config.boundary, resource_dir.as_deref());
+    let cli_options = BoundaryCommandOptions::default()
+        .with_timeout(state.config.operation_timeout(OperationKind::Request));
+
+    let info = cli_detect::detect_boundary_cli(
+        candidates,
+        &state.config.boundary.min_version,
+        state.config.boundary.auto_detect,
+        &cli_options,
+    ).await?;
+
+    if server_override.is_none() {
+        *state.boundary_cli.lock().unwrap() = Some(info.clone());
+    }
+
+    Ok(info)
+}
+
+// Execute Boundary CLI command with comprehensive logging; secret arguments are
+// replaced with placeholders in everything logged or returned
+#[instrument(skip(args))]
+pub async fn execute_boundary_command(
+    cli_path: &str,
+    args: Vec<&str>,
+    server_addr: Option<&str>,
+    options: &BoundaryCommandOptions,
+) -> Result<BoundaryCommandResult, BoundaryCommandError> {
+    let (mut cmd, command_str) = boundary_command(cli_path, &args, server_addr, options);
+
+    let timeout = options.timeout.unwrap_or(Duration::from_secs(DEFAULT_CLI_TIMEOUT_SECONDS));
+    debug!("Command deadline: {:?}", timeout);
+
+    let mut child = cmd.spawn().map_err(|e| {
+        let error = BoundaryCommandError::Spawn {
+            command: command_str.clone(),
+            message: e.to_string(),
+        };
+        error!("{}", error);
+        error
+    })?;
+
+    // Drain both pipes while waiting so a chatty child can't block on a full pipe
+    let stdout_task = read_pipe(child.stdout.take());
+    let stderr_task = read_pipe(child.stderr.take());
+
+    let cancel = options.cancel.clone().unwrap_or_default();
+
+    let status = tokio::select! {
+        status = child.wait() => status.map_err(|e| {
+            let error = BoundaryCommandError::Spawn {
+                command: command_str.clone(),
+                message: e.to_string(),
+            };
+            error!("{}", error);
+            error
+        })?,
+        _ = tokio::time::sleep(timeout) => {
+            kill_process_tree(&mut child).await;
+            let error = BoundaryCommandError::TimedOut {
+                command: command_str.clone(),
+                timeout_seconds: timeout.as_secs(),
+            };
+            error!("{}", error);
+            return Err(error);
+        }
+        _ = cancel.cancelled() => {
+            kill_process_tree(&mut child).await;
+            let error = BoundaryCommandError::Cancelled { command: command_str.clone() };
+            warn!("{}", error);
+            return Err(error);
+        }
+    };
+
+    let stdout = stdout_task.await.unwrap_or_default();
+    let stderr = stderr_task.await.unwrap_or_default();
+    let success = status.success();
+    let exit_code = status.code().unwrap_or(-1);
+
+    let result = BoundaryCommandResult {
+        success,
+        exit_code,
+        stdout: stdout.clone(),
+        stderr: stderr.clone(),
+        command: command_str.clone(),
+    };
+
+    if success {
+        info!("Boundary CLI command succeeded with exit code: {}", exit_code);
+        debug!("Command output: {}", stdout);
+    } else {
+        error!("Boundary CLI command failed with exit code: {}", exit_code);
+        error!("Command error: {}", stderr);
+        debug!("Command output: {}", stdout);
+    }
+
+    Ok(result)
+}
+
+// Build a Boundary CLI invocation with the per-invocation options applied, and the redacted
+// command line to log it by
+fn boundary_command(
+    cli_path: &str,
+    args: &[&str],
+    server_addr: Option<&str>,
+    options: &BoundaryCommandOptions,
+) -> (Command, String) {
+    let logged_args = redact::redact_args(args);
+    let command_str = format!("{} {}", cli_path, logged_args.join(" "));
+    info!("Executing Boundary CLI command: {}", command_str);
+    debug!("CLI path: {}", cli_path);
+    debug!("Arguments: {:?}", logged_args);
+
+    if let Some(addr) = server_addr {
+        debug!("Server address: {}", addr);
+    }
+
+    // Build the command
+    let mut cmd = Command::new(cli_path);
+    cmd.args(args);
+
+    // Add server address if provided
+    let mut full_command_args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    if let Some(addr) = server_addr {
+        cmd.arg("-addr").arg(addr);
+        full_command_args.push("-addr".to_string());
+        full_command_args.push(addr.to_string());
+    }
+
+    // Per-invocation keyring mode, token and environment (never the parent process env)
+    if let Some(keyring_type) = &options.keyring_type {
+        cmd.arg("-keyring-type").arg(keyring_type);
+        full_command_args.push("-keyring-type".to_string());
+        full_command_args.push(keyring_type.clone());
+    }
+    if let Some(token) = &options.token {
+        let token_ref = format!("env://{}", BOUNDARY_TOKEN_ENV);
+        cmd.env(BOUNDARY_TOKEN_ENV, token);
+        cmd.arg("-token").arg(&token_ref);
+        full_command_args.push("-token".to_string());
+        full_command_args.push(token_ref);
+    }
+    for (key, value) in &options.env {
+        cmd.env(key, value);
+    }
+    if !options.env.is_empty() {
+        debug!("Extra environment variables: {:?}", options.env.keys().collect::<Vec<_>>());
+    }
+
+    // Log the complete command that can be copied and run directly
+    let complete_command = format!("{} {}", cli_path, redact::redact_args(&full_command_args).join(" "));
+    info!("COMPLETE CLI COMMAND (copy this to test manually): {}", complete_command);
+
+    // Configure stdio
+    cmd.stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+
+    // Run in a dedicated process group so the whole tree can be killed
+    #[cfg(unix)]
+    cmd.process_group(0);
+
+    (cmd, command_str)
+}
+
+// Read a child pipe to the end in the background
+fn read_pipe<R: AsyncRead + Unpin + Send + 'static>(pipe: Option<R>) -> tokio::task::JoinHandle<String> {
+    tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            if let Err(e) = pipe.read_to_end(&mut buffer).await {
+                debug!("Failed to read CLI output pipe: {}", e);
+            }
+        }
+        String::from_utf8_lossy(&buffer).to_string()
+    })
+}
+
+// Kill a CLI child process together with anything it spawned
+async fn kill_process_tree(child: &mut tokio::process::Child) {
+    if let Some(pid) = child.id() {
+        info!("Killing Boundary CLI process tree (pid {})", pid);
+
+        // The child leads its own process group (see execute_boundary_command)
+        #[cfg(unix)]
+        unsafe {
+            libc::kill(-(pid as i32), libc::SIGKILL);
+        }
+
+        #[cfg(windows)]
+        {
+            let pid_str = pid.to_string();
+            if let Err(e) = Command::new("taskkill")
+                .args(["/PID", pid_str.as_str(), "/T", "/F"])
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await
+            {
+                warn!("taskkill failed for pid {}: {}", pid, e);
+            }
+        }
+    }
+
+    if let Err(e) = child.kill().await {
+        debug!("Boundary CLI process already exited: {}", e);
+    }
+}
+
+// Controller API client for a server, using the server's current token if known. The
+// server's HTTP client is built once and reused, so its connections are too.
+fn api_client_for_server(state: &AppState, server: &Server) -> Option<BoundaryApiClient> {
+    let token = {
+        let auth_tokens = state.auth_tokens.lock().unwrap();
+        auth_tokens.get(&server.id).map(|t| t.access_token.clone())
+    };
+
+    let mut api_clients = state.api_clients.lock().unwrap();
+    if let Some((url, client)) = api_clients.get(&server.id) {
+        if *url == server.url {
+            return Some(client.with_token(token));
+        }
+    }
+
+    match BoundaryApiClient::new(
+        &server.url,
+        None,
+        state.config.security.ssl_verify,
+        state.config.security.timeout_seconds,
+    ) {
+        Ok(client) => {
+            let authenticated = client.with_token(token);
+            api_clients.insert(server.id.clone(), (server.url.clone(), client));
+            Some(authenticated)
+        }
+        Err(e) => {
+            warn!("Controller API unavailable for server {}, using CLI only: {}", server.id, e);
+            None
+        }
+    }
+}
+
+// Listen settings for a target: its own preferences win over the server entry's
+fn listen_settings_for_target(state: &AppState, server: &Server, target_id: &str) -> ListenSettings {
+    let target = state.preferences.target(&server.id, target_id);
+    ListenSettings {
+        listen_addr: target.listen_addr.or_else(|| server.listen_addr.clone()),
+        listen_port: target.listen_port.or(server.listen_port),
+    }
+}
+
+// Build per-invocation CLI options carrying the server's current token, if known
+fn cli_options_for_server(state: &AppState, server: &Server) -> BoundaryCommandOptions {
+    let options = BoundaryCommandOptions::default()
+        .with_timeout(state.config.operation_timeout(OperationKind::Request))
+        .with_retry(RetryPolicy::from_config(&state.config.connection));
+
+    let auth_tokens = state.auth_tokens.lock().unwrap();
+    match auth_tokens.get(&server.id) {
+        Some(token) => options.with_token(&token.access_token),
+        None => options,
+    }
+}
+
+// A target as last discovered, if the discovery cache still holds it
+fn cached_target(state: &AppState, server_id: &str, target_id: &str) -> Option<BoundaryTarget> {
+    let user_id = cache_user_id(state, server_id)?;
+    let cached = state.discovery_cache.load(server_id, &user_id)?;
+    cached.targets.into_iter().find(|target| target.id == target_id)
+}
+
+// User the discovery cache is keyed by: whoever holds the server's current token
+fn cache_user_id(state: &AppState, server_id: &str) -> Option<String> {
+    let auth_tokens = state.auth_tokens.lock().unwrap();
+    auth_tokens.get(server_id).map(|t| t.user_id.clone()).filter(|id| !id.is_empty())
+}
+
+fn cache_scopes(state: &AppState, server_id: &str, user_id: &str, scopes: &[BoundaryScope]) {
+    let result = state.discovery_cache.update(server_id, user_id, |entry| {
+        entry.scopes = scopes.to_vec();
+        entry.scopes_fetched_at = Some(chrono::Utc::now());
+    });
+    if let Err(e) = result {
+        warn!("Failed to cache scopes for server {}: {}", server_id, e);
+    }
+}
+
+// Store freshly discovered targets and tell the frontend what changed since the cached list
+fn cache_targets(app: &AppHandle, state: &AppState, server_id: &str, scope_id: &str, targets: &[BoundaryTarget]) {
+    let Some(user_id) = cache_user_id(state, server_id) else {
+        debug!("No known user for server {}, not caching targets", server_id);
+        return;
+    };
+
+    let previous = state
+        .discovery_cache
+        .load(server_id, &user_id)
+        .filter(|entry| entry.targets_fetched_at.is_some() && entry.target_scope_id.as_deref() == Some(scope_id));
+
+    let fetched_at = chrono::Utc::now();
+    let result = state.discovery_cache.update(server_id, &user_id, |entry| {
+        entry.target_scope_id = Some(scope_id.to_string());
+        entry.targets = targets.to_vec();
+        entry.targets_fetched_at = Some(fetched_at);
+    });
+    if let Err(e) = result {
+        warn!("Failed to cache targets for server {}: {}", server_id, e);
+    }
+    if state.discovery_cache.is_enabled() {
+        let searchable = SearchableTargets { user_id: user_id.clone(), fetched_at, targets: Arc::new(targets.to_vec()) };
+        state.searchable_targets.lock().unwrap().insert(server_id.to_string(), searchable);
+    }
+
+    let Some(previous) = previous else {
+        return;
+    };
+    let diff = cache::diff_targets(&previous.targets, targets);
+    if diff.is_empty() {
+        debug!("Cached targets for server {} are up to date", server_id);
+        return;
+    }
+
+    info!(
+        "Target list for server {} changed: {} added, {} removed, {} changed",
+        server_id,
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+    let payload = TargetCacheDiff { server_id: server_id.to_string(), diff };
+    if let Err(e) = app.emit(TARGET_CACHE_DIFF_EVENT, payload) {
+        warn!("Failed to emit target cache diff: {}", e);
+    }
+}
+
+// CLI options for the interactive OIDC flow, which uses the CLI's own keyring
+fn auth_cli_options(state: &AppState) -> BoundaryCommandOptions {
+    BoundaryCommandOptions::default()
+        .with_timeout(state.config.operation_timeout(OperationKind::Authenticate))
+}
+
+// A registered cancellable operation, unregistered when dropped
+struct OperationGuard {
+    id: String,
+    token: CancellationToken,
+    operations: Arc<Mutex<HashMap<String, CancellationToken>>>,
+}
+
+impl OperationGuard {
+    fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        let mut operations = self.operations.lock().unwrap();
+        operations.remove(&self.id);
+    }
+}
+
+// Register an operation the frontend can cancel, under its own id or a generated one
+fn register_operation(state: &AppState, operation_id: Option<&str>) -> OperationGuard {
+    static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+    let id = operation_id
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| format!("op-{}", NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed)));
+    let token = CancellationToken::new();
+
+    {
+        let mut operations = state.operations.lock().unwrap();
+        operations.insert(id.clone(), token.clone());
+    }
+
+    debug!("Registered cancellable operation: {}", id);
+    OperationGuard {
+        id,
+        token,
+        operations: state.operations.clone(),
+    }
+}
+
+// Remember the current token for a server so API calls can authenticate
+fn remember_auth_token(state: &AppState, token: &StoredToken) {
+    let mut auth_tokens = state.auth_tokens.lock().unwrap();
+    auth_tokens.insert(token.server_id.clone(), token.clone());
+}
+
+// Run one controller API call for `action`. None means the CLI should be asked instead,
+// which is only safe when the request never reached the controller: a request that was
+// sent may have been acted on (a second authorize-session would create a second session),
+// and an answer such as 403 would be the same from the CLI. Like CLI commands, the call is
+// abandoned as soon as the operation is cancelled.
+async fn controller_call<T>(
+    cli_options: &BoundaryCommandOptions,
+    action: &str,
+    call: impl std::future::Future<Output = Result<T, boundary_api::ApiError>>,
+) -> Result<Option<T>, String> {
+    let result = match &cli_options.cancel {
+        Some(cancel) => tokio::select! {
+            result = call => result,
+            _ = cancel.cancelled() => {
+                let error = BoundaryCommandError::Cancelled { command: format!("controller API: {}", action) };
+                info!("{}", error);
+                return Err(error.into());
+            }
+        },
+        None => call.await,
+    };
+
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_unsent() => {
+            warn!("Controller API could not {}, falling back to CLI: {}", action, e);
+            Ok(None)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to {}: {}", action, e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Discover available auth methods from a Boundary server, retrying transient failures
+#[instrument]
+pub async fn discover_auth_methods(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryAuthMethod>, String> {
+    retry::with_retry(&cli_options.retry, "discover auth methods", cli_options.cancel.as_ref(), || {
+        discover_auth_methods_once(api, cli_path, cli_options, server_addr)
+    }).await
+}
+
+// One attempt to discover auth methods: controller API first, then the CLI
+async fn discover_auth_methods_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryAuthMethod>, String> {
+    info!("Discovering auth methods from server: {}", server_addr);
+
+    if let Some(api) = api {
+        if let Some(auth_methods) = controller_call(cli_options, "discover auth methods", api.list_auth_methods("global")).await? {
+            info!("Discovered {} auth methods via controller API", auth_methods.len());
+            debug!("Auth methods: {:?}", auth_methods);
+            return Ok(auth_methods);
+        }
+    }
+
+    let result = execute_boundary_command(
+        cli_path,
+        vec!["auth-methods", "list", "-format", "json"],
+        Some(server_addr),
+        cli_options,
+    ).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to discover auth methods: {}", result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Parse JSON output
+    match models::items_from_str::<AuthMethodItem>(&result.stdout) {
+        Ok(items) => {
+            let auth_methods: Vec<BoundaryAuthMethod> = items.into_iter().map(BoundaryAuthMethod::from).collect();
+
+            info!("Discovered {} auth methods", auth_methods.len());
+            debug!("Auth methods: {:?}", auth_methods);
+            Ok(auth_methods)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to parse auth methods JSON: {}", e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Discover every scope visible to the user (orgs and projects, recursively from global),
+// retrying transient failures
+#[instrument]
+pub async fn discover_scopes(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryScope>, String> {
+    retry::with_retry(&cli_options.retry, "discover scopes", cli_options.cancel.as_ref(), || {
+        discover_scopes_once(api, cli_path, cli_options, server_addr)
+    }).await
+}
+
+// One attempt to discover scopes: controller API first, then the CLI
+async fn discover_scopes_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryScope>, String> {
+    info!("Discovering scopes from server: {}", server_addr);
+
+    if let Some(api) = api {
+        if let Some(scopes) = controller_call(cli_options, "discover scopes", api.list_scopes("global", true)).await? {
+            info!("Discovered {} scopes via controller API", scopes.len());
+            debug!("Scopes: {:?}", scopes);
+            return Ok(scopes);
+        }
+    }
+
+    let result = execute_boundary_command(
+        cli_path,
+        vec!["scopes", "list", "-scope-id", "global", "-recursive", "-format", "json"],
+        Some(server_addr),
+        cli_options,
+    ).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to discover scopes: {}", result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Parse JSON output
+    match models::items_from_str::<ScopeItem>(&result.stdout) {
+        Ok(items) => {
+            let scopes: Vec<BoundaryScope> = items.into_iter().map(BoundaryScope::from).collect();
+
+            info!("Discovered {} scopes", scopes.len());
+            debug!("Scopes: {:?}", scopes);
+            Ok(scopes)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to parse scopes JSON: {}", e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Arrange a flat scope list into the global -> org -> project tree. Scopes whose parent
+// isn't visible to the user are attached to global so they stay selectable.
+pub fn build_scope_tree(scopes: &[BoundaryScope]) -> BoundaryScopeNode {
+    let known: std::collections::HashSet<&str> = scopes.iter().map(|s| s.id.as_str()).collect();
+    let mut children_of: HashMap<&str, Vec<&BoundaryScope>> = HashMap::new();
+
+    for scope in scopes.iter().filter(|s| s.id != "global") {
+        let parent = match scope.parent_scope_id.as_deref() {
+            Some(parent) if known.contains(parent) => parent,
+            _ => "global",
+        };
+        children_of.entry(parent).or_default().push(scope);
+    }
+
+    fn attach(scope: BoundaryScope, children_of: &HashMap<&str, Vec<&BoundaryScope>>) -> BoundaryScopeNode {
+        let mut children: Vec<BoundaryScopeNode> = children_of
+            .get(scope.id.as_str())
+            .map(|children| children.iter().map(|c| attach((*c).clone(), children_of)).collect())
+            .unwrap_or_default();
+        children.sort_by_key(|c| c.scope.name.to_lowercase());
+        BoundaryScopeNode { scope, children }
+    }
+
+    let root = scopes.iter().find(|s| s.id == "global").cloned().unwrap_or_else(|| BoundaryScope {
+        id: "global".to_string(),
+        name: "Global".to_string(),
+        scope_type: "global".to_string(),
+        description: String::new(),
+        parent_scope_id: None,
+    });
+
+    attach(root, &children_of)
+}
+
+// Discover the full scope hierarchy, rooted at global
+#[instrument]
+pub async fn discover_scope_tree(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<BoundaryScopeNode, String> {
+    let scopes = discover_scopes(api, cli_path, cli_options, server_addr).await?;
+    let tree = build_scope_tree(&scopes);
+    info!("Built scope tree with {} top-level scopes", tree.children.len());
+    Ok(tree)
+}
+
+// Discover OIDC-specific authentication capabilities
+#[instrument]
+async fn discover_oidc_auth_methods(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryAuthMethod>, String> {
+    info!("Discovering OIDC auth methods from server: {}", server_addr);
+
+    // Get all auth methods first
+    let all_auth_methods = discover_auth_methods(api, cli_path, cli_options, server_addr).await?;
+
+    // Filter for OIDC methods only
+    let oidc_methods: Vec<BoundaryAuthMethod> = all_auth_methods
+        .into_iter()
+        .filter(|method| method.method_type.eq_ignore_ascii_case("oidc"))
+        .collect();
+
+    info!("Found {} OIDC auth methods", oidc_methods.len());
+    debug!("OIDC auth methods: {:?}", oidc_methods);
+
+    if oidc_methods.is_empty() {
+        warn!("No OIDC authentication methods found on server: {}", server_addr);
+        return Err("No OIDC authentication methods available on this server".to_string());
+    }
+
+    Ok(oidc_methods)
+}
+
+// Check if server supports OIDC authentication
+#[instrument]
+async fn verify_oidc_support(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<bool, String> {
+    info!("Verifying OIDC support for server: {}", server_addr);
+
+    match discover_oidc_auth_methods(api, cli_path, cli_options, server_addr).await {
+        Ok(oidc_methods) => {
+            let supports_oidc = !oidc_methods.is_empty();
+            if supports_oidc {
+                info!("Server {} supports OIDC authentication with {} method(s)", server_addr, oidc_methods.len());
+            } else {
+                warn!("Server {} does not support OIDC authentication", server_addr);
+            }
+            Ok(supports_oidc)
+        }
+        Err(e) => {
+            error!("Failed to verify OIDC support for server {}: {}", server_addr, e);
+            Ok(false) // Return false instead of error - server just doesn't support OIDC
+        }
+    }
+}
+
+// Discover available targets from a Boundary server, retrying transient failures
+#[instrument]
+pub async fn discover_targets(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    query: &TargetQuery,
+) -> Result<Vec<BoundaryTarget>, String> {
+    retry::with_retry(&cli_options.retry, "discover targets", cli_options.cancel.as_ref(), || {
+        discover_targets_once(api, cli_path, cli_options, server_addr, query)
+    }).await
+}
+
+// One attempt to discover targets: controller API first, then the CLI
+async fn discover_targets_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    query: &TargetQuery,
+) -> Result<Vec<BoundaryTarget>, String> {
+    info!("Discovering targets from server: {}", server_addr);
+
+    if let Some(api) = api {
+        let scope = query.scope_id.as_deref().unwrap_or("global");
+        if let Some(targets) = controller_call(cli_options, "discover targets", api.list_targets(scope, query.recursive, query.filter.as_deref())).await? {
+            info!("Discovered {} targets via controller API", targets.len());
+            debug!("Targets: {:?}", targets);
+            return Ok(targets);
+        }
+    }
+
+    let mut args = vec!["targets", "list", "-format", "json"];
+
+    // Add scope filter if provided
+    if let Some(scope) = &query.scope_id {
+        info!("Filtering targets for scope: {}", scope);
+        args.push("-scope-id");
+        args.push(scope);
+    }
+    if query.recursive {
+        args.push("-recursive");
+    }
+    if let Some(filter) = &query.filter {
+        info!("Applying target filter: {}", filter);
+        args.push("-filter");
+        args.push(filter);
+    }
+
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to discover targets: {}", result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Parse JSON output
+    match models::items_from_str::<TargetItem>(&result.stdout) {
+        Ok(items) => {
+            let targets: Vec<BoundaryTarget> = items.into_iter().map(BoundaryTarget::from).collect();
+
+            info!("Discovered {} targets", targets.len());
+            debug!("Targets: {:?}", targets);
+            Ok(targets)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to parse targets JSON: {}", e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Discover every target below `scope_id` with a single recursive listing. Users without
+// list permission on that scope can still see targets in their own projects, so a
+// permission error falls back to listing each project.
+#[instrument]
+pub async fn discover_all_targets(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    scope_id: &str,
+    filter: Option<&str>,
+    discovery: &TargetDiscoveryOptions,
+) -> Result<Vec<BoundaryTarget>, String> {
+    info!("Discovering targets recursively from scope {} on server: {}", scope_id, server_addr);
+
+    let query = TargetQuery {
+        scope_id: Some(scope_id.to_string()),
+        recursive: true,
+        filter: filter.map(str::to_string),
+    };
+
+    match discover_targets(api, cli_path, cli_options, server_addr, &query).await {
+        Ok(targets) => {
+            info!("Discovered {} total targets under scope {}", targets.len(), scope_id);
+            Ok(targets)
+        }
+        Err(e) if error::is_permission_denied(&e) => {
+            warn!("Recursive target listing not permitted, listing each project instead: {}", e);
+            discover_targets_per_scope(api, cli_path, cli_options, server_addr, scope_id, filter, discovery).await
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// List targets project by project under `scope_id`, `discovery.concurrency` projects at a time
+async fn discover_targets_per_scope(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    scope_id: &str,
+    filter: Option<&str>,
+    discovery: &TargetDiscoveryOptions,
+) -> Result<Vec<BoundaryTarget>, String> {
+    // First, get all scopes; targets only live in projects
+    let scopes = discover_scopes(api, cli_path, cli_options, server_addr).await?;
+
+    let in_scope = |scope: &BoundaryScope| {
+        scope_id == "global" || scope.id == scope_id || scope.parent_scope_id.as_deref() == Some(scope_id)
+    };
+    let projects: Vec<BoundaryScope> = scopes
+        .into_iter()
+        .filter(|s| s.scope_type == "project" && in_scope(s))
+        .collect();
+    let total = projects.len();
+
+    info!("Discovering targets in {} projects, {} at a time", total, discovery.concurrency);
+
+    // Each project is listed in its own task; the semaphore bounds how many CLI
+    // processes or API requests run at once
+    let semaphore = Arc::new(Semaphore::new(discovery.concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, scope) in projects.iter().cloned().enumerate() {
+        let semaphore = semaphore.clone();
+        let api = api.cloned();
+        let cli_path = cli_path.to_string();
+        let cli_options = cli_options.clone();
+        let server_addr = server_addr.to_string();
+        let query = TargetQuery {
+            scope_id: Some(scope.id.clone()),
+            recursive: false,
+            filter: filter.map(str::to_string),
+        };
+
+        tasks.spawn(
+            async move {
+                let _permit = semaphore.acquire_owned().await.expect("discovery semaphore is never closed");
+                info!("Discovering targets in scope: {} ({})", scope.name, scope.id);
+                let result = discover_targets(api.as_ref(), &cli_path, &cli_options, &server_addr, &query).await;
+                (index, result)
+            }
+            .in_current_span(),
+        );
+    }
+
+    // Report projects as they finish, but keep the final list in scope order
+    let mut results: Vec<Vec<BoundaryTarget>> = vec![Vec::new(); total];
+    let mut first_error = None;
+    let mut failed = 0;
+    let mut completed = 0;
+
+    while let Some(joined) = tasks.join_next().await {
+        let (index, result) = match joined {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                error!("Target discovery task failed: {}", e);
+                continue;
+            }
+        };
+        let scope = &projects[index];
+        completed += 1;
+
+        let error = match result {
+            Ok(scope_targets) => {
+                info!("Found {} targets in scope {} ({}/{})", scope_targets.len(), scope.name, completed, total);
+                results[index] = scope_targets;
+                None
+            }
+            Err(e) => {
+                warn!("Failed to discover targets in scope {}: {}", scope.name, e);
+                failed += 1;
+                first_error.get_or_insert_with(|| e.clone());
+                Some(e)
+            }
+        };
+
+        if let Some(on_scope) = &discovery.on_scope {
+            on_scope(&ScopeTargets {
+                scope_id: scope.id.clone(),
+                scope_name: scope.name.clone(),
+                targets: results[index].clone(),
+                error,
+                completed,
+                total,
+            });
+        }
+    }
+
+    // Partial results are still useful; only fail when no project could be listed
+    if failed > 0 && failed == total {
+        return Err(first_error.unwrap_or_default());
+    }
+
+    let all_targets: Vec<BoundaryTarget> = results.into_iter().flatten().collect();
+    if failed > 0 {
+        warn!("Discovered {} targets; {} of {} projects could not be listed", all_targets.len(), failed, total);
+    } else {
+        info!("Discovered {} total targets across all scopes", all_targets.len());
+    }
+    debug!("All targets: {:?}", all_targets);
+    Ok(all_targets)
+}
+
+// Read one target with everything the controller reports about it, retrying transient failures
+#[instrument]
+pub async fn get_target_details(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+) -> Result<BoundaryTarget, String> {
+    retry::with_retry(&cli_options.retry, "read target", cli_options.cancel.as_ref(), || {
+        get_target_details_once(api, cli_path, cli_options, server_addr, target_id)
+    }).await
+}
+
+// One attempt to read a target: controller API first, then the CLI
+async fn get_target_details_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+) -> Result<BoundaryTarget, String> {
+    info!("Reading target details: {}", target_id);
+
+    if let Some(api) = api {
+        if let Some(target) = controller_call(cli_options, "read target", api.read_target(target_id)).await? {
+            debug!("Target details via controller API: {:?}", target);
+            return Ok(target);
+        }
+    }
+
+    let args = vec!["targets", "read", "-id", target_id, "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to read target {}: {}", target_id, result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    match models::item_from_str::<TargetItem>(&result.stdout) {
+        Ok(item) => {
+            let target = BoundaryTarget::from(item);
+            debug!("Target details: {:?}", target);
+            Ok(target)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to parse target JSON: {}", e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Read a host set, retrying transient failures
+#[instrument]
+pub async fn read_host_set(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    host_set_id: &str,
+) -> Result<BoundaryHostSet, String> {
+    retry::with_retry(&cli_options.retry, "read host set", cli_options.cancel.as_ref(), || {
+        read_host_set_once(api, cli_path, cli_options, server_addr, host_set_id)
+    }).await
+}
+
+// One attempt to read a host set: controller API first, then the CLI
+async fn read_host_set_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    host_set_id: &str,
+) -> Result<BoundaryHostSet, String> {
+    debug!("Reading host set: {}", host_set_id);
+
+    if let Some(api) = api {
+        if let Some(host_set) = controller_call(cli_options, "read host set", api.read_host_set(host_set_id)).await? {
+            return Ok(host_set);
+        }
+    }
+
+    let args = vec!["host-sets", "read", "-id", host_set_id, "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to read host set {}: {}", host_set_id, result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    models::item_from_str::<HostSetItem>(&result.stdout)
+        .map(BoundaryHostSet::from)
+        .map_err(|e| format!("Failed to parse host set JSON: {}", e))
+}
+
+// List the hosts of a host catalog, retrying transient failures
+#[instrument]
+pub async fn list_hosts(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    host_catalog_id: &str,
+) -> Result<Vec<BoundaryHost>, String> {
+    retry::with_retry(&cli_options.retry, "list hosts", cli_options.cancel.as_ref(), || {
+        list_hosts_once(api, cli_path, cli_options, server_addr, host_catalog_id)
+    }).await
+}
+
+// One attempt to list hosts: controller API first, then the CLI
+async fn list_hosts_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    host_catalog_id: &str,
+) -> Result<Vec<BoundaryHost>, String> {
+    debug!("Listing hosts in catalog: {}", host_catalog_id);
+
+    if let Some(api) = api {
+        if let Some(hosts) = controller_call(cli_options, "list hosts", api.list_hosts(host_catalog_id)).await? {
+            return Ok(hosts);
+        }
+    }
+
+    let args = vec!["hosts", "list", "-host-catalog-id", host_catalog_id, "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to list hosts in catalog {}: {}", host_catalog_id, result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    models::items_from_str::<HostItem>(&result.stdout)
+        .map(|items| items.into_iter().map(BoundaryHost::from).collect())
+        .map_err(|e| format!("Failed to parse hosts JSON: {}", e))
+}
+
+// The host sets a target draws from, each with its hosts in host set order
+#[instrument]
+pub async fn discover_target_hosts(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+) -> Result<Vec<BoundaryHostSet>, String> {
+    let target = get_target_details(api, cli_path, cli_options, server_addr, target_id).await?;
+    info!("Target {} has {} host sources", target_id, target.host_source_ids.len());
+
+    // Host sets only reference host ids; list each catalog once to resolve them
+    let mut catalogs: HashMap<String, Vec<BoundaryHost>> = HashMap::new();
+    let mut host_sets = Vec::with_capacity(target.host_source_ids.len());
+
+    for host_set_id in &target.host_source_ids {
+        let mut host_set = read_host_set(api, cli_path, cli_options, server_addr, host_set_id).await?;
+
+        if !catalogs.contains_key(&host_set.host_catalog_id) {
+            let hosts = list_hosts(api, cli_path, cli_options, server_addr, &host_set.host_catalog_id).await?;
+            catalogs.insert(host_set.host_catalog_id.clone(), hosts);
+        }
+        let catalog = &catalogs[&host_set.host_catalog_id];
+
+        host_set.hosts = host_set
+            .host_ids
+            .iter()
+            .filter_map(|id| catalog.iter().find(|host| &host.id == id).cloned())
+            .collect();
+        debug!("Host set {} resolves to {} hosts", host_set.id, host_set.hosts.len());
+        host_sets.push(host_set);
+    }
+
+    Ok(host_sets)
+}
+
+// List every alias the user can see, retrying transient failures
+#[instrument]
+pub async fn discover_aliases(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryAlias>, String> {
+    retry::with_retry(&cli_options.retry, "list aliases", cli_options.cancel.as_ref(), || {
+        discover_aliases_once(api, cli_path, cli_options, server_addr)
+    }).await
+}
+
+// One attempt to list aliases: controller API first, then the CLI
+async fn discover_aliases_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+) -> Result<Vec<BoundaryAlias>, String> {
+    debug!("Listing aliases");
+
+    if let Some(api) = api {
+        if let Some(aliases) = controller_call(cli_options, "list aliases", api.list_aliases()).await? {
+            return Ok(aliases);
+        }
+    }
+
+    let args = vec!["aliases", "list", "-scope-id", "global", "-recursive", "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to list aliases: {}", result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    models::items_from_str::<AliasItem>(&result.stdout)
+        .map(|items| items.into_iter().map(BoundaryAlias::from).collect())
+        .map_err(|e| format!("Failed to parse aliases JSON: {}", e))
+}
+
+// Find the alias with this value (aliases are case-insensitive, like DNS names)
+#[instrument]
+pub async fn resolve_alias(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    value: &str,
+) -> Result<BoundaryAlias, String> {
+    let aliases = discover_aliases(api, cli_path, cli_options, server_addr).await?;
+    let alias = aliases
+        .into_iter()
+        .find(|alias| alias.value.eq_ignore_ascii_case(value))
+        .ok_or_else(|| format!("No alias named '{}'", value))?;
+
+    match &alias.destination_id {
+        Some(target_id) => info!("Alias {} resolves to target {}", value, target_id),
+        None => return Err(format!("Alias '{}' does not point to a target", value)),
+    }
+    Ok(alias)
+}
+
+// Public id prefixes of Boundary's target types
+const TARGET_ID_PREFIXES: [&str; 3] = ["ttcp", "tssh", "trdp"];
+
+// Boundary target ids look like "ttcp_1234567890": a target type prefix and 10 base62
+// characters. Anything else, "test_db" included, is taken for an alias.
+pub fn is_target_id(reference: &str) -> bool {
+    match reference.split_once('_') {
+        Some((prefix, suffix)) => {
+            TARGET_ID_PREFIXES.contains(&prefix) && suffix.len() == 10 && suffix.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        None => false,
+    }
+}
+
+// Target id and alias host for a target id or alias
+#[instrument]
+pub async fn resolve_target_reference(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    reference: &str,
+) -> Result<(String, Option<String>), String> {
+    if is_target_id(reference) {
+        return Ok((reference.to_string(), None));
+    }
+
+    let alias = resolve_alias(api, cli_path, cli_options, server_addr, reference).await?;
+    Ok((alias.destination_id.unwrap_or_default(), alias.host_id))
+}
+
+// Fill in the aliases of targets that were listed without them
+pub fn attach_aliases(targets: &mut [BoundaryTarget], aliases: &[BoundaryAlias]) {
+    for target in targets.iter_mut().filter(|t| t.aliases.is_empty()) {
+        target.aliases = aliases
+            .iter()
+            .filter(|alias| alias.destination_id.as_deref() == Some(target.id.as_str()))
+            .map(|alias| alias.value.clone())
+            .collect();
+    }
+}
+
+// Fail before authorizing when the user lacks authorize-session on the target, instead of
+// with the CLI's permission error. `known` is a possibly stale copy of the target (e.g. from
+// the discovery cache); a refusal is confirmed with a fresh read since grants change, and a
+// failed read lets authorization go ahead and speak for itself.
+#[instrument(skip(known))]
+pub async fn check_session_permission(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+    known: Option<&BoundaryTarget>,
+) -> Result<(), String> {
+    if known.is_some_and(|target| target.can_authorize_session()) {
+        return Ok(());
+    }
+
+    let target = match get_target_details(api, cli_path, cli_options, server_addr, target_id).await {
+        Ok(target) => target,
+        Err(e) => {
+            warn!("Could not check permissions on target {}, authorizing anyway: {}", target_id, e);
+            return Ok(());
+        }
+    };
+
+    if target.can_authorize_session() {
+        return Ok(());
+    }
+
+    let error_msg = format!(
+        "You are not permitted to connect to target '{}': {} is not among your allowed actions ({})",
+        target.name,
+        AUTHORIZE_SESSION_ACTION,
+        target.authorized_actions.join(", ")
+    );
+    warn!("{}", error_msg);
+    Err(error_msg)
+}
+
+// Authorize a session for a specific target. Every authorization creates a session, so
+// only failures that prove the controller never saw the request are retried; after a
+// timeout or a 5xx a session may already exist that nobody would know to cancel.
+#[instrument]
+pub async fn authorize_session(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+    host_id: Option<&str>,
+) -> Result<BoundarySessionAuthorization, String> {
+    retry::with_retry_classified(
+        &cli_options.retry,
+        "authorize session",
+        cli_options.cancel.as_ref(),
+        retry::classify_unsent_error,
+        || authorize_session_once(api, cli_path, cli_options, server_addr, target_id, host_id),
+    ).await
+}
+
+// One attempt to authorize session: controller API first, then the CLI
+async fn authorize_session_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    target_id: &str,
+    host_id: Option<&str>,
+) -> Result<BoundarySessionAuthorization, String> {
+    info!("Authorizing session for target: {}", target_id);
+
+    if let Some(api) = api {
+        if let Some(authorization) = controller_call(cli_options, "authorize session", api.authorize_session(target_id, host_id)).await? {
+            info!("Session authorized via controller API: {}", authorization.session_id);
+            debug!("Authorization details: {:?}", authorization);
+            return Ok(authorization);
+        }
+    }
+
+    let mut args = vec!["targets", "authorize-session", "-id", target_id, "-format", "json"];
+
+    // Add host ID if specified
+    if let Some(host) = host_id {
+        info!("Targeting specific host: {}", host);
+        args.push("-host-id");
+        args.push(host);
+    }
+
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        let error_msg = format!("Failed to authorize session for target {}: {}", target_id, result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Parse JSON output
+    match models::item_from_str::<SessionAuthorizationItem>(&result.stdout) {
+        Ok(item) => {
+            let authorization = BoundarySessionAuthorization::from(item);
+
+            info!("Session authorized successfully: {}", authorization.session_id);
+            debug!("Authorization details: {:?}", authorization);
+            Ok(authorization)
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to parse session authorization JSON: {}", e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Start the `boundary connect` proxy for an authorized session; returns as soon as the
+// proxy reports its listener, leaving it running for the lifetime of the connection
+#[instrument]
+pub async fn establish_connection(
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_id: &str,
+    authorization: &BoundarySessionAuthorization,
+    connection_type: ConnectionType,
+    target_name: &str,
+    listen: &ListenSettings,
+) -> Result<ConnectionProcess, String> {
+    info!("Establishing {} connection for session: {}", format!("{:?}", connection_type).to_lowercase(), authorization.session_id);
+
+    let type_str = match connection_type {
+        ConnectionType::SSH => "ssh",
+        ConnectionType::RDP => "rdp",
+        ConnectionType::TCP => "tcp",
+        ConnectionType::HTTP => "http",
+    };
+
+    let mut args = vec![
+        "connect",
+        type_str,
+        "-authz-token",
+        &authorization.authorization_token,
+        "-format",
+        "json",
+    ];
+    let listen_args = listen.args();
+    args.extend(listen_args.iter().map(String::as_str));
+
+    // The timeout only bounds startup; the proxy runs until the connection is terminated
+    let (cmd, command_str) = boundary_command(cli_path, &args, None, cli_options);
+    let timeout = cli_options.timeout.unwrap_or(Duration::from_secs(DEFAULT_CLI_TIMEOUT_SECONDS));
+    let proxy = connections::start_proxy(cmd, &command_str, timeout, cli_options.cancel.clone())
+        .await
+        .map_err(|e| {
+            let error_msg = format!("Failed to establish {} connection: {}", type_str, e);
+            error!("{}", error_msg);
+            error_msg
+        })?;
+
+    let connection = BoundaryConnection {
+        session_id: authorization.session_id.clone(),
+        server_id: server_id.to_string(),
+        target_id: authorization.target_id.clone(),
+        target_name: target_name.to_string(),
+        connection_type: type_str.to_string(),
+        local_address: proxy.startup.address.clone(),
+        local_port: proxy.startup.port,
+        status: "active".to_string(),
+        created_time: chrono::Utc::now().to_rfc3339(),
+        expiration_time: proxy.startup.expiration.clone().or_else(|| authorization.expiration_time.clone()),
+        connection_limit: proxy.startup.connection_limit,
+        fixed_port: listen.listen_port.is_some(),
+    };
+
+    let process = ConnectionProcess::new(connection, proxy);
+
+    // A proxy elsewhere than the configured port would break whatever relies on that port
+    if let Some(port) = listen.listen_port.filter(|port| *port != process.connection.local_port) {
+        let error_msg = format!(
+            "Local port {} is already in use: the proxy listens on {}:{} instead",
+            port, process.connection.local_address, process.connection.local_port
+        );
+        error!("{}", error_msg);
+        if let Err(e) = process.terminate().await {
+            warn!("Failed to stop the misplaced proxy: {}", e);
+        }
+        return Err(error_msg);
+    }
+
+    info!("Connection established successfully: {}:{}", process.connection.local_address, process.connection.local_port);
+    debug!("Connection details: {:?}", process.connection);
+    Ok(process)
+}
+
+// Outcome of terminating a connection. The local proxy and the controller session are
+// stopped independently, so one can fail while the other succeeds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionTermination {
+    pub session_id: String,
+    pub proxy_stopped: bool,
+    pub session_status: Option<String>, // Last status the controller reported
+    pub errors: Vec<String>,
+}
+
+impl ConnectionTermination {
+    pub fn is_complete(&self) -> bool {
+        self.proxy_stopped && self.session_status.as_deref() == Some(SESSION_TERMINATED)
+    }
+}
+
+// Terminate an active connection: stop its proxy, then cancel its session on the
+// controller so it does not stay usable until it expires
+#[instrument]
+pub async fn terminate_connection(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    process: ConnectionProcess,
+) -> ConnectionTermination {
+    let session_id = process.connection.session_id.clone();
+    info!("Terminating connection for session: {}", session_id);
+
+    let mut termination = ConnectionTermination {
+        session_id: session_id.clone(),
+        proxy_stopped: true,
+        session_status: None,
+        errors: Vec::new(),
+    };
+
+    if let Err(e) = process.terminate().await {
+        warn!("{}", e);
+        termination.proxy_stopped = false;
+        termination.errors.push(e);
+    }
+
+    match cancel_session(api, cli_path, cli_options, server_addr, &session_id).await {
+        Ok(session) => termination.session_status = Some(session.status),
+        Err(e) => {
+            termination.session_status = read_session(api, cli_path, cli_options, server_addr, &session_id)
+                .await
+                .ok()
+                .map(|session| session.status);
+            termination.errors.push(e);
+        }
+    }
+
+    if termination.is_complete() {
+        info!("Connection terminated and session {} cancelled", session_id);
+    } else {
+        warn!("Connection for session {} only partly terminated: {}", session_id, termination.errors.join("; "));
+    }
+    termination
+}
+
+// Cancel a session and wait until the controller reports it terminated
+#[instrument]
+pub async fn cancel_session(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    session_id: &str,
+) -> Result<SessionItem, String> {
+    let cancelled = retry::with_retry(&cli_options.retry, "cancel session", cli_options.cancel.as_ref(), || {
+        cancel_session_once(api, cli_path, cli_options, server_addr, session_id)
+    }).await;
+
+    let mut session = match cancelled {
+        Ok(session) => session,
+        Err(e) => {
+            let error_msg = format!("Failed to cancel session {} on the controller: {}", session_id, e);
+            error!("{}", error_msg);
+
+            // A session that has already ended (e.g. expired) needs no cancelling
+            return match read_session(api, cli_path, cli_options, server_addr, session_id).await {
+                Ok(session) if session.status == SESSION_TERMINATED => Ok(session),
+                _ => Err(error_msg),
+            };
+        }
+    };
+
+    // Cancelling moves the session to "canceling" until its workers have closed it
+    for _ in 0..SESSION_CANCEL_CONFIRM_ATTEMPTS {
+        if session.status == SESSION_TERMINATED {
+            info!("Session {} terminated", session_id);
+            return Ok(session);
+        }
+        tokio::time::sleep(SESSION_CANCEL_CONFIRM_INTERVAL).await;
+        session = read_session(api, cli_path, cli_options, server_addr, session_id)
+            .await
+            .map_err(|e| format!("Failed to confirm session {} was cancelled: {}", session_id, e))?;
+    }
+
+    if session.status == SESSION_TERMINATED {
+        return Ok(session);
+    }
+    let error_msg = format!("Session {} was cancelled but is still {} on the controller", session_id, session.status);
+    warn!("{}", error_msg);
+    Err(error_msg)
+}
+
+// One attempt to cancel a session: controller API first, then the CLI
+async fn cancel_session_once(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    session_id: &str,
+) -> Result<SessionItem, String> {
+    info!("Cancelling session: {}", session_id);
+
+    if let Some(api) = api {
+        if let Some(session) = controller_call(cli_options, "cancel session", api.cancel_session(session_id)).await? {
+            info!("Session {} cancelled via controller API, now {}", session_id, session.status);
+            return Ok(session);
+        }
+    }
+
+    let args = vec!["sessions", "cancel", "-id", session_id, "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        return Err(result.stderr.trim().to_string());
+    }
+
+    let session = models::item_from_str::<SessionItem>(&result.stdout)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+    info!("Session {} cancelled, now {}", session_id, session.status);
+    Ok(session)
+}
+
+// Read a session: controller API first, then the CLI
+async fn read_session(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    session_id: &str,
+) -> Result<SessionItem, String> {
+    if let Some(api) = api {
+        if let Some(session) = controller_call(cli_options, "read session", api.read_session(session_id)).await? {
+            return Ok(session);
+        }
+    }
+
+    let args = vec!["sessions", "read", "-id", session_id, "-format", "json"];
+    let result = execute_boundary_command(cli_path, args, Some(server_addr), cli_options).await?;
+
+    if !result.success {
+        return Err(result.stderr.trim().to_string());
+    }
+    models::item_from_str::<SessionItem>(&result.stdout).map_err(|e| format!("Failed to parse session JSON: {}", e))
+}
+
+// Check if a command/executable exists in the system
+async fn check_command_exists(command: &str) -> bool {
+    match Command::new("which")
+        .arg(command)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+    {
+        Ok(status) => status.success(),
+        Err(_) => false,
+    }
+}
+
+// Check if a file exists at a specific path
+fn check_file_exists(path: &str) -> bool {
+    std::path::Path::new(path).exists()
+}
+
+// Get the current platform
+fn get_current_platform() -> String {
+    #[cfg(target_os = "windows")]
+    return "windows".to_string();
+
+    #[cfg(target_os = "macos")]
+    return "macos".to_string();
+
+    #[cfg(target_os = "linux")]
+    return "linux".to_string();
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+    return "unknown".to_string();
+}
+
+// Detect RDP clients on Windows
+#[instrument]
+async fn detect_windows_rdp_clients() -> Vec<RdpClientInfo> {
+    info!("Detecting Windows RDP clients");
+    let mut clients = Vec::new();
+
+    // Windows built-in RDP client (mstsc)
+    if check_command_exists("mstsc").await {
+        clients.push(RdpClientInfo {
+            name: "Microsoft Terminal Services Client".to_string(),
+            executable_path: "mstsc".to_string(),
+            client_type: "builtin".to_string(),
+            platform: "windows".to_string(),
+            version: None,
+            supports_fullscreen: true,
+            supports_resolution: true,
+            supports_credentials: true,
+        });
+        info!("Found Windows built-in RDP client (mstsc)");
+    }
+
+    // Check for other Windows RDP clients in common locations
+    let other_clients = vec![
+        ("Royal TS", "C:\\Program Files\\Royal TS V6\\RoyalTS.exe", "third_party"),
+        ("Remote Desktop Manager", "C:\\Program Files\\Devolutions\\Remote Desktop Manager\\RemoteDesktopManager.exe", "third_party"),
+        ("Jump Desktop", "C:\\Program Files\\Jump Desktop\\JumpDesktop.exe", "third_party"),
+    ];
+
+    for (name, path, client_type) in other_clients {
+        if check_file_exists(path) {
+            clients.push(RdpClientInfo {
+                name: name.to_string(),
+                executable_path: path.to_string(),
+                client_type: client_type.to_string(),
+                platform: "windows".to_string(),
+                version: None,
+                supports_fullscreen: true,
+                supports_resolution: true,
+                supports_credentials: true,
+            });
+            info!("Found Windows RDP client: {}", name);
+        }
+    }
+
+    info!("Detected {} Windows RDP clients", clients.len());
+    clients
+}
+
+// Detect RDP clients on macOS
+#[instrument]
+async fn detect_macos_rdp_clients() -> Vec<RdpClientInfo> {
+    info!("Detecting macOS RDP clients");
+    let mut clients = Vec::new();
+
+    // Common macOS RDP client paths
+    let macos_clients = vec![
+        (
+            "Microsoft Remote Desktop",
+            "/Applications/Microsoft Remote Desktop.app/Contents/MacOS/Microsoft Remote Desktop",
+            "microsoft"
+        ),
+        (
+            "Royal TSX",
+            "/Applications/Royal TSX.app/Contents/MacOS/Royal TSX",
+            "third_party"
+        ),
+        (
+            "Jump Desktop",
+            "/Applications/Jump Desktop.app/Contents/MacOS/Jump Desktop",
+            "third_party"
+        ),
+        (
+            "Screens for Organizations",
+            "/Applications/Screens for Organizations.app/Contents/MacOS/Screens for Organizations",
+            "third_party"
+        ),
+        (
+            "Remote Desktop Scanner",
+            "/Applications/Remote Desktop Scanner.app/Contents/MacOS/Remote Desktop Scanner",
+            "third_party"
+        ),
+    ];
+
+    for (name, path, client_type) in macos_clients {
+        if check_file_exists(path) {
+            clients.push(RdpClientInfo {
+                name: name.to_string(),
+                executable_path: path.to_string(),
+                client_type: client_type.to_string(),
+                platform: "macos".to_string(),
+                version: None,
+                supports_fullscreen: true,
+                supports_resolution: true,
+                supports_credentials: true,
+            });
+            info!("Found macOS RDP client: {}", name);
+        }
+    }
+
+    info!("Detected {} macOS RDP clients", clients.len());
+    clients
+}
+
+// Detect RDP clients on Linux
+#[instrument]
+async fn detect_linux_rdp_clients() -> Vec<RdpClientInfo> {
+    info!("Detecting Linux RDP clients");
+    let mut clients = Vec::new();
+
+    // Common Linux RDP clients
+    let linux_clients = vec![
+        ("xfreerdp", "xfreerdp", "freerdp"),
+        ("rdesktop", "rdesktop", "rdesktop"),
+        ("remmina", "remmina", "remmina"),
+        ("vinagre", "vinagre", "vinagre"),
+        ("tsclient", "tsclient", "tsclient"),
+    ];
+
+    for (name, command, client_type) in linux_clients {
+        if check_command_exists(command).await {
+            clients.push(RdpClientInfo {
+                name: name.to_string(),
+                executable_path: command.to_string(),
+                client_type: client_type.to_string(),
+                platform: "linux".to_string(),
+                version: None,
+                supports_fullscreen: true,
+                supports_resolution: true,
+                supports_credentials: true,
+            });
+            info!("Found Linux RDP client: {}", name);
+        }
+    }
+
+    info!("Detected {} Linux RDP clients", clients.len());
+    clients
+}
+
+// Detect all available RDP clients on the current platform
+#[instrument]
+async fn detect_rdp_clients() -> Result<DetectedRdpClients, String> {
+    let platform = get_current_platform();
+    info!("Detecting RDP clients for platform: {}", platform);
+
+    let clients = match platform.as_str() {
+        "windows" => detect_windows_rdp_clients().await,
+        "macos" => detect_macos_rdp_clients().await,
+        "linux" => detect_linux_rdp_clients().await,
+        _ => {
+            warn!("Unsupported platform for RDP client detection: {}", platform);
+            Vec::new()
+        }
+    };
+
+    // Determine default client based on platform preferences
+    let default_client = if !clients.is_empty() {
+        match platform.as_str() {
+            "windows" => {
+                // Prefer built-in mstsc on Windows
+                clients.iter()
+                    .find(|c| c.client_type == "builtin")
+                    .or_else(|| clients.first())
+                    .map(|c| c.name.clone())
+            },
+            "macos" => {
+                // Prefer Microsoft Remote Desktop on macOS
+                clients.iter()
+                    .find(|c| c.name.contains("Microsoft Remote Desktop"))
+                    .or_else(|| clients.first())
+                    .map(|c| c.name.clone())
+            },
+            "linux" => {
+                // Prefer xfreerdp on Linux
+                clients.iter()
+                    .find(|c| c.name == "xfreerdp")
+                    .or_else(|| clients.first())
+                    .map(|c| c.name.clone())
+            },
+            _ => clients.first().map(|c| c.name.clone()),
+        }
+    } else {
+        None
+    };
+
+    let result = DetectedRdpClients {
+        clients,
+        default_client,
+        platform,
+    };
+
+    info!("RDP client detection completed: {} clients found, default: {:?}",
+          result.clients.len(), result.default_client);
+    debug!("Detected RDP clients: {:?}", result);
+
+    Ok(result)
+}
+
+// Launch an RDP client with connection details
+#[instrument]
+async fn launch_rdp_client(
+    client_info: &RdpClientInfo,
+    connection: &BoundaryConnection,
+    config: &RdpConfig,
+) -> Result<(), String> {
+    info!("Launching RDP client: {} for connection {}:{}",
+          client_info.name, connection.local_address, connection.local_port);
+
+    let platform = get_current_platform();
+    let mut cmd = Command::new(&client_info.executable_path);
+
+    match platform.as_str() {
+        "windows" if client_info.name.contains("Microsoft Terminal Services Client") => {
+            // Windows mstsc command line arguments
+            cmd.arg(&format!("{}:{}", connection.local_address, connection.local_port));
+
+            if config.fullscreen {
+                cmd.arg("/f");
+            }
+
+            if config.resolution != "auto" {
+                cmd.arg("/w").arg(config.resolution.split('x').next().unwrap_or("1920"));
+                cmd.arg("/h").arg(config.resolution.split('x').nth(1).unwrap_or("1080"));
+            }
+        },
+        "macos" if client_info.name.contains("Microsoft Remote Desktop") => {
+            // Microsoft Remote Desktop for macOS uses different arguments
+            cmd.arg("rdp://").arg(&format!("{}:{}", connection.local_address, connection.local_port));
+        },
+        "linux" if client_info.name == "xfreerdp" => {
+            // xfreerdp command line arguments
+            cmd.arg(&format!("/v:{}:{}", connection.local_address, connection.local_port));
+
+            if config.fullscreen {
+                cmd.arg("/f");
+            }
+
+            if config.resolution != "auto" {
+                cmd.arg(&format!("/size:{}", config.resolution));
+            }
+        },
+        "linux" if client_info.name == "rdesktop" => {
+            // rdesktop command line arguments
+            cmd.arg(&format!("{}:{}", connection.local_address, connection.local_port));
+
+            if config.fullscreen {
+                cmd.arg("-f");
+            }
+
+            if config.resolution != "auto" {
+                cmd.arg("-g").arg(&config.resolution);
+            }
+        },
+        "linux" if client_info.name == "remmina" => {
+            // remmina can take an RDP connection string
+            cmd.arg(&format!("rdp://{}:{}", connection.local_address, connection.local_port));
+        },
+        _ => {
+            // Generic fallback - just pass the address and port
+            cmd.arg(&format!("{}:{}", connection.local_address, connection.local_port));
+        }
+    }
+
+    info!("Executing RDP client command: {:?}", cmd);
+
+    match cmd.spawn() {
+        Ok(mut child) => {
+            info!("RDP client launched successfully with PID: {:?}", child.id());
+
+            // Don't wait for the child process to complete, as RDP clients typically run independently
+            tokio::spawn(async move {
+                match child.wait().await {
+                    Ok(status) => {
+                        info!("RDP client exited with status: {}", status);
+                    }
+                    Err(e) => {
+                        error!("Error waiting for RDP client: {}", e);
+                    }
+                }
+            });
+
+            Ok(())
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to launch RDP client '{}': {}", client_info.name, e);
+            error!("{}", error_msg);
+            Err(error_msg)
+        }
+    }
+}
+
+// Create a keychain entry for a specific server and user
+fn create_keychain_entry(server_id: &str, user_id: &str) -> Result<Entry, String> {
+    let service = "regis-boundary-client";
+    let account = format!("{}@{}", user_id, server_id);
+
+    Entry::new(service, &account).map_err(|e| {
+        format!("Failed to create keychain entry for {}: {}", account, e)
+    })
+}
+
+// Store authentication token in system keychain
+#[instrument]
+async fn store_auth_token(token: &StoredToken) -> Result<(), String> {
+    info!("Storing authentication token for user {} on server {}",
+          token.user_id, token.server_id);
+
+    let entry = create_keychain_entry(&token.server_id, &token.user_id)?;
+
+    // Serialize the token data to JSON for storage
+    let token_json = serde_json::to_string(token).map_err(|e| {
+        format!("Failed to serialize token for storage: {}", e)
+    })?;
+
+    // Store the serialized token in keychain
+    entry.set_password(&token_json).map_err(|e| {
+        let error_msg = format!("Failed to store token in keychain: {}", e);
+        error!("{}", error_msg);
+        error_msg
+    })?;
+
+    info!("Authentication token stored successfully in keychain");
+    debug!("Token metadata: server_id={}, user_id={}, scope_id={}, expires_at={:?}",
+           token.server_id, token.user_id, token.scope_id, token.expires_at);
+
+    Ok(())
+}
+
+// Retrieve authentication token from system keychain
+#[instrument]
+async fn retrieve_auth_token(server_id: &str, user_id: &str) -> Result<StoredToken, String> {
+    info!("Retrieving authentication token for user {} on server {}", user_id, server_id);
+
+    let entry = create_keychain_entry(server_id, user_id)?;
+
+    // Retrieve the token from keychain
+    let token_json = entry.get_password().map_err(|e| {
+        let error_msg = format!("Failed to retrieve token from keychain: {}", e);
+        debug!("{}", error_msg);
+        error_msg
+    })?;
+
+    // Deserialize the token data from JSON
+    let token: StoredToken = serde_json::from_str(&token_json).map_err(|e| {
+        let error_msg = format!("Failed to deserialize stored token: {}", e);
+        error!("{}", error_msg);
+        error_msg
+    })?;
+
+    // Check if token has expired
+    if let Some(expires_at) = &token.expires_at {
+        let now = chrono::Utc::now();
+        match chrono::DateTime::parse_from_rfc3339(expires_at) {
+            Ok(expiry_time) => {
+                if now > expiry_time {
+                    let error_msg = format!("Stored token has expired (expired at: {})", expires_at);
+                    warn!("{}", error_msg);
+                    return Err(error_msg);
+                }
+            }
+            Err(e) => {
+                warn!("Failed to parse token expiry time '{}': {}", expires_at, e);
+            }
+        }
+    }
+
+    info!("Authentication token retrieved successfully from keychain");
+    debug!("Token metadata: server_id={}, user_id={}, scope_id={}, expires_at={:?}",
+           token.server_id, token.user_id, token.scope_id, token.expires_at);
+
+    Ok(token)
+}
+
+// Delete authentication token from system keychain
+#[instrument]
+async fn delete_auth_token(server_id: &str, user_id: &str) -> Result<(), String> {
+    info!("Deleting authentication token for user {} on server {}", user_id, server_id);
+
+    let entry = create_keychain_entry(server_id, user_id)?;
+
+    entry.delete_password().map_err(|e| {
+        let error_msg = format!("Failed to delete token from keychain: {}", e);
+        error!("{}", error_msg);
+        error_msg
+    })?;
+
+    info!("Authentication token deleted successfully from keychain");
+    Ok(())
+}
+
+// Check if authentication token exists in keychain
+#[instrument]
+async fn token_exists_in_keychain(server_id: &str, user_id: &str) -> bool {
+    debug!("Checking if token exists for user {} on server {}", user_id, server_id);
+
+    match create_keychain_entry(server_id, user_id) {
+        Ok(entry) => {
+            match entry.get_password() {
+                Ok(_) => {
+                    debug!("Token found in keychain");
+                    true
+                }
+                Err(_) => {
+                    debug!("No token found in keychain");
+                    false
+                }
+            }
+        }
+        Err(e) => {
+            debug!("Failed to create keychain entry: {}", e);
+            false
+        }
+    }
+}
+
+// List all stored tokens (metadata only, not actual tokens)
+#[instrument]
+async fn list_stored_token_metadata() -> Result<Vec<TokenMetadata>, String> {
+    info!("Listing stored token metadata");
+
+    // Note: The keyring crate doesn't provide a way to enumerate all entries,
+    // so we'll need to maintain a separate index or return empty for now.
+    // In a production implementation, you might want to store an index file
+    // in the user's config directory that tracks which tokens are stored.
+
+    warn!("Token enumeration not fully implemented - returning empty list");
+    debug!("Consider implementing a token index file for production use");
+
+    Ok(Vec::new())
+}
+
+// Check if a stored token is expired or will expire soon
+#[instrument]
+async fn is_token_expired_or_expiring(token: &StoredToken, threshold_minutes: u32) -> Result<bool, String> {
+    let expires_at = match &token.expires_at {
+        Some(expiry_str) => expiry_str,
+        None => {
+            debug!("Token has no expiration time - assuming valid");
+            return Ok(false);
+        }
+    };
+
+    let expiry_time = chrono::DateTime::parse_from_rfc3339(expires_at)
+        .map_err(|e| format!("Failed to parse token expiry time '{}': {}", expires_at, e))?;
+
+    let now = chrono::Utc::now();
+    let threshold = chrono::Duration::minutes(threshold_minutes as i64);
+    let expiry_threshold = expiry_time - threshold;
+
+    let is_expiring = now >= expiry_threshold;
+
+    if is_expiring {
+        info!("Token for user {} on server {} is expired or expiring soon (expires: {}, threshold: {} minutes)",
+              token.user_id, token.server_id, expires_at, threshold_minutes);
+    } else {
+        debug!("Token for user {} on server {} is still valid (expires: {})",
+               token.user_id, token.server_id, expires_at);
+    }
+
+    Ok(is_expiring)
+}
+
+// Validate current token by checking its status via Boundary CLI
+#[instrument]
+pub async fn validate_token_with_cli(
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    token: &StoredToken
+) -> Result<bool, String> {
+    debug!("Validating token for user {} on server {} via CLI", token.user_id, token.server_id);
+
+    // Try a simple command that requires authentication, passing this token to the child only
+    let result = execute_boundary_command(
+        cli_path,
+        vec!["auth-tokens", "list", "-format", "json"],
+        Some(server_addr),
+        &cli_options.clone().with_token(&token.access_token),
+    ).await;
+
+    match result {
+        Ok(cmd_result) => {
+            if cmd_result.success {
+                debug!("Token validation successful");
+                Ok(true)
+            } else {
+                warn!("Token validation failed: {}", cmd_result.stderr);
+                Ok(false)
+            }
+        }
+        Err(e) => {
+            warn!("Token validation error: {}", e);
+            Ok(false)
+        }
+    }
+}
+
+// Trigger re-authentication for an expired token
+#[instrument]
+pub async fn trigger_reauthentication(
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    auth_method_id: &str,
+    server_id: &str,
+) -> Result<StoredToken, String> {
+    info!("Triggering re-authentication for server {} with auth method {}", server_id, auth_method_id);
+
+    // For OIDC authentication, the CLI handles the flow automatically
+    let result = execute_boundary_command(
+        cli_path,
+        vec!["authenticate", "oidc", "-auth-method-id", auth_method_id, "-format", "json"],
+        Some(server_addr),
+        cli_options,
+    ).await?;
+
+    if !result.success {
+        let error_msg = format!("Re-authentication failed: {}", result.stderr);
+        error!("{}", error_msg);
+        return Err(error_msg);
+    }
+
+    // Parse the authentication result
+    let auth_info = models::auth_token_from_str(&result.stdout)
+        .map_err(|e| format!("Failed to parse authentication response: {}", e))?;
+
+    // Extract token information
+    let access_token = auth_info.token.clone().unwrap_or_else(|| {
+        // If token is not in output, try to get it from CLI config
+        warn!("Token not found in auth response, will attempt to retrieve from CLI config");
+        String::new()
+    });
+
+    let user_id = auth_info.user_id;
+    let expiration_time = auth_info.expiration_time;
+
+    // If we couldn't get the token from the response, try the CLI config
+    let final_token = if access_token.is_empty() {
+        // Try to get token from CLI keyring
+        let token_result = execute_boundary_command(
+            cli_path,
+            vec!["config", "get-token"],
+            None,
+            cli_options,
+        ).await?;
+
+        if token_result.success {
+            token_result.stdout.trim().to_string()
+        } else {
+            return Err("Failed to retrieve token after authentication".to_string());
+        }
+    } else {
+        access_token
+    };
+
+    let new_token = StoredToken {
+        access_token: final_token,
+        refresh_token: None, // Boundary doesn't use refresh tokens
+        expires_at: expiration_time,
+        server_id: server_id.to_string(),
+        user_id,
+        scope_id: "global".to_string(), // Default scope
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    info!("Re-authentication successful for server {}", server_id);
+    Ok(new_token)
+}
+
+// Check and refresh token if needed (validation and re-authentication run under different deadlines)
+#[allow(clippy::too_many_arguments)]
+#[instrument]
+async fn check_and_refresh_token(
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    auth_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    auth_method_id: &str,
+    server_id: &str,
+    user_id: &str,
+    threshold_minutes: u32,
+) -> Result<Option<StoredToken>, String> {
+    debug!("Checking token status for user {} on server {}", user_id, server_id);
+
+    // Try to retrieve current token
+    let current_token = match retrieve_auth_token(server_id, user_id).await {
+        Ok(token) => token,
+        Err(_) => {
+            info!("No stored token found for user {} on server {}", user_id, server_id);
+            return Ok(None);
+        }
+    };
+
+    // Check if token is expired or expiring soon
+    let needs_refresh = is_token_expired_or_expiring(&current_token, threshold_minutes).await?;
+
+    if !needs_refresh {
+        // Double-check with CLI validation
+        if validate_token_with_cli(cli_path, cli_options, server_addr, &current_token).await? {
+            debug!("Token is valid, no refresh needed");
+            return Ok(Some(current_token));
+        } else {
+            info!("Token failed CLI validation, triggering re-authentication");
+        }
+    }
+
+    // Token needs refresh - trigger re-authentication
+    info!("Token expired or invalid, triggering re-authentication");
+    let new_token = trigger_reauthentication(cli_path, auth_options, server_addr, auth_method_id, server_id).await?;
+
+    // Store the new token
+    store_auth_token(&new_token).await?;
+
+    info!("Token refresh completed successfully");
+    Ok(Some(new_token))
+}
+
+// Auto-logout functionality - remove expired tokens
+#[instrument]
+async fn auto_logout_expired_tokens(max_age_minutes: u32) -> Result<Vec<String>, String> {
+    info!("Performing auto-logout check for tokens older than {} minutes", max_age_minutes);
+
+    // Note: This is a simplified implementation since keyring doesn't provide enumeration
+    // In a production implementation, you would maintain an index of stored tokens
+
+    let logged_out_servers = Vec::new();
+
+    // This is a placeholder - in reality you would:
+    // 1. Maintain a list/index of active tokens
+    // 2. Check each token's expiry
+    // 3. Remove expired tokens from keychain
+    // 4. Return list of servers that were logged out
+
+    warn!("Auto-logout functionality needs token enumeration - consider implementing token index");
+
+    Ok(logged_out_servers)
+}
+
+// Start background token monitoring service
+#[instrument]
+async fn start_token_monitoring(
+    app_state: Arc<AppState>,
+    check_interval_seconds: u64,
+    refresh_threshold_minutes: u32,
+) {
+    info!("Starting background token monitoring service");
+    info!("Check interval: {}s, Refresh threshold: {}min", check_interval_seconds, refresh_threshold_minutes);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(check_interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            // Check if monitoring is still enabled
+            let monitoring_enabled = {
+                let enabled = app_state.monitoring_enabled.lock().unwrap();
+                *enabled
+            };
+
+            if !monitoring_enabled {
+                debug!("Token monitoring stopped");
+                break;
+            }
+
+            // Perform auto-logout check
+            let auto_logout_minutes = app_state.config.security.auto_logout_minutes;
+            match auto_logout_expired_tokens(auto_logout_minutes).await {
+                Ok(logged_out) => {
+                    if !logged_out.is_empty() {
+                        info!("Auto-logout completed for {} servers: {:?}", logged_out.len(), logged_out);
+                    }
+                }
+                Err(e) => {
+                    error!("Auto-logout check failed: {}", e);
+                }
+            }
+
+            debug!("Token monitoring cycle completed");
+        }
+    });
+
+    info!("Background token monitoring started");
+}
+
+// Map a Boundary session status to a health status
+fn session_health_status(session_status: &str) -> String {
+    if session_status == "active" {
+        "healthy".to_string()
+    } else {
+        format!("session_status_{}", session_status)
+    }
+}
+
+// Check session health by verifying connection is still responsive
+#[instrument]
+pub async fn check_session_health(
+    api: Option<&BoundaryApiClient>,
+    cli_path: &str,
+    cli_options: &BoundaryCommandOptions,
+    server_addr: &str,
+    session_id: &str,
+) -> Result<SessionHealth, String> {
+    debug!("Checking health for session: {}", session_id);
+
+    let start_time = std::time::Instant::now();
+    let timestamp = chrono::Utc::now().to_rfc3339();
+
+    // Prefer the controller API for the session status check
+    let api_check = match api {
+        Some(api) => match controller_call(cli_options, "read session", api.read_session(session_id)).await {
+            Ok(Some(session_info)) => {
+                let response_time = start_time.elapsed().as_millis() as u64;
+                debug!("Session {} status from controller API: {}", session_id, session_info.status);
+                Some((session_health_status(&session_info.status), Some(response_time), 0))
+            }
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Session health check error: {}", e);
+                Some(("error".to_string(), None, 1))
+            }
+        },
+        None => None,
+    };
+
+    let (status, response_time, error_count) = match api_check {
+        Some(check) => check,
+        None => {
+            // Use Boundary CLI to check session status
+            let result = execute_boundary_command(
+                cli_path,
+                vec!["sessions", "read", "-id", session_id, "-format", "json"],
+                Some(server_addr),
+                cli_options,
+            ).await;
+
+            match result {
+                Ok(cmd_result) => {
+                    if cmd_result.success {
+                        let response_time = start_time.elapsed().as_millis() as u64;
+
+                        // Try to parse the session info to get more details
+                        match models::item_from_str::<SessionItem>(&cmd_result.stdout) {
+                            Ok(session_info) => {
+                                debug!("Session {} status from CLI: {}", session_id, session_info.status);
+                                (session_health_status(&session_info.status), Some(response_time), 0)
+                            }
+                            Err(e) => {
+                                warn!("Failed to parse session info JSON: {}", e);
+                                ("healthy".to_string(), Some(response_time), 0)
+                            }
+                        }
+                    } else {
+                        warn!("Session health check failed: {}", cmd_result.stderr);
+                        ("unhealthy".to_string(), None, 1)
+                    }
+                }
+                Err(e) => {
+                    warn!("Session health check error: {}", e);
+                    ("error".to_string(), None, 1)
+                }
+            }
+        }
+    };
+
+    let health = SessionHealth {
+        session_id: session_id.to_string(),
+        status,
+        last_check: timestamp,
+        response_time_ms: response_time,
+        error_count,
+        consecutive_failures: if error_count > 0 { 1 } else { 0 },
+    };
+
+    debug!("Session health check result: {:?}", health);
+    Ok(health)
+}
+
+// Monitor all active sessions and update their health status
+#[instrument]
+async fn monitor_active_sessions(
+    app_state: &AppState,
+) -> Result<SessionMonitoringStats, String> {
+    info!("Monitoring active sessions");
+
+    let monitoring_enabled = {
+        let enabled = app_state.monitoring_enabled.lock().unwrap();
+        *enabled
+    };
+
+    if !monitoring_enabled {
+        debug!("Session monitoring is disabled");
+        return Ok(SessionMonitoringStats {
+            total_sessions: 0,
+            active_sessions: 0,
+            failed_sessions: 0,
+            monitoring_enabled: false,
+            last_check: chrono::Utc::now().to_rfc3339(),
+        });
+    }
+
+    let connections = {
+        let active_connections = app_state.active_connections.lock().unwrap();
+        active_connections.iter().map(|process| process.connection.clone()).collect::<Vec<_>>()
+    };
+
+    let mut health_checks = Vec::new();
+    let mut stats = SessionMonitoringStats {
+        total_sessions: connections.len(),
+        active_sessions: 0,
+        failed_sessions: 0,
+        monitoring_enabled: true,
+        last_check: chrono::Utc::now().to_rfc3339(),
+    };
+
+    for connection in &connections {
+        // This is a placeholder - in a real implementation, you would need
+        // the CLI path and server address for each connection
+        // For now, we'll create a dummy health check
+        let health = SessionHealth {
+            session_id: connection.session_id.clone(),
+            status: if connection.status == "active" {
+                "healthy".to_string()
+            } else {
+                "unhealthy".to_string()
+            },
+            last_check: chrono::Utc::now().to_rfc3339(),
+            response_time_ms: Some(50), // Mock response time
+            error_count: 0,
+            consecutive_failures: 0,
+        };
+
+        if health.status == "healthy" {
+            stats.active_sessions += 1;
+        } else {
+            stats.failed_sessions += 1;
+        }
+
+        health_checks.push(health);
+    }
+
+    // Update the session health tracking
+    {
+        let mut session_health = app_state.session_health.lock().unwrap();
+        for health in health_checks {
+            session_health.insert(health.session_id.clone(), health);
+        }
+    }
+
+    info!("Session monitoring completed: {} total, {} active, {} failed",
+          stats.total_sessions, stats.active_sessions, stats.failed_sessions);
+
+    Ok(stats)
+}
+
+// Start periodic session monitoring
+#[instrument]
+async fn start_session_monitoring(app_state: Arc<AppState>) {
+    info!("Starting periodic session monitoring");
+
+    // Enable monitoring
+    {
+        let mut enabled = app_state.monitoring_enabled.lock().unwrap();
+        *enabled = true;
+    }
+
+    // Spawn a background task for periodic monitoring
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
+
+        loop {
+            interval.tick().await;
+
+            let monitoring_enabled = {
+                let enabled = app_state.monitoring_enabled.lock().unwrap();
+                *enabled
+            };
+
+            if !monitoring_enabled {
+                debug!("Session monitoring stopped");
+                break;
+            }
+
+            match monitor_active_sessions(&app_state).await {
+                Ok(stats) => {
+                    debug!("Session monitoring cycle completed: {:?}", stats);
+                }
+                Err(e) => {
+                    error!("Session monitoring failed: {}", e);
+                }
+            }
+        }
+    });
+
+    info!("Session monitoring started");
+}
+
+// Stop session monitoring
+#[instrument]
+async fn stop_session_monitoring(app_state: &AppState) -> Result<(), String> {
+    info!("Stopping session monitoring");
+
+    {
+        let mut enabled = app_state.monitoring_enabled.lock().unwrap();
+        *enabled = false;
+    }
+
+    info!("Session monitoring stopped");
+    Ok(())
+}
+
+// Get current session health for a specific session
+#[instrument]
+async fn get_session_health(
+    app_state: &AppState,
+    session_id: &str,
+) -> Result<SessionHealth, String> {
+    debug!("Getting health for session: {}", session_id);
+
+    let session_health = app_state.session_health.lock().unwrap();
+    session_health
+        .get(session_id)
+        .cloned()
+        .ok_or_else(|| format!("No health data found for session: {}", session_id))
+}
+
+// Get monitoring statistics for all sessions
+#[instrument]
+async fn get_monitoring_stats(app_state: &AppState) -> Result<SessionMonitoringStats, String> {
+    debug!("Getting session monitoring statistics");
+
+    let connections = {
+        let active_connections = app_state.active_connections.lock().unwrap();
+        active_connections.len()
+    };
+
+    let (active_count, failed_count) = {
+        let session_health = app_state.session_health.lock().unwrap();
+        let mut active = 0;
+        let mut failed = 0;
+
+        for health in session_health.values() {
+            if health.status == "healthy" {
+                active += 1;
+            } else {
+                failed += 1;
+            }
+        }
+
+        (active, failed)
+    };
+
+    let monitoring_enabled = {
+        let enabled = app_state.monitoring_enabled.lock().unwrap();
+        *enabled
+    };
+
+    Ok(SessionMonitoringStats {
+        total_sessions: connections,
+        active_sessions: active_count,
+        failed_sessions: failed_count,
+        monitoring_enabled,
+        last_check: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+// Load configuration with resource fallback system
+#[instrument(skip(app))]
+fn load_resource_with_fallback(app: &AppHandle, filename: &str) -> Result<String, String> {
+    debug!("Loading resource: {}", filename);
+
+    // Try to resolve the resource using Tauri's resource resolver
+    let resource_result = app
+        .path()
+        .resolve(filename, tauri::path::BaseDirectory::Resource)
+        .and_then(|path| {
+            debug!("Resolved resource path: {:?}", path);
+            fs::read_to_string(&path).map_err(|e| {
+                warn!("Failed to read resolved path {:?}: {}", path, e);
+                tauri::Error::Io(e)
+            })
+        });
+
+    match resource_result {
+        Ok(content) => {
+            info!("Successfully loaded {} from resources", filename);
+            Ok(content)
+        }
+        Err(e) => {
+            warn!("Failed to load {} from resources: {:?}", filename, e);
+
+            // Fallback: try multiple paths for development
+            let mut fallback_content = None;
+            let mut last_error = String::new();
+
+            // Try: resource directory (direct path)
+            if let Ok(resource_dir) = app.path().resource_dir() {
+                let resource_path = resource_dir.join(filename);
+                debug!("Trying resource directory path: {:?}", resource_path);
+
+                match fs::read_to_string(&resource_path) {
+                    Ok(content) => {
+                        fallback_content = Some(content);
+                        info!("Successfully read {} from resource directory", filename);
+                    }
+                    Err(e) => {
+                        last_error = format!("Resource dir failed: {}", e);
+                        debug!("Resource dir failed: {}", e);
+
+                        // Try the _up_ subdirectory where resources are actually bundled
+                        let resource_up_path = resource_dir.join("_up_").join(filename);
+                        debug!("Trying resource _up_ directory path: {:?}", resource_up_path);
+
+                        match fs::read_to_string(&resource_up_path) {
+                            Ok(content) => {
+                                fallback_content = Some(content);
+                                info!("Successfully read {} from resource _up_ directory", filename);
+                            }
+                            Err(e2) => {
+                                last_error = format!("{} | Resource _up_ dir failed: {}", last_error, e2);
+                                debug!("Resource _up_ dir failed: {}", e2);
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Try: relative to project root (development)
+            if fallback_content.is_none() {
+                let project_path = std::path::Path::new("../").join(filename);
+                debug!("Trying project path: {:?}", project_path);
+
+                match fs::read_to_string(&project_path) {
+                    Ok(content) => {
+                        fallback_content = Some(content);
+                        info!("Successfully read {} from project directory", filename);
+                    }
+                    Err(e) => {
+                        last_error = format!("{} | Project dir failed: {}", last_error, e);
+                        debug!("Project dir failed: {}", e);
+                    }
+                }
+            }
+
+            // Try: current directory
+            if fallback_content.is_none() {
+                let current_path = std::path::Path::new(filename);
+                debug!("Trying current path: {:?}", current_path);
+
+                match fs::read_to_string(current_path) {
+                    Ok(content) => {
+                        fallback_content = Some(content);
+                        info!("Successfully read {} from current directory", filename);
+                    }
+                    Err(e) => {
+                        last_error = format!("{} | Current dir failed: {}", last_error, e);
+                        debug!("Current dir failed: {}", e);
+                    }
+                }
+            }
+
+            fallback_content.ok_or_else(|| {
+                let error_msg = format!(
+                    "Failed to find {} in any location. Resource error: {:?}. Fallback errors: {}",
+                    filename, e, last_error
+                );
+                error!("{}", error_msg);
+                error_msg
+            })
+        }
+    }
+}
+
+// Helper function to load system configuration
+#[instrument(skip(app))]
+fn load_system_config(app: &AppHandle) -> Config {
+    match load_resource_with_fallback(app, "config.json") {
+        Ok(config_content) => {
+            match serde_json::from_str::<Config>(&config_content) {
+                Ok(parsed_config) => {
+                    info!("Successfully loaded and parsed system config.json");
+                    parsed_config
+                }
+                Err(e) => {
+                    error!("Failed to parse system config.json: {}. Using default configuration.", e);
+                    Config::default()
+                }
+            }
+        }
+        Err(e) => {
+            error!("Failed to load system config.json: {}. Using default configuration.", e);
+            Config::default()
+        }
+    }
+}
+
+// Load configuration with user overrides
+#[instrument(skip(app))]
+fn load_config(app: &AppHandle) -> Config {
+    debug!("Loading application configuration with user overrides");
+
+    // Step 1: Check for local test configuration first (for development)
+    let mut local_test_config_path = PathBuf::from("local-test-config.json");
+    // Try both current directory and project root (for Tauri dev mode)
+    if !local_test_config_path.exists() {
+        local_test_config_path = PathBuf::from("../local-test-config.json");
+    }
+
+    let mut config = if local_test_config_path.exists() {
+        info!("Found local test configuration file: {:?}", local_test_config_path);
+        match fs::read_to_string(&local_test_config_path) {
+            Ok(test_config_content) => {
+                match serde_json::from_str::<Config>(&test_config_content) {
+                    Ok(parsed_config) => {
+                        info!("Successfully loaded and parsed local test config");
+                        parsed_config
+                    }
+                    Err(e) => {
+                        error!("Failed to parse local test config: {}. Falling back to system config.", e);
+                        load_system_config(app)
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to read local test config: {}. Falling back to system config.", e);
+                load_system_config(app)
+            }
+        }
+    } else {
+        // Step 2: Load system configuration (fallback)
+        load_system_config(app)
+    };
+
+    // Step 3: Try to load user configuration overrides
+    match get_user_config_path() {
+        Ok(user_config_path) => {
+            debug!("Checking for user config at: {:?}", user_config_path);
+
+            if user_config_path.exists() {
+                info!("Found user configuration file: {:?}", user_config_path);
+
+                match fs::read_to_string(&user_config_path) {
+                    Ok(user_config_content) => {
+                        match serde_json::from_str::<Config>(&user_config_content) {
+                            Ok(user_config) => {
+                                info!("Successfully loaded and parsed user config overrides");
+                                config.merge_with_user_config(user_config);
+                                info!("User configuration overrides applied successfully");
+                            }
+                            Err(e) => {
+                                error!("Failed to parse user config.json: {}. Continuing with system config only.", e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to read user config.json: {}. Continuing with system config only.", e);
+                    }
+                }
+            } else {
+                debug!("No user configuration file found. Using system config only.");
+            }
+        }
+        Err(e) => {
+            error!("Failed to determine user config path: {}. Continuing with system config only.", e);
+        }
+    }
+
+    debug!("Final merged configuration: {:?}", config);
+    config
+}
+
+// Tauri commands
+#[command]
+#[instrument(skip(app))]
+async fn load_servers(app: AppHandle) -> Result<Vec<Server>, RegisError> {
+    info!("Loading servers from system and user configurations");
+
+    // Step 1: Check for local test servers first (for development)
+    let mut local_test_servers_path = PathBuf::from("local-test-servers.json");
+    // Try both current directory and project root (for Tauri dev mode)
+    if !local_test_servers_path.exists() {
+        local_test_servers_path = PathBuf::from("../local-test-servers.json");
+    }
+
+    let mut all_servers = Vec::new();
+
+    if local_test_servers_path.exists() {
+        info!("Found local test servers file: {:?}", local_test_servers_path);
+        match fs::read_to_string(&local_test_servers_path) {
+            Ok(test_servers_content) => {
+                match serde_json::from_str::<ServerConfig>(&test_servers_content) {
+                    Ok(config) => {
+                        info!("Successfully loaded {} local test servers", config.servers.len());
+                        debug!("Local test servers: {:?}", config.servers);
+                        all_servers.extend(config.servers);
+                        // Return early - use only test servers in development mode
+                        return Ok(all_servers);
+                    }
+                    Err(e) => {
+                        let error_msg = format!("Failed to parse local test servers: {}", e);
+                        error!("{}", error_msg);
+                        return Err(RegisError::Config(error_msg));
+                    }
+                }
+            }
+            Err(e) => {
+                let error_msg = format!("Failed to read local test servers: {}", e);
+                error!("{}", error_msg);
+                return Err(RegisError::Config(error_msg));
+            }
+        }
+    }
+
+    // Step 2: Load system servers if no local test servers
+    match load_resource_with_fallback(&app, "servers.json") {
+        Ok(config_content) => {
+            match serde_json::from_str::<ServerConfig>(&config_content) {
+                Ok(config) => {
+                    info!("Successfully loaded {} system servers", config.servers.len());
+                    debug!("System servers: {:?}", config.servers);
+                    all_servers.extend(config.servers);
+                }
+                Err(e) => {
+                    let error_msg = format!("Failed to parse system servers.json: {}", e);
+                    error!("{}", error_msg);
+                    return Err(RegisError::Config(error_msg));
+                }
+            }
+        }
+        Err(e) => {
+            let error_msg = format!("Failed to load system servers.json: {}", e);
+            error!("{}", error_msg);
+            return Err(RegisError::Config(error_msg));
+        }
+    }
+
+    // Step 3: Try to load user servers (additive)
+    match get_user_servers_path() {
+        Ok(user_servers_path) => {
+            debug!("Checking for user servers at: {:?}", user_servers_path);
+
+            if user_servers_path.exists() {
+                info!("Found user servers file: {:?}", user_servers_path);
+
+                match fs::read_to_string(&user_servers_path) {
+                    Ok(user_servers_content) => {
+                        match serde_json::from_str::<ServerConfig>(&user_servers_content) {
+                            Ok(user_config) => {
+                                info!("Successfully loaded {} user servers", user_config.servers.len());
+                                debug!("User servers: {:?}", user_config.servers);
+
+                                // Add user servers to the list (additive merge)
+                                all_servers.extend(user_config.servers);
+                                info!("User servers added successfully");
+                            }
+                            Err(e) => {
+                                error!("Failed to parse user servers.json: {}. Continuing with system servers only.", e);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        error!("Failed to read user servers.json: {}. Continuing with system servers only.", e);
+                    }
+                }
+            } else {
+                debug!("No user servers file found. Using system servers only.");
+            }
+        }
+        Err(e) => {
+            error!("Failed to determine user servers path: {}. Continuing with system servers only.", e);
+        }
+    }
+
+    info!("Total servers loaded: {}", all_servers.len());
+    debug!("All servers: {:?}", all_servers);
+
+    Ok(all_servers)
+}
+
+#[command]
+#[instrument]
+async fn log_from_frontend(level: String, component: String, message: String, data: Option<String>) -> Result<(), RegisError> {
+    let log_data = data.unwrap_or_default();
+
+    match level.as_str() {
+        "debug" => debug!(component = %component, data = %log_data, "{}", message),
+        "info" => info!(component = %component, data = %log_data, "{}", message),
+        "warn" => warn!(component = %component, data = %log_data, "{}", message),
+        "error" => error!(component = %component, data = %log_data, "{}", message),
+        _ => info!(component = %component, data = %log_data, "{}", message),
+    }
+
+    Ok(())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn get_config(app: AppHandle) -> Result<Config, RegisError> {
+    debug!("Frontend requested application configuration");
+
+    let state = app.state::<AppState>();
+    debug!("Returning configuration to frontend");
+    Ok(state.config.clone())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn verify_boundary_cli_command(app: AppHandle, server_id: String) -> Result<BoundaryCliInfo, RegisError> {
+    info!("Verifying Boundary CLI for server: {}", server_id);
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let info = resolve_boundary_cli(&app, Some(server)).await.map_err(RegisError::CliNotFound)?;
+    info!("{}", info.reason);
+    Ok(info)
+}
+
+// Staged DNS/TCP/TLS/HTTP reachability report for a server, for support staff
+#[command]
+#[instrument(skip(app))]
+async fn diagnose_server_command(app: AppHandle, server_id: String) -> Result<DiagnosticReport, RegisError> {
+    info!("Running diagnostics for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    Ok(diagnostics::diagnose_server(server, &state.config).await)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_auth_methods_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryAuthMethod>, RegisError> {
+    info!("Discovering auth methods for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    discover_auth_methods(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::cli)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_scopes_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryScope>, RegisError> {
+    info!("Discovering scopes for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    let scopes = discover_scopes(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::cli)?;
+    if let Some(user_id) = cache_user_id(&state, &server_id) {
+        cache_scopes(&state, &server_id, &user_id, &scopes);
+    }
+    Ok(scopes)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_scope_tree_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<BoundaryScopeNode, RegisError> {
+    info!("Discovering scope tree for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+
+    let scopes = discover_scopes(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::cli)?;
+    if let Some(user_id) = cache_user_id(&state, &server_id) {
+        cache_scopes(&state, &server_id, &user_id, &scopes);
+    }
+    Ok(build_scope_tree(&scopes))
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_oidc_auth_methods_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryAuthMethod>, RegisError> {
+    info!("Discovering OIDC auth methods for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    discover_oidc_auth_methods(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::auth)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn verify_oidc_support_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<bool, RegisError> {
+    info!("Verifying OIDC support for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    verify_oidc_support(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::cli)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_targets_command(
+    app: AppHandle,
+    server_id: String,
+    scope_id: Option<String>,
+    recursive: Option<bool>,
+    filter: Option<String>,
+    operation_id: Option<String>,
+) -> Result<Vec<BoundaryTarget>, RegisError> {
+    info!("Discovering targets for server: {} in scope: {:?}", server_id, scope_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    let query = TargetQuery {
+        scope_id,
+        recursive: recursive.unwrap_or(false),
+        filter: filter.filter(|f| !f.trim().is_empty()),
+    };
+
+    discover_targets(api.as_ref(), &cli_path, &cli_options, &server.url, &query).await.map_err(RegisError::cli)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn discover_all_targets_command(app: AppHandle, server_id: String, filter: Option<String>, operation_id: Option<String>) -> Result<Vec<BoundaryTarget>, RegisError> {
+    info!("Discovering all targets for server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    let scope_id = server.target_scope.as_deref().unwrap_or("global");
+    let filter = filter.as_deref().filter(|f| !f.trim().is_empty());
+
+    // Stream each project's targets to the frontend when discovery falls back to per-scope listing
+    let emitter = app.clone();
+    let discovery = TargetDiscoveryOptions::default()
+        .with_concurrency(state.config.connection.discovery_concurrency)
+        .on_scope(move |scope| {
+            let progress = TargetDiscoveryProgress { operation_id: operation_id.clone(), scope: scope.clone() };
+            if let Err(e) = emitter.emit(TARGET_DISCOVERY_PROGRESS_EVENT, progress) {
+                warn!("Failed to emit target discovery progress: {}", e);
+            }
+        });
+
+    let mut targets = discover_all_targets(api.as_ref(), &cli_path, &cli_options, &server.url, scope_id, filter, &discovery)
+        .await
+        .map_err(RegisError::cli)?;
+
+    // Target listings may omit aliases; releases before 0.16 have none at all
+    if targets.iter().any(|t| t.aliases.is_empty()) {
+        match discover_aliases(api.as_ref(), &cli_path, &cli_options, &server.url).await {
+            Ok(aliases) => attach_aliases(&mut targets, &aliases),
+            Err(e) => debug!("Target aliases unavailable: {}", e),
+        }
+    }
+
+    // Only the complete list is cached; a filtered one would look like removed targets
+    if filter.is_none() {
+        cache_targets(&app, &state, &server_id, scope_id, &targets);
+    }
+    Ok(targets)
+}
+
+// Cache entry of the user currently signed in to the server
+async fn load_cached_discovery(app: &AppHandle, server_id: &str) -> Result<Option<CachedDiscovery>, RegisError> {
+    let state = app.state::<AppState>();
+    let Some(user_id) = cache_user_id(&state, server_id) else {
+        return Ok(None);
+    };
+
+    let servers = load_servers(app.clone()).await?;
+    let target_scope = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .and_then(|s| s.target_scope.clone())
+        .unwrap_or_else(|| "global".to_string());
+
+    let mut cached = state.discovery_cache.load(server_id, &user_id);
+    // Targets discovered from another scope (targetScope changed) are not what the user expects
+    if let Some(entry) = cached.as_mut().filter(|e| e.target_scope_id.as_deref() != Some(target_scope.as_str())) {
+        entry.targets.clear();
+        entry.targets_fetched_at = None;
+    }
+    Ok(cached)
+}
+
+// The signed-in user's cached targets from memory, read from the cache file only the first time
+async fn searchable_targets(app: &AppHandle, state: &AppState, server_id: &str) -> Result<Option<Arc<Vec<BoundaryTarget>>>, RegisError> {
+    let Some(user_id) = cache_user_id(state, server_id) else {
+        return Ok(None);
+    };
+
+    {
+        let mut searchable = state.searchable_targets.lock().unwrap();
+        match searchable.get(server_id) {
+            Some(entry) if entry.user_id == user_id && state.discovery_cache.is_fresh(Some(entry.fetched_at)) => {
+                return Ok(Some(entry.targets.clone()));
+            }
+            Some(_) => {
+                searchable.remove(server_id);
+            }
+            None => {}
+        }
+    }
+
+    let Some(cached) = load_cached_discovery(app, server_id).await? else {
+        return Ok(None);
+    };
+    let Some(fetched_at) = cached.targets_fetched_at else {
+        return Ok(None);
+    };
+    let targets = Arc::new(cached.targets);
+    let entry = SearchableTargets { user_id, fetched_at, targets: targets.clone() };
+    state.searchable_targets.lock().unwrap().insert(server_id.to_string(), entry);
+    Ok(Some(targets))
+}
+
+// Scopes and targets cached from the last discovery, shown while a fresh one runs
+#[command]
+#[instrument(skip(app))]
+async fn get_cached_discovery_command(app: AppHandle, server_id: String) -> Result<Option<CachedDiscovery>, RegisError> {
+    let cached = load_cached_discovery(&app, &server_id).await?;
+
+    info!(
+        "Cached discovery for server {}: {} scopes, {} targets",
+        server_id,
+        cached.as_ref().map_or(0, |c| c.scopes.len()),
+        cached.as_ref().map_or(0, |c| c.targets.len())
+    );
+    Ok(cached)
+}
+
+// Rank the cached targets against a query; favorites and recently used targets come first.
+// None when nothing is cached (e.g. target_cache_ttl_seconds is 0), so the caller can filter itself.
+#[command]
+#[instrument(skip(app))]
+async fn search_targets_command(app: AppHandle, server_id: String, query: String, limit: Option<usize>) -> Result<Option<Vec<TargetMatch>>, RegisError> {
+    let state = app.state::<AppState>();
+    let Some(targets) = searchable_targets(&app, &state, &server_id).await? else {
+        debug!("No cached targets to search on server {}", server_id);
+        return Ok(None);
+    };
+
+    let preferences = state.preferences.server(&server_id);
+    let matches = search::search_targets(&targets, &query, &preferences.favorites, &preferences.recent, limit);
+    debug!("Search for {:?} matched {} of {} targets", query, matches.len(), targets.len());
+    Ok(Some(matches))
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn get_target_details_command(app: AppHandle, server_id: String, target_id: String, operation_id: Option<String>) -> Result<BoundaryTarget, RegisError> {
+    info!("Reading details of target: {} on server: {}", target_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    get_target_details(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id).await.map_err(RegisError::cli)
+}
+
+// Authorize a session; `target_id` may also be an alias such as "db.prod.internal"
+#[command]
+#[instrument(skip(app))]
+async fn authorize_session_command(app: AppHandle, server_id: String, target_id: String, host_id: Option<String>, operation_id: Option<String>) -> Result<BoundarySessionAuthorization, RegisError> {
+    info!("Authorizing session for target: {} on server: {}", target_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    let (target_id, alias_host) = resolve_target_reference(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id)
+        .await
+        .map_err(RegisError::target)?;
+
+    let cached = cached_target(&state, &server_id, &target_id);
+    check_session_permission(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id, cached.as_ref())
+        .await
+        .map_err(RegisError::PermissionDenied)?;
+
+    // An explicit host wins, then the alias's host; otherwise use the host pinned for this target, if any
+    let pinned_host = match host_id.as_ref().or(alias_host.as_ref()) {
+        Some(_) => None,
+        None => state.preferences.target(&server_id, &target_id).host_id,
+    };
+    let host_id = host_id.or(alias_host).or_else(|| pinned_host.clone());
+    if let Some(host) = &pinned_host {
+        info!("Using host {} pinned for target {}", host, target_id);
+    }
+
+    match authorize_session(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id, host_id.as_deref()).await {
+        Ok(authorization) => Ok(authorization),
+        // A pinned host that has left the target's host sets must not block connecting. Only
+        // a rejected request is retried: after any other failure a session may already exist
+        Err(e) if pinned_host.is_some() && matches!(retry::status_code(&e), Some(400 | 404)) => {
+            warn!("Authorization with pinned host failed, letting Boundary pick a host: {}", e);
+            let authorization = authorize_session(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id, None)
+                .await
+                .map_err(RegisError::target)?;
+
+            info!("Forgetting stale pinned host for target {}", target_id);
+            if let Err(e) = state.preferences.update_target(&server_id, &target_id, |target| target.host_id = None) {
+                warn!("Failed to clear pinned host: {}", e);
+            }
+            Ok(authorization)
+        }
+        Err(e) => Err(RegisError::target(e)),
+    }
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn list_aliases_command(app: AppHandle, server_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryAlias>, RegisError> {
+    info!("Listing aliases on server: {}", server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    discover_aliases(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::cli)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn resolve_alias_command(app: AppHandle, server_id: String, alias: String, operation_id: Option<String>) -> Result<BoundaryAlias, RegisError> {
+    info!("Resolving alias: {} on server: {}", alias, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    resolve_alias(api.as_ref(), &cli_path, &cli_options, &server.url, &alias).await.map_err(RegisError::target)
+}
+
+// Host sets of a target with their hosts, for pinning a specific host
+#[command]
+#[instrument(skip(app))]
+async fn list_target_hosts_command(app: AppHandle, server_id: String, target_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryHostSet>, RegisError> {
+    info!("Listing hosts of target: {} on server: {}", target_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    discover_target_hosts(api.as_ref(), &cli_path, &cli_options, &server.url, &target_id).await.map_err(RegisError::cli)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn list_hosts_command(app: AppHandle, server_id: String, host_catalog_id: String, operation_id: Option<String>) -> Result<Vec<BoundaryHost>, RegisError> {
+    info!("Listing hosts in catalog: {} on server: {}", host_catalog_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server).with_cancel(operation.token());
+    info!("Using CLI path: {} for server: {}", cli_path, server.url);
+
+    list_hosts(api.as_ref(), &cli_path, &cli_options, &server.url, &host_catalog_id).await.map_err(RegisError::cli)
+}
+
+// Host pinned for a target, if any
+#[command]
+#[instrument(skip(app))]
+async fn get_pinned_host_command(app: AppHandle, server_id: String, target_id: String) -> Result<Option<String>, RegisError> {
+    let state = app.state::<AppState>();
+    Ok(state.preferences.target(&server_id, &target_id).host_id)
+}
+
+// Pin a host for a target, or unpin with host_id = null
+#[command]
+#[instrument(skip(app))]
+async fn set_pinned_host_command(app: AppHandle, server_id: String, target_id: String, host_id: Option<String>) -> Result<(), RegisError> {
+    info!("Setting pinned host for target {} on server {}: {:?}", target_id, server_id, host_id);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_target(&server_id, &target_id, |target| target.host_id = host_id)
+        .map_err(RegisError::Internal)?;
+    Ok(())
+}
+
+// Local listen address and port set for a target (not those inherited from the server)
+#[command]
+#[instrument(skip(app))]
+async fn get_listen_settings_command(app: AppHandle, server_id: String, target_id: String) -> Result<ListenSettings, RegisError> {
+    let state = app.state::<AppState>();
+    let target = state.preferences.target(&server_id, &target_id);
+    Ok(ListenSettings { listen_addr: target.listen_addr, listen_port: target.listen_port })
+}
+
+// Set a target's local listen address and port; null falls back to the server's settings
+#[command]
+#[instrument(skip(app))]
+async fn set_listen_settings_command(app: AppHandle, server_id: String, target_id: String, settings: ListenSettings) -> Result<(), RegisError> {
+    info!("Setting listen settings for target {} on server {}: {:?}", target_id, server_id, settings);
+
+    let listen_addr = settings.listen_addr.map(|addr| addr.trim().to_string()).filter(|addr| !addr.is_empty());
+    if let Some(addr) = &listen_addr {
+        addr.parse::<std::net::IpAddr>()
+            .map_err(|_| RegisError::Config(format!("'{}' is not an IP address to listen on", addr)))?;
+    }
+    let listen_port = settings.listen_port.filter(|port| *port != 0);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_target(&server_id, &target_id, |target| {
+            target.listen_addr = listen_addr;
+            target.listen_port = listen_port;
+        })
+        .map_err(RegisError::Internal)?;
+    Ok(())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn pin_favorite_target_command(app: AppHandle, server_id: String, target_id: String) -> Result<Vec<String>, RegisError> {
+    info!("Adding target {} to favorites on server {}", target_id, server_id);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_server(&server_id, |server| {
+            server.pin_favorite(&target_id);
+            server.favorites.clone()
+        })
+        .map_err(RegisError::Internal)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn unpin_favorite_target_command(app: AppHandle, server_id: String, target_id: String) -> Result<Vec<String>, RegisError> {
+    info!("Removing target {} from favorites on server {}", target_id, server_id);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_server(&server_id, |server| {
+            server.unpin_favorite(&target_id);
+            server.favorites.clone()
+        })
+        .map_err(RegisError::Internal)
+}
+
+// Favorite target ids, in the order they were pinned
+#[command]
+#[instrument(skip(app))]
+async fn list_favorite_targets_command(app: AppHandle, server_id: String) -> Result<Vec<String>, RegisError> {
+    let state = app.state::<AppState>();
+    Ok(state.preferences.server(&server_id).favorites)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn clear_favorite_targets_command(app: AppHandle, server_id: String) -> Result<(), RegisError> {
+    info!("Clearing favorite targets on server {}", server_id);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_server(&server_id, |server| server.favorites.clear())
+        .map_err(RegisError::Internal)
+}
+
+// Targets connected to on this server, most recent first
+#[command]
+#[instrument(skip(app))]
+async fn list_recent_targets_command(app: AppHandle, server_id: String) -> Result<Vec<RecentTarget>, RegisError> {
+    let state = app.state::<AppState>();
+    Ok(state.preferences.server(&server_id).recent)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn clear_recent_targets_command(app: AppHandle, server_id: String) -> Result<(), RegisError> {
+    info!("Clearing recently used targets on server {}", server_id);
+
+    let state = app.state::<AppState>();
+    state
+        .preferences
+        .update_server(&server_id, |server| server.recent.clear())
+        .map_err(RegisError::Internal)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn establish_connection_command(
+    app: AppHandle,
+    server_id: String,
+    authorization: BoundarySessionAuthorization,
+    connection_type: String,
+    target_name: String,
+    operation_id: Option<String>,
+) -> Result<BoundaryConnection, RegisError> {
+    info!("Establishing {} connection for session: {}", connection_type, authorization.session_id);
+
+    let state = app.state::<AppState>();
+
+    // Load servers to find the specific server
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+
+    // Parse connection type
+    let conn_type = match connection_type.to_lowercase().as_str() {
+        "ssh" => ConnectionType::SSH,
+        "rdp" => ConnectionType::RDP,
+        "tcp" => ConnectionType::TCP,
+        "http" => ConnectionType::HTTP,
+        _ => return Err(RegisError::TargetFailed(format!("Unsupported connection type: {}", connection_type))),
+    };
+
+    let operation = register_operation(&state, operation_id.as_deref());
+    let cli_options = cli_options_for_server(&state, server)
+        .with_timeout(state.config.operation_timeout(OperationKind::Connect))
+        .with_cancel(operation.token());
+
+    // A fixed port must be free; say who holds it rather than let the proxy fail
+    let listen = listen_settings_for_target(&state, server, &authorization.target_id);
+    if let Some(port) = listen.listen_port {
+        let owner = {
+            let active_connections = state.active_connections.lock().unwrap();
+            active_connections
+                .iter()
+                .find(|process| !process.has_exited() && process.connection.local_port == port)
+                .map(|process| process.connection.target_name.clone())
+        };
+        if let Some(target) = owner {
+            return Err(RegisError::PortInUse(format!("Local port {} is already used by your connection to '{}'", port, target)));
+        }
+        connections::check_listen_port(&listen).await.map_err(RegisError::PortInUse)?;
+    }
+
+    let process = establish_connection(&cli_path, &cli_options, &server_id, &authorization, conn_type, &target_name, &listen).await.map_err(RegisError::target)?;
+    let connection = process.connection.clone();
+
+    // Track it before following it, so that the very first events find it; the proxy keeps
+    // running until the connection is terminated
+    let emitter = app.clone();
+    let tracked = state.active_connections.clone();
+    let (session_id, event_server_id) = (connection.session_id.clone(), server_id.clone());
+    {
+        let mut active_connections = state.active_connections.lock().unwrap();
+        active_connections.push(process);
+        let process = active_connections.last_mut().expect("connection was just tracked");
+
+        // Keep the frontend, and the tracked status, up to date until the proxy exits
+        connections::follow_connection(process, SESSION_EXPIRY_WARNING, move |event| {
+            if matches!(event, ConnectionEvent::ProxyExited { .. }) {
+                // Nothing is left to terminate once the proxy is gone
+                let mut active_connections = tracked.lock().unwrap();
+                active_connections.retain(|p| p.connection.session_id != session_id);
+                info!("Proxy for session {} has exited, no longer tracking it", session_id);
+            } else if let Some(status) = event.status() {
+                let mut active_connections = tracked.lock().unwrap();
+                if let Some(process) = active_connections.iter_mut().find(|p| p.connection.session_id == session_id) {
+                    process.connection.status = status.to_string();
+                }
+            }
+
+            let update = ConnectionUpdate { session_id: session_id.clone(), server_id: event_server_id.clone(), event };
+            if let Err(e) = emitter.emit(CONNECTION_EVENT, update) {
+                warn!("Failed to emit connection event: {}", e);
+            }
+        });
+    }
+
+    info!("Connection established and tracked: {}:{}", connection.local_address, connection.local_port);
+
+    if let Err(e) = state
+        .preferences
+        .update_server(&server_id, |server| server.record_use(&connection.target_id, &target_name, chrono::Utc::now()))
+    {
+        warn!("Failed to record recently used target: {}", e);
+    }
+    Ok(connection)
+}
+
+// Cancel an in-flight operation by id, or every in-flight operation when no id is given
+#[command]
+#[instrument(skip(app))]
+async fn cancel_operation_command(app: AppHandle, operation_id: Option<String>) -> Result<usize, RegisError> {
+    let state = app.state::<AppState>();
+    let operations = state.operations.lock().unwrap();
+
+    match operation_id {
+        Some(id) => {
+            let token = operations
+                .get(&id)
+                .ok_or_else(|| RegisError::Internal(format!("No in-flight operation with id '{}'", id)))?;
+            info!("Frontend cancelled operation: {}", id);
+            token.cancel();
+            Ok(1)
+        }
+        None => {
+            info!("Frontend cancelled all {} in-flight operations", operations.len());
+            for token in operations.values() {
+                token.cancel();
+            }
+            Ok(operations.len())
+        }
+    }
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn get_active_connections_command(app: AppHandle) -> Result<Vec<BoundaryConnection>, RegisError> {
+    let state = app.state::<AppState>();
+    let mut active_connections = state.active_connections.lock().unwrap();
+
+    // Proxies stop by themselves when their session ends or is cancelled elsewhere
+    active_connections.retain(|process| {
+        let exited = process.has_exited();
+        if exited {
+            info!("Proxy for session {} has exited, no longer tracking it", process.connection.session_id);
+        }
+        !exited
+    });
+    Ok(active_connections.iter().map(|process| process.connection.clone()).collect())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn terminate_connection_command(app: AppHandle, session_id: String) -> Result<ConnectionTermination, RegisError> {
+    info!("Terminating connection for session: {}", session_id);
+
+    let state = app.state::<AppState>();
+
+    // Find and remove the connection
+    let process = {
+        let mut active_connections = state.active_connections.lock().unwrap();
+        let index = active_connections
+            .iter()
+            .position(|process| process.connection.session_id == session_id)
+            .ok_or_else(|| RegisError::TargetFailed(format!("Connection with session id '{}' not found", session_id)))?;
+
+        active_connections.remove(index)
+    };
+
+    // Load servers to find the server the session belongs to
+    let server_id = process.connection.server_id.clone();
+    let servers = load_servers(app.clone()).await?;
+    let Some(server) = servers.iter().find(|s| s.id == server_id) else {
+        // Without the server the session can't be cancelled, but the proxy still must stop
+        let mut errors = vec![format!("Server with id '{}' not found, session was not cancelled", server_id)];
+        let proxy_stopped = match process.terminate().await {
+            Ok(()) => true,
+            Err(e) => {
+                errors.push(e);
+                false
+            }
+        };
+        return Ok(ConnectionTermination { session_id, proxy_stopped, session_status: None, errors });
+    };
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let cli_options = cli_options_for_server(&state, server);
+
+    // Terminate the connection; partial failures are reported, not raised
+    let termination = terminate_connection(api.as_ref(), &cli_path, &cli_options, &server.url, process).await;
+
+    info!("Connection removed from tracking: {}", session_id);
+    Ok(termination)
+}
+
+#[command]
+#[instrument]
+async fn detect_rdp_clients_command() -> Result<DetectedRdpClients, RegisError> {
+    info!("Frontend requested RDP client detection");
+    detect_rdp_clients().await.map_err(RegisError::RdpClient)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn launch_rdp_client_command(
+    app: AppHandle,
+    session_id: String,
+    client_name: Option<String>
+) -> Result<(), RegisError> {
+    info!("Frontend requested RDP client launch for session: {}", session_id);
+
+    let state = app.state::<AppState>();
+
+    // Find the connection
+    let connection = {
+        let active_connections = state.active_connections.lock().unwrap();
+        active_connections
+            .iter()
+            .find(|process| process.connection.session_id == session_id)
+            .map(|process| process.connection.clone())
+            .ok_or_else(|| RegisError::TargetFailed(format!("Connection with session id '{}' not found", session_id)))?
+    };
+
+    // Detect available RDP clients
+    let detected_clients = detect_rdp_clients().await.map_err(RegisError::RdpClient)?;
+
+    if detected_clients.clients.is_empty() {
+        return Err(RegisError::RdpClient("No RDP clients found on this system".to_string()));
+    }
+
+    // Choose the client to use
+    let client_to_use = if let Some(requested_client) = client_name {
+        // User specified a particular client
+        detected_clients.clients
+            .iter()
+            .find(|c| c.name == requested_client)
+            .ok_or_else(|| RegisError::RdpClient(format!("Requested RDP client '{}' not found", requested_client)))?
+    } else {
+        // Use default client or first available
+        let default_name = detected_clients.default_client
+            .as_ref()
+            .unwrap_or(&detected_clients.clients[0].name);
+
+        detected_clients.clients
+            .iter()
+            .find(|c| c.name == *default_name)
+            .unwrap_or(&detected_clients.clients[0])
+    };
+
+    info!("Using RDP client: {}", client_to_use.name);
+
+    // Launch the RDP client
+    launch_rdp_client(client_to_use, &connection, &state.config.rdp).await.map_err(RegisError::RdpClient)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn store_auth_token_command(app: AppHandle, token: StoredToken) -> Result<(), RegisError> {
+    info!("Frontend requested token storage for user {} on server {}",
+          token.user_id, token.server_id);
+    store_auth_token(&token).await.map_err(RegisError::credentials)?;
+    remember_auth_token(&app.state::<AppState>(), &token);
+    Ok(())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn retrieve_auth_token_command(app: AppHandle, server_id: String, user_id: String) -> Result<StoredToken, RegisError> {
+    info!("Frontend requested token retrieval for user {} on server {}", user_id, server_id);
+    let token = retrieve_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+    remember_auth_token(&app.state::<AppState>(), &token);
+    Ok(token)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn delete_auth_token_command(app: AppHandle, server_id: String, user_id: String) -> Result<(), RegisError> {
+    info!("Frontend requested token deletion for user {} on server {}", user_id, server_id);
+    delete_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+
+    let state = app.state::<AppState>();
+    state.auth_tokens.lock().unwrap().remove(&server_id);
+    state.discovery_cache.remove(&server_id, &user_id);
+    state.searchable_targets.lock().unwrap().remove(&server_id);
+    Ok(())
+}
+
+#[command]
+#[instrument]
+async fn token_exists_command(server_id: String, user_id: String) -> Result<bool, RegisError> {
+    debug!("Frontend checking if token exists for user {} on server {}", user_id, server_id);
+    Ok(token_exists_in_keychain(&server_id, &user_id).await)
+}
+
+#[command]
+#[instrument]
+async fn list_stored_tokens_command() -> Result<Vec<TokenMetadata>, RegisError> {
+    info!("Frontend requested list of stored token metadata");
+    list_stored_token_metadata().await.map_err(RegisError::credentials)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn refresh_auth_token_command(
+    app: AppHandle,
+    server_id: String,
+    user_id: String
+) -> Result<StoredToken, RegisError> {
+    info!("Frontend requested token refresh for user {} on server {}", user_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Retrieve the current stored token
+    let current_token = retrieve_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+
+    // Find the server configuration
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let cli_options = cli_options_for_server(&state, server);
+
+    // Get auth method for re-authentication
+    let auth_methods = discover_oidc_auth_methods(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::auth)?;
+    let auth_method_id = auth_methods.first()
+        .ok_or_else(|| RegisError::AuthenticationFailed("No OIDC auth methods available".to_string()))?
+        .id.clone();
+
+    // Trigger re-authentication
+    let refreshed_token = trigger_reauthentication(
+        &cli_path,
+        &auth_cli_options(&state),
+        &server.url,
+        &auth_method_id,
+        &server_id,
+    ).await.map_err(RegisError::auth)?;
+
+    // Store the refreshed token
+    store_auth_token(&refreshed_token).await.map_err(RegisError::credentials)?;
+    remember_auth_token(&state, &refreshed_token);
+
+    Ok(refreshed_token)
+}
+
+// Validate a token using Boundary CLI
+#[command]
+#[instrument(skip(app))]
+async fn validate_token_command(
+    app: AppHandle,
+    server_id: String,
+    user_id: String
+) -> Result<bool, RegisError> {
+    info!("Frontend requested token validation for user {} on server {}", user_id, server_id);
+
+    let state = app.state::<AppState>();
+
+    // Retrieve the current stored token
+    let current_token = retrieve_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+
+    // Find the server configuration
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let cli_options = cli_options_for_server(&state, server);
+
+    // Validate the token
+    validate_token_with_cli(&cli_path, &cli_options, &server.url, &current_token).await.map_err(RegisError::auth)
+}
+
+// Check and refresh token if needed (main orchestration function)
+#[command]
+#[instrument(skip(app))]
+async fn check_and_refresh_token_command(
+    app: AppHandle,
+    server_id: String,
+    user_id: String,
+    threshold_minutes: Option<u32>
+) -> Result<StoredToken, RegisError> {
+    info!("Frontend requested token check and refresh for user {} on server {}", user_id, server_id);
+
+    let state = app.state::<AppState>();
+    let threshold = threshold_minutes.unwrap_or(5); // Default 5 minutes
+
+    // Retrieve the current stored token
+    let current_token = retrieve_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+
+    // Find the server configuration
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+    let api = api_client_for_server(&state, server);
+    let cli_options = cli_options_for_server(&state, server);
+
+    // Get auth method for re-authentication if needed
+    let auth_methods = discover_oidc_auth_methods(api.as_ref(), &cli_path, &cli_options, &server.url).await.map_err(RegisError::auth)?;
+    let auth_method_id = auth_methods.first()
+        .ok_or_else(|| RegisError::AuthenticationFailed("No OIDC auth methods available".to_string()))?
+        .id.clone();
+
+    // Check and refresh token
+    let result = check_and_refresh_token(
+        &cli_path,
+        &cli_options,
+        &auth_cli_options(&state),
+        &server.url,
+        &auth_method_id,
+        &server_id,
+        &user_id,
+        threshold
+    ).await.map_err(RegisError::auth)?;
+
+    let token = match result {
+        Some(token) => token,
+        None => current_token // Return existing token if no refresh was needed
+    };
+
+    remember_auth_token(&state, &token);
+    Ok(token)
+}
+
+// Start background token auto-refresh monitoring service
+#[command]
+#[instrument(skip(app))]
+async fn start_token_auto_refresh_command(
+    app: AppHandle,
+    check_interval_seconds: Option<u64>,
+    refresh_threshold_minutes: Option<u32>
+) -> Result<(), RegisError> {
+    info!("Frontend requested to start token auto-refresh monitoring service");
+
+    let state = app.state::<AppState>();
+    let interval = check_interval_seconds.unwrap_or(300); // Default 5 minutes
+    let threshold = refresh_threshold_minutes.unwrap_or(5); // Default 5 minutes
+
+    // Start the background monitoring service
+    let app_state = Arc::new(AppState {
+        config: state.config.clone(),
+        active_connections: state.active_connections.clone(),
+        session_health: state.session_health.clone(),
+        monitoring_enabled: state.monitoring_enabled.clone(),
+        auth_tokens: state.auth_tokens.clone(),
+        operations: state.operations.clone(),
+        boundary_cli: state.boundary_cli.clone(),
+        preferences: state.preferences.clone(),
+        discovery_cache: state.discovery_cache.clone(),
+        searchable_targets: state.searchable_targets.clone(),
+        api_clients: state.api_clients.clone(),
+    });
+    start_token_monitoring(app_state, interval, threshold).await;
+
+    Ok(())
+}
+
+// Auto-logout expired tokens
+#[command]
+#[instrument]
+async fn auto_logout_expired_tokens_command() -> Result<Vec<String>, RegisError> {
+    info!("Frontend requested auto-logout of expired tokens");
+    auto_logout_expired_tokens(60).await.map_err(RegisError::credentials) // Default 60 minutes max age
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn start_session_monitoring_command(app: AppHandle) -> Result<(), RegisError> {
+    info!("Frontend requested to start session monitoring");
+
+    let state = app.state::<AppState>();
+    let app_state = Arc::new(AppState {
+        config: state.config.clone(),
+        active_connections: state.active_connections.clone(),
+        session_health: state.session_health.clone(),
+        monitoring_enabled: state.monitoring_enabled.clone(),
+        auth_tokens: state.auth_tokens.clone(),
+        operations: state.operations.clone(),
+        boundary_cli: state.boundary_cli.clone(),
+        preferences: state.preferences.clone(),
+        discovery_cache: state.discovery_cache.clone(),
+        searchable_targets: state.searchable_targets.clone(),
+        api_clients: state.api_clients.clone(),
+    });
+
+    start_session_monitoring(app_state).await;
+    Ok(())
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn stop_session_monitoring_command(app: AppHandle) -> Result<(), RegisError> {
+    info!("Frontend requested to stop session monitoring");
+    let state = app.state::<AppState>();
+    stop_session_monitoring(&*state).await.map_err(RegisError::Internal)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn get_session_health_command(app: AppHandle, session_id: String) -> Result<SessionHealth, RegisError> {
+    debug!("Frontend requested health for session: {}", session_id);
+    let state = app.state::<AppState>();
+    get_session_health(&*state, &session_id).await.map_err(RegisError::Internal)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn get_monitoring_stats_command(app: AppHandle) -> Result<SessionMonitoringStats, RegisError> {
+    debug!("Frontend requested monitoring statistics");
+    let state = app.state::<AppState>();
+    get_monitoring_stats(&*state).await.map_err(RegisError::Internal)
+}
+
+#[command]
+#[instrument(skip(app))]
+async fn monitor_sessions_once_command(app: AppHandle) -> Result<SessionMonitoringStats, RegisError> {
+    info!("Frontend requested one-time session monitoring check");
+    let state = app.state::<AppState>();
+    monitor_active_sessions(&*state).await.map_err(RegisError::cli)
+}
+
+// === OIDC AUTHENTICATION COMMANDS ===
+
+// Initiate OIDC authentication flow
+#[command]
+#[instrument(skip(app))]
+async fn initiate_oidc_auth_command(
+    app: AppHandle,
+    auth_request: OIDCAuthRequest
+) -> Result<OIDCAuthProgress, RegisError> {
+    info!("Frontend requested OIDC authentication for server {} with auth method {}",
+          auth_request.server_id, auth_request.auth_method_id);
+
+    let state = app.state::<AppState>();
+
+    // Find the server configuration
+    let servers = load_servers(app.clone()).await?;
+    let server = servers
+        .iter()
+        .find(|s| s.id == auth_request.server_id)
+        .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", auth_request.server_id)))?;
+
+    let cli_path = get_boundary_cli_path(server, &state);
+
+    // Start OIDC authentication in background
+    let server_id = auth_request.server_id.clone();
+    let auth_method_id = auth_request.auth_method_id.clone();
+    let server_url = server.url.clone();
+    let app_handle = app.clone();
+    let operation = register_operation(&state, auth_request.operation_id.as_deref());
+    let cli_options = auth_cli_options(&state).with_cancel(operation.token());
+
+    tokio::spawn(async move {
+        info!("Starting background OIDC authentication for server {}", server_id);
+
+        // Keep the operation registered (and cancellable) until the flow finishes
+        let _operation = operation;
+
+        match trigger_reauthentication(&cli_path, &cli_options, &server_url, &auth_method_id, &server_id).await {
+            Ok(token) => {
+                info!("OIDC authentication completed successfully for server {}", server_id);
+                remember_auth_token(&app_handle.state::<AppState>(), &token);
+                // Store the token
+                if let Err(e) = store_auth_token(&token).await {
+                    error!("Failed to store authentication token: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("OIDC authentication failed for server {}: {}", server_id, e);
+            }
+        }
+    });
+
+    // Return initial progress
+    Ok(OIDCAuthProgress {
+        status: "started".to_string(),
+        message: "Initiating OIDC authentication...".to_string(),
+        auth_url: None,
+        progress_percent: 10,
+    })
+}
+
+// Check OIDC authentication status
+#[command]
+#[instrument(skip(app))]
+async fn check_oidc_auth_status_command(
+    app: AppHandle,
+    server_id: String,
+    user_id: String
+) -> Result<OIDCAuthResult, RegisError> {
+    info!("Frontend checking OIDC authentication status for user {} on server {}", user_id, server_id);
+
+    // Check if we have a stored token
+    match retrieve_auth_token(&server_id, &user_id).await {
+        Ok(token) => {
+            info!("Found stored authentication token for user {} on server {}", user_id, server_id);
+
+            // Check if we need to discover scopes
+            let state = app.state::<AppState>();
+            remember_auth_token(&state, &token);
+            let servers = load_servers(app.clone()).await?;
+            let server = servers
+                .iter()
+                .find(|s| s.id == server_id)
+                .ok_or_else(|| RegisError::Config(format!("Server with id '{}' not found", server_id)))?;
+
+            let cli_path = get_boundary_cli_path(server, &state);
+            let api = api_client_for_server(&state, server);
+            let cli_options = cli_options_for_server(&state, server);
+
+            // Discover available scopes
+            match discover_scopes(api.as_ref(), &cli_path, &cli_options, &server.url).await {
+                Ok(scopes) => {
+                    cache_scopes(&state, &server_id, &token.user_id, &scopes);
+                    Ok(OIDCAuthResult {
+                        success: true,
+                        token: Some(token),
+                        error: None,
+                        scope_tree: Some(build_scope_tree(&scopes)),
+                        scopes: Some(scopes),
+                    })
+                }
+                Err(e) => {
+                    warn!("Failed to discover scopes but token exists: {}", e);
+                    Ok(OIDCAuthResult {
+                        success: true,
+                        token: Some(token),
+                        error: None,
+                        scopes: None,
+                        scope_tree: None,
+                    })
+                }
+            }
+        }
+        Err(_) => {
+            Ok(OIDCAuthResult {
+                success: false,
+                token: None,
+                error: Some("No authentication token found".to_string()),
+                scopes: None,
+                scope_tree: None,
+            })
+        }
+    }
+}
+
+// Complete OIDC authentication workflow with scope selection
+#[command]
+#[instrument(skip(app))]
+async fn complete_oidc_auth_workflow_command(
+    app: AppHandle,
+    server_id: String,
+    user_id: String,
+    scope_id: Option<String>
+) -> Result<OIDCAuthResult, RegisError> {
+    info!("Frontend completing OIDC workflow for user {} on server {} with scope {:?}",
+          user_id, server_id, scope_id);
+
+    // Retrieve the current token
+    let mut token = retrieve_auth_token(&server_id, &user_id).await.map_err(RegisError::credentials)?;
+
+    // Update the scope if provided
+    if let Some(scope) = scope_id {
+        token.scope_id = scope;
+        store_auth_token(&token).await.map_err(RegisError::credentials)?;
+    }
+
+    remember_auth_token(&app.state::<AppState>(), &token);
+
+    Ok(OIDCAuthResult {
+        success: true,
+        token: Some(token),
+        error: None,
+        scopes: None,
+        scope_tree: None,
+    })
+}
+
+// Application entry point, called from main.rs
+pub fn run() {
+    // Early logging setup with minimal configuration
+    println!("Starting Regis application...");
+
+    tauri::Builder::default()
+        .setup(|app| {
+            println!("Initializing application...");
+
+            // Load configuration
+            let config = load_config(app.handle());
+
+            // Initialize logging system
+            if let Err(e) = init_logging(&config.logging) {
+                eprintln!("Failed to initialize logging: {}", e);
+                println!("Continuing with console logging only");
+            }
+
+            // Log startup information
+            info!("=== Regis Application Starting ===");
+            info!("Version: {}", app.package_info().version);
+            info!("Debug mode: {}", config.advanced.debug_mode);
+
+            let discovery_cache = open_discovery_cache(&config.connection);
+
+            // Store configuration and initialize connection tracking and monitoring in app state
+            app.manage(AppState {
+                config,
+                active_connections: Arc::new(Mutex::new(Vec::new())),
+                session_health: Arc::new(Mutex::new(HashMap::new())),
+                monitoring_enabled: Arc::new(Mutex::new(false)),
+                auth_tokens: Arc::new(Mutex::new(HashMap::new())),
+                operations: Arc::new(Mutex::new(HashMap::new())),
+                boundary_cli: Arc::new(Mutex::new(None)),
+                preferences: Arc::new(load_preferences()),
+                discovery_cache: Arc::new(discovery_cache),
+                searchable_targets: Arc::new(Mutex::new(HashMap::new())),
+                api_clients: Arc::new(Mutex::new(HashMap::new())),
+            });
+
+            // Detect the Boundary CLI in the background so the first command doesn't wait on it
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = resolve_boundary_cli(&app_handle, None).await {
+                    warn!("Boundary CLI detection at startup failed: {}", e);
+                }
+            });
+
+            info!("Application initialization completed successfully");
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            load_servers,
+            log_from_frontend,
+            get_config,
+            verify_boundary_cli_command,
+            diagnose_server_command,
+            discover_auth_methods_command,
+            discover_scopes_command,
+            discover_scope_tree_command,
+            discover_oidc_auth_methods_command,
+            verify_oidc_support_command,
+            discover_targets_command,
+            discover_all_targets_command,
+            get_cached_discovery_command,
+            search_targets_command,
+            get_target_details_command,
+            list_target_hosts_command,
+            list_hosts_command,
+            get_pinned_host_command,
+            set_pinned_host_command,
+            get_listen_settings_command,
+            set_listen_settings_command,
+            list_aliases_command,
+            resolve_alias_command,
+            pin_favorite_target_command,
+            unpin_favorite_target_command,
+            list_favorite_targets_command,
+            clear_favorite_targets_command,
+            list_recent_targets_command,
+            clear_recent_targets_command,
+            authorize_session_command,
+            establish_connection_command,
+            cancel_operation_command,
+            get_active_connections_command,
+            terminate_connection_command,
+            detect_rdp_clients_command,
+            launch_rdp_client_command,
+            store_auth_token_command,
+            retrieve_auth_token_command,
+            delete_auth_token_command,
+            token_exists_command,
+            list_stored_tokens_command,
+            refresh_auth_token_command,
+            validate_token_command,
+            check_and_refresh_token_command,
+            start_token_auto_refresh_command,
+            auto_logout_expired_tokens_command,
+            initiate_oidc_auth_command,
+            check_oidc_auth_status_command,
+            complete_oidc_auth_workflow_command,
+            start_session_monitoring_command,
+            stop_session_monitoring_command,
+            get_session_health_command,
+            get_monitoring_stats_command,
+            monitor_sessions_once_command
+        ])
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // Proxies run in their own process group and would outlive the app
+            if let tauri::RunEvent::Exit = event {
+                let processes: Vec<ConnectionProcess> =
+                    app.state::<AppState>().active_connections.lock().unwrap().drain(..).collect();
+                if !processes.is_empty() {
+                    info!("Stopping {} proxies before exit", processes.len());
+                    tauri::async_runtime::block_on(async {
+                        for process in processes {
+                            if let Err(e) = process.terminate().await {
+                                warn!("{}", e);
+                            }
+                        }
+                    });
+                }
+            }
+        });
+}
\ No newline at end of file
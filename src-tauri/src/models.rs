@@ -0,0 +1,587 @@
+// Typed models for Boundary CLI (`-format json`) and controller API payloads
+//
+// Both sources return the same resources, but the envelope differs between releases:
+// list output is a bare array on old CLIs and `{"items": [...]}` (optionally with
+// "status_code") on newer ones, where "items" is omitted when empty; single resources
+// are either bare or wrapped in `{"item": {...}}`. Required fields are plain `String`s
+// so a missing id or token fails to parse instead of turning into an empty string.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::redact;
+use crate::{BoundaryAlias, BoundaryAuthMethod, BoundaryHost, BoundaryHostSet, BoundaryScope, BoundarySessionAuthorization, BoundaryTarget};
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScopeInfo {
+    pub id: String,
+    #[serde(rename = "type", default)]
+    pub scope_type: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub parent_scope_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AuthMethodItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub method_type: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScopeItem {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub scope_type: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub scope_id: Option<String>, // Parent scope
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TargetAttributes {
+    #[serde(default)]
+    pub default_port: Option<u16>,
+    #[serde(default)]
+    pub default_client_port: Option<u16>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TargetItem {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub target_type: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub address: Option<String>,
+    // Top-level on old releases, under "attributes" on newer ones
+    #[serde(default)]
+    pub default_port: Option<u16>,
+    #[serde(default)]
+    pub attributes: Option<TargetAttributes>,
+    #[serde(default)]
+    pub scope: Option<ScopeInfo>,
+    #[serde(default)]
+    pub session_max_seconds: Option<u32>,
+    #[serde(default)]
+    pub session_connection_limit: Option<i32>,
+    #[serde(default)]
+    pub authorized_actions: Vec<String>,
+    // Older releases have a single worker filter, newer ones split it into egress/ingress
+    #[serde(default)]
+    pub worker_filter: Option<String>,
+    #[serde(default)]
+    pub egress_worker_filter: Option<String>,
+    #[serde(default)]
+    pub ingress_worker_filter: Option<String>,
+    // Host sets were renamed host sources
+    #[serde(default)]
+    pub host_source_ids: Vec<String>,
+    #[serde(default)]
+    pub host_set_ids: Vec<String>,
+    // "application" credential sources were renamed "brokered"
+    #[serde(default)]
+    pub brokered_credential_source_ids: Vec<String>,
+    #[serde(default)]
+    pub application_credential_source_ids: Vec<String>,
+    #[serde(default)]
+    pub injected_application_credential_source_ids: Vec<String>,
+    // Aliases pointing at the target (0.16+)
+    #[serde(default)]
+    pub aliases: Vec<TargetAliasItem>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TargetAliasItem {
+    pub value: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HostSetItem {
+    pub id: String,
+    pub host_catalog_id: String,
+    #[serde(rename = "type", default)]
+    pub host_set_type: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub host_ids: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HostAttributes {
+    #[serde(default)]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HostItem {
+    pub id: String,
+    #[serde(default)]
+    pub host_catalog_id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    // Static hosts carry an address attribute; plugin hosts report what the provider synced
+    #[serde(default)]
+    pub attributes: Option<HostAttributes>,
+    #[serde(default)]
+    pub ip_addresses: Vec<String>,
+    #[serde(default)]
+    pub dns_names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AuthorizeSessionArguments {
+    #[serde(default)]
+    pub host_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct AliasAttributes {
+    #[serde(default)]
+    pub authorize_session_arguments: Option<AuthorizeSessionArguments>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct AliasItem {
+    pub id: String,
+    pub value: String,
+    #[serde(rename = "type", default)]
+    pub alias_type: Option<String>,
+    // Unset for aliases that don't point anywhere yet
+    #[serde(default)]
+    pub destination_id: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub attributes: Option<AliasAttributes>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct SessionAuthorizationItem {
+    pub session_id: String,
+    pub target_id: String,
+    pub authorization_token: String,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub host_id: Option<String>,
+    #[serde(default)]
+    pub host_set_id: Option<String>,
+    #[serde(default)]
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub scope: Option<ScopeInfo>,
+    #[serde(default)]
+    pub created_time: Option<String>,
+    #[serde(default)]
+    pub expiration_time: Option<String>,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(rename = "type", default)]
+    pub target_type: Option<String>,
+    #[serde(default)]
+    pub connection_limit: Option<i32>,
+}
+
+impl std::fmt::Debug for SessionAuthorizationItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionAuthorizationItem")
+            .field("session_id", &self.session_id)
+            .field("target_id", &self.target_id)
+            .field("authorization_token", &redact::masked(&self.authorization_token))
+            .field("host_id", &self.host_id)
+            .field("endpoint", &self.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SessionState {
+    pub status: String,
+    #[serde(default)]
+    pub start_time: Option<String>,
+    #[serde(default)]
+    pub end_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct SessionItem {
+    pub id: String,
+    pub status: String,
+    #[serde(default)]
+    pub target_id: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub host_id: Option<String>,
+    #[serde(default)]
+    pub scope_id: Option<String>,
+    #[serde(default)]
+    pub scope: Option<ScopeInfo>,
+    #[serde(rename = "type", default)]
+    pub session_type: Option<String>,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub created_time: Option<String>,
+    #[serde(default)]
+    pub expiration_time: Option<String>,
+    #[serde(default)]
+    pub termination_reason: Option<String>,
+    #[serde(default)]
+    pub states: Vec<SessionState>,
+    #[serde(default)]
+    pub version: u32,
+}
+
+// Result of `authenticate <method> -format json`. The token itself is optional because
+// some CLI builds only write it to their keyring.
+#[derive(Deserialize, Clone)]
+pub struct AuthTokenItem {
+    pub user_id: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub auth_method_id: Option<String>,
+    #[serde(default)]
+    pub expiration_time: Option<String>,
+}
+
+impl std::fmt::Debug for AuthTokenItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthTokenItem")
+            .field("user_id", &self.user_id)
+            .field("id", &self.id)
+            .field("token", &self.token.as_deref().map(redact::masked))
+            .field("auth_method_id", &self.auth_method_id)
+            .field("expiration_time", &self.expiration_time)
+            .finish()
+    }
+}
+
+// Parse a list payload into typed items, naming the offending item on failure
+pub fn parse_items<T: DeserializeOwned>(json: &Value) -> Result<Vec<T>, String> {
+    let items = match json {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(map) => match map.get("items") {
+            Some(Value::Array(items)) => items.as_slice(),
+            Some(Value::Null) | None => &[],
+            Some(other) => return Err(format!("expected \"items\" to be an array, got {}", other)),
+        },
+        other => return Err(format!("expected a list response, got {}", other)),
+    };
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            T::deserialize(item).map_err(|e| {
+                let id = item["id"].as_str().unwrap_or("<no id>");
+                format!("item {} ({}): {}", index, id, e)
+            })
+        })
+        .collect()
+}
+
+// Parse a single-resource payload, bare or wrapped in "item"
+pub fn parse_item<T: DeserializeOwned>(json: &Value) -> Result<T, String> {
+    let item = match json.get("item") {
+        Some(item) if item.is_object() => item,
+        _ => json,
+    };
+    T::deserialize(item).map_err(|e| e.to_string())
+}
+
+// Parse CLI `-format json` list output
+pub fn items_from_str<T: DeserializeOwned>(output: &str) -> Result<Vec<T>, String> {
+    let json: Value = serde_json::from_str(output).map_err(|e| e.to_string())?;
+    parse_items(&json)
+}
+
+// Parse CLI `-format json` single-resource output
+pub fn item_from_str<T: DeserializeOwned>(output: &str) -> Result<T, String> {
+    let json: Value = serde_json::from_str(output).map_err(|e| e.to_string())?;
+    parse_item(&json)
+}
+
+// Parse `authenticate` output; the token fields sit under "item" -> "attributes" on current CLIs
+pub fn auth_token_from_str(output: &str) -> Result<AuthTokenItem, String> {
+    let json: Value = serde_json::from_str(output).map_err(|e| e.to_string())?;
+    let item = match json.get("item") {
+        Some(item) if item.is_object() => item,
+        _ => &json,
+    };
+    let item = match item.get("attributes") {
+        Some(attributes) if attributes.is_object() => attributes,
+        _ => item,
+    };
+    AuthTokenItem::deserialize(item).map_err(|e| e.to_string())
+}
+
+impl From<AuthMethodItem> for BoundaryAuthMethod {
+    fn from(item: AuthMethodItem) -> Self {
+        BoundaryAuthMethod {
+            name: item.name.unwrap_or_else(|| item.id.clone()),
+            id: item.id,
+            method_type: item.method_type,
+            description: item.description.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<ScopeItem> for BoundaryScope {
+    fn from(item: ScopeItem) -> Self {
+        BoundaryScope {
+            name: item.name.unwrap_or_else(|| item.id.clone()),
+            id: item.id,
+            scope_type: item.scope_type,
+            description: item.description.unwrap_or_default(),
+            parent_scope_id: item.scope_id,
+        }
+    }
+}
+
+impl From<TargetItem> for BoundaryTarget {
+    fn from(item: TargetItem) -> Self {
+        let default_port = item
+            .attributes
+            .as_ref()
+            .and_then(|a| a.default_port)
+            .or(item.default_port);
+        let default_client_port = item.attributes.as_ref().and_then(|a| a.default_client_port);
+
+        let scope_id = item.scope_id.or_else(|| item.scope.as_ref().map(|s| s.id.clone()));
+        let scope_name = item.scope.and_then(|s| s.name);
+
+        let host_source_ids = if item.host_source_ids.is_empty() { item.host_set_ids } else { item.host_source_ids };
+        let brokered_credential_source_ids = if item.brokered_credential_source_ids.is_empty() {
+            item.application_credential_source_ids
+        } else {
+            item.brokered_credential_source_ids
+        };
+
+        BoundaryTarget {
+            id: item.id,
+            name: item.name,
+            target_type: item.target_type,
+            description: item.description.unwrap_or_default(),
+            address: item.address,
+            default_port,
+            scope_id,
+            scope_name,
+            session_max_seconds: item.session_max_seconds,
+            session_connection_limit: item.session_connection_limit,
+            authorized_actions: item.authorized_actions,
+            default_client_port,
+            egress_worker_filter: item.egress_worker_filter.or(item.worker_filter),
+            ingress_worker_filter: item.ingress_worker_filter,
+            host_source_ids,
+            brokered_credential_source_ids,
+            injected_application_credential_source_ids: item.injected_application_credential_source_ids,
+            aliases: item.aliases.into_iter().map(|alias| alias.value).collect(),
+        }
+    }
+}
+
+impl From<AliasItem> for BoundaryAlias {
+    fn from(item: AliasItem) -> Self {
+        let host_id = item
+            .attributes
+            .and_then(|a| a.authorize_session_arguments)
+            .and_then(|a| a.host_id);
+
+        BoundaryAlias {
+            id: item.id,
+            value: item.value,
+            alias_type: item.alias_type.unwrap_or_else(|| "target".to_string()),
+            destination_id: item.destination_id,
+            host_id,
+            name: item.name.unwrap_or_default(),
+            description: item.description.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<HostSetItem> for BoundaryHostSet {
+    fn from(item: HostSetItem) -> Self {
+        BoundaryHostSet {
+            name: item.name.unwrap_or_else(|| item.id.clone()),
+            id: item.id,
+            host_catalog_id: item.host_catalog_id,
+            host_set_type: item.host_set_type.unwrap_or_default(),
+            description: item.description.unwrap_or_default(),
+            host_ids: item.host_ids,
+            hosts: Vec::new(),
+        }
+    }
+}
+
+impl From<HostItem> for BoundaryHost {
+    fn from(item: HostItem) -> Self {
+        let address = item
+            .attributes
+            .and_then(|a| a.address)
+            .or_else(|| item.dns_names.first().cloned())
+            .or_else(|| item.ip_addresses.first().cloned());
+
+        BoundaryHost {
+            name: item.name.unwrap_or_else(|| item.id.clone()),
+            id: item.id,
+            host_catalog_id: item.host_catalog_id.unwrap_or_default(),
+            description: item.description.unwrap_or_default(),
+            address,
+            ip_addresses: item.ip_addresses,
+            dns_names: item.dns_names,
+        }
+    }
+}
+
+impl From<SessionAuthorizationItem> for BoundarySessionAuthorization {
+    fn from(item: SessionAuthorizationItem) -> Self {
+        let scope_id = item
+            .scope_id
+            .or_else(|| item.scope.map(|s| s.id))
+            .unwrap_or_default();
+
+        BoundarySessionAuthorization {
+            authorization_token: item.authorization_token,
+            session_id: item.session_id,
+            target_id: item.target_id,
+            user_id: item.user_id.unwrap_or_default(),
+            host_id: item.host_id,
+            scope_id,
+            created_time: item.created_time.unwrap_or_default(),
+            expiration_time: item.expiration_time,
+            connection_limit: item.connection_limit.unwrap_or(-1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! fixture {
+        ($release:literal, $name:literal) => {
+            include_str!(concat!("../tests/fixtures/boundary/", $release, "/", $name, ".json"))
+        };
+    }
+
+    #[test]
+    fn parses_bare_payloads_from_0_9() {
+        let methods: Vec<AuthMethodItem> = items_from_str(fixture!("0.9", "auth-methods")).unwrap();
+        assert_eq!(methods[0].id, "ampw_1234567890");
+        assert_eq!(methods[0].method_type, "password");
+
+        let scopes: Vec<ScopeItem> = items_from_str(fixture!("0.9", "scopes")).unwrap();
+        assert_eq!(BoundaryScope::from(scopes[0].clone()).name, "Engineering");
+
+        let targets: Vec<TargetItem> = items_from_str(fixture!("0.9", "targets")).unwrap();
+        let target = BoundaryTarget::from(targets[0].clone());
+        assert_eq!(target.id, "ttcp_1234567890");
+        assert_eq!(target.default_port, Some(22));
+        assert_eq!(target.address, None);
+
+        let auth: SessionAuthorizationItem = item_from_str(fixture!("0.9", "authorize-session")).unwrap();
+        let auth = BoundarySessionAuthorization::from(auth);
+        assert_eq!(auth.authorization_token, "FAKE-AUTHZ-TOKEN-0.9");
+        assert_eq!(auth.scope_id, "p_1234567890");
+        assert_eq!(auth.connection_limit, -1);
+
+        let session: SessionItem = item_from_str(fixture!("0.9", "session")).unwrap();
+        assert_eq!(session.status, "active");
+        assert_eq!(session.states.len(), 2);
+    }
+
+    #[test]
+    fn parses_wrapped_payloads_from_0_13() {
+        let methods: Vec<AuthMethodItem> = items_from_str(fixture!("0.13", "auth-methods")).unwrap();
+        assert_eq!(methods.len(), 2);
+        assert!(methods[0].is_primary);
+        assert_eq!(methods[0].method_type, "oidc");
+        // Unnamed auth methods fall back to their id
+        assert_eq!(BoundaryAuthMethod::from(methods[1].clone()).name, "ampw_1234567890");
+
+        let scopes: Vec<ScopeItem> = items_from_str(fixture!("0.13", "scopes")).unwrap();
+        assert_eq!(scopes[1].scope_id.as_deref(), Some("o_1234567890"));
+
+        let targets: Vec<TargetItem> = items_from_str(fixture!("0.13", "targets")).unwrap();
+        assert_eq!(BoundaryTarget::from(targets[0].clone()).default_port, Some(3389));
+
+        let auth: SessionAuthorizationItem = item_from_str(fixture!("0.13", "authorize-session")).unwrap();
+        assert_eq!(auth.session_id, "s_1234567890");
+        assert_eq!(auth.endpoint.as_deref(), Some("tcp://10.0.0.5:3389"));
+
+        let session: SessionItem = item_from_str(fixture!("0.13", "session")).unwrap();
+        assert_eq!(session.id, "s_1234567890");
+        assert_eq!(session.status, "active");
+    }
+
+    #[test]
+    fn parses_paginated_payloads_from_0_19() {
+        let methods: Vec<AuthMethodItem> = items_from_str(fixture!("0.19", "auth-methods")).unwrap();
+        assert_eq!(methods[0].id, "amoidc_abcdefghij");
+
+        // Empty lists omit "items" entirely
+        let scopes: Vec<ScopeItem> = items_from_str(fixture!("0.19", "scopes")).unwrap();
+        assert!(scopes.is_empty());
+
+        let targets: Vec<TargetItem> = items_from_str(fixture!("0.19", "targets")).unwrap();
+        let targets: Vec<BoundaryTarget> = targets.into_iter().map(BoundaryTarget::from).collect();
+        assert_eq!(targets[0].address.as_deref(), Some("10.0.1.20"));
+        assert_eq!(targets[0].default_port, Some(22));
+        assert_eq!(targets[1].target_type, "tcp");
+
+        let auth: SessionAuthorizationItem = item_from_str(fixture!("0.19", "authorize-session")).unwrap();
+        assert_eq!(BoundarySessionAuthorization::from(auth).scope_id, "p_abcdefghij");
+
+        let session: SessionItem = item_from_str(fixture!("0.19", "session")).unwrap();
+        assert_eq!(session.status, "terminated");
+        assert_eq!(session.termination_reason.as_deref(), Some("closed by end-user"));
+    }
+
+    #[test]
+    fn missing_required_fields_are_errors() {
+        let err = items_from_str::<TargetItem>(r#"{"items": [{"id": "ttcp_1", "type": "tcp"}]}"#).unwrap_err();
+        assert!(err.contains("ttcp_1") && err.contains("name"), "{}", err);
+
+        let err = item_from_str::<SessionAuthorizationItem>(
+            r#"{"item": {"session_id": "s_1", "target_id": "ttcp_1"}}"#,
+        )
+        .unwrap_err();
+        assert!(err.contains("authorization_token"), "{}", err);
+
+        let err = item_from_str::<SessionItem>(r#"{"item": {"id": "s_1"}}"#).unwrap_err();
+        assert!(err.contains("status"), "{}", err);
+
+        assert!(items_from_str::<ScopeItem>(r#"{"items": {}}"#).is_err());
+        assert!(items_from_str::<ScopeItem>("not json").is_err());
+    }
+}
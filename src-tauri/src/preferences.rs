@@ -0,0 +1,279 @@
+// Per-user preferences persisted in ~/.regis/preferences.json
+//
+// Unlike config.json, which users edit by hand, this file is written by Regis itself as
+// choices are made in the UI. Everything is keyed by server id, then target id, so one
+// profile can hold preferences for several Boundary clusters. Each server also keeps the
+// user's favorite targets and the targets they connected to most recently.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::{debug, info, warn};
+
+pub const PREFERENCES_FILE: &str = "preferences.json";
+
+// Targets remembered in a server's recently used list
+pub const RECENT_TARGETS_LIMIT: usize = 10;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Preferences {
+    #[serde(default)]
+    pub servers: HashMap<String, ServerPreferences>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ServerPreferences {
+    #[serde(default)]
+    pub targets: HashMap<String, TargetPreferences>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub favorites: Vec<String>, // Target ids in the order they were pinned
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent: Vec<RecentTarget>, // Most recently used first
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentTarget {
+    pub target_id: String,
+    pub target_name: String, // Shown before discovery has listed the target again
+    pub last_used: DateTime<Utc>,
+}
+
+impl ServerPreferences {
+    // Returns false when the target was already a favorite
+    pub fn pin_favorite(&mut self, target_id: &str) -> bool {
+        if self.favorites.iter().any(|id| id == target_id) {
+            return false;
+        }
+        self.favorites.push(target_id.to_string());
+        true
+    }
+
+    // Returns false when the target was not a favorite
+    pub fn unpin_favorite(&mut self, target_id: &str) -> bool {
+        let before = self.favorites.len();
+        self.favorites.retain(|id| id != target_id);
+        self.favorites.len() != before
+    }
+
+    // Move the target to the front of the recently used list, dropping the oldest entries
+    pub fn record_use(&mut self, target_id: &str, target_name: &str, used_at: DateTime<Utc>) {
+        self.recent.retain(|recent| recent.target_id != target_id);
+        self.recent.insert(
+            0,
+            RecentTarget {
+                target_id: target_id.to_string(),
+                target_name: target_name.to_string(),
+                last_used: used_at,
+            },
+        );
+        self.recent.truncate(RECENT_TARGETS_LIMIT);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.targets.is_empty() && self.favorites.is_empty() && self.recent.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetPreferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_id: Option<String>, // Host passed to authorize-session instead of a random one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen_addr: Option<String>, // Local address for the connect proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen_port: Option<u16>, // Fixed local port, so saved client profiles keep working
+}
+
+impl TargetPreferences {
+    fn is_empty(&self) -> bool {
+        self.host_id.is_none() && self.listen_addr.is_none() && self.listen_port.is_none()
+    }
+}
+
+// Preferences loaded once at startup and written back on every change
+#[derive(Debug)]
+pub struct PreferenceStore {
+    path: Option<PathBuf>, // None keeps preferences in memory only
+    preferences: Mutex<Preferences>,
+}
+
+impl PreferenceStore {
+    // A missing file is an empty profile; an unreadable one is logged and ignored
+    pub fn load(path: PathBuf) -> Self {
+        let preferences = match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(preferences) => {
+                    info!("Loaded user preferences from {:?}", path);
+                    preferences
+                }
+                Err(e) => {
+                    warn!("Ignoring unreadable preferences file {:?}: {}", path, e);
+                    Preferences::default()
+                }
+            },
+            Err(_) => {
+                debug!("No preferences file at {:?}", path);
+                Preferences::default()
+            }
+        };
+
+        PreferenceStore { path: Some(path), preferences: Mutex::new(preferences) }
+    }
+
+    pub fn in_memory() -> Self {
+        PreferenceStore { path: None, preferences: Mutex::new(Preferences::default()) }
+    }
+
+    pub fn server(&self, server_id: &str) -> ServerPreferences {
+        let preferences = self.preferences.lock().unwrap();
+        preferences.servers.get(server_id).cloned().unwrap_or_default()
+    }
+
+    pub fn target(&self, server_id: &str, target_id: &str) -> TargetPreferences {
+        let preferences = self.preferences.lock().unwrap();
+        preferences
+            .servers
+            .get(server_id)
+            .and_then(|server| server.targets.get(target_id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Apply `change` to one server's preferences and persist the result
+    pub fn update_server<T>(
+        &self,
+        server_id: &str,
+        change: impl FnOnce(&mut ServerPreferences) -> T,
+    ) -> Result<T, String> {
+        let mut preferences = self.preferences.lock().unwrap();
+        let server = preferences.servers.entry(server_id.to_string()).or_default();
+        let result = change(server);
+
+        // Don't accumulate empty entries for servers the user has reset
+        if server.is_empty() {
+            preferences.servers.remove(server_id);
+        }
+
+        if let Some(path) = &self.path {
+            save(path, &preferences)?;
+        }
+        Ok(result)
+    }
+
+    // Apply `change` to one target's preferences and persist the result
+    pub fn update_target(
+        &self,
+        server_id: &str,
+        target_id: &str,
+        change: impl FnOnce(&mut TargetPreferences),
+    ) -> Result<TargetPreferences, String> {
+        self.update_server(server_id, |server| {
+            let target = server.targets.entry(target_id.to_string()).or_default();
+            change(target);
+            let updated = target.clone();
+
+            // Don't accumulate empty entries for targets the user has reset
+            if updated.is_empty() {
+                server.targets.remove(target_id);
+            }
+            updated
+        })
+    }
+}
+
+// Write through a temporary file so a crash never leaves half a preferences file behind
+fn save(path: &Path, preferences: &Preferences) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(preferences)
+        .map_err(|e| format!("Failed to serialize preferences: {}", e))?;
+    let temp_path = path.with_extension("json.tmp");
+
+    fs::write(&temp_path, content).map_err(|e| format!("Failed to write {:?}: {}", temp_path, e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to replace {:?}: {}", path, e))?;
+
+    debug!("Saved user preferences to {:?}", path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("regis-preferences-{}-{}.json", std::process::id(), name))
+    }
+
+    #[test]
+    fn pinned_hosts_survive_a_reload() {
+        let path = temp_path("reload");
+        let store = PreferenceStore::load(path.clone());
+        store
+            .update_target("dev", "ttcp_1", |target| target.host_id = Some("hst_1".to_string()))
+            .unwrap();
+
+        let reloaded = PreferenceStore::load(path.clone());
+        assert_eq!(reloaded.target("dev", "ttcp_1").host_id.as_deref(), Some("hst_1"));
+        assert_eq!(reloaded.target("dev", "ttcp_2"), TargetPreferences::default());
+
+        // A target is only forgotten once none of its settings remain
+        reloaded.update_target("dev", "ttcp_1", |target| target.listen_port = Some(13389)).unwrap();
+        reloaded.update_target("dev", "ttcp_1", |target| target.host_id = None).unwrap();
+        assert_eq!(PreferenceStore::load(path.clone()).target("dev", "ttcp_1").listen_port, Some(13389));
+
+        reloaded.update_target("dev", "ttcp_1", |target| target.listen_port = None).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("dev"), "{}", content);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn favorites_and_recent_targets_are_kept_per_server() {
+        let path = temp_path("favorites");
+        let store = PreferenceStore::load(path.clone());
+
+        assert!(store.update_server("dev", |server| server.pin_favorite("ttcp_1")).unwrap());
+        assert!(!store.update_server("dev", |server| server.pin_favorite("ttcp_1")).unwrap());
+        store.update_server("dev", |server| server.pin_favorite("ttcp_2")).unwrap();
+        for i in 0..RECENT_TARGETS_LIMIT + 2 {
+            store
+                .update_server("dev", |server| server.record_use(&format!("ttcp_{}", i), "target", Utc::now()))
+                .unwrap();
+        }
+        store.update_server("dev", |server| server.record_use("ttcp_3", "db", Utc::now())).unwrap();
+
+        let reloaded = PreferenceStore::load(path.clone());
+        let dev = reloaded.server("dev");
+        assert_eq!(dev.favorites, vec!["ttcp_1", "ttcp_2"]);
+        assert_eq!(dev.recent.len(), RECENT_TARGETS_LIMIT);
+        assert_eq!(dev.recent[0].target_id, "ttcp_3");
+        assert_eq!(dev.recent[0].target_name, "db");
+        assert_eq!(dev.recent.iter().filter(|r| r.target_id == "ttcp_3").count(), 1);
+        assert!(reloaded.server("prod").favorites.is_empty());
+
+        reloaded
+            .update_server("dev", |server| {
+                server.unpin_favorite("ttcp_1");
+                server.unpin_favorite("ttcp_2");
+                server.recent.clear();
+            })
+            .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("dev"), "{}", content);
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn unreadable_files_start_empty() {
+        let path = temp_path("corrupt");
+        fs::write(&path, "{ not json").unwrap();
+
+        let store = PreferenceStore::load(path.clone());
+        assert_eq!(store.target("dev", "ttcp_1"), TargetPreferences::default());
+
+        let _ = fs::remove_file(path);
+    }
+}
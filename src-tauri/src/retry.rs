@@ -0,0 +1,324 @@
+// Retry policy for Boundary CLI/API calls
+//
+// Failures are sorted into retryable (network errors, 5xx, timeouts) and fatal
+// (permission denied, bad token, cancelled, malformed responses) by inspecting the
+// error message, since both the CLI and the controller API surface errors as text.
+
+use rand::Rng;
+use regex::Regex;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::ConnectionConfig;
+
+// Upper bound for a single backoff delay, whatever the attempt number
+const MAX_RETRY_DELAY_SECONDS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    Retryable,
+    Fatal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    // A single attempt, no retries
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl RetryPolicy {
+    // `retry_attempts` counts retries after the first attempt
+    pub fn from_config(config: &ConnectionConfig) -> Self {
+        RetryPolicy {
+            max_attempts: config.retry_attempts.saturating_add(1),
+            base_delay: Duration::from_secs(config.retry_delay_seconds as u64),
+            max_delay: Duration::from_secs(MAX_RETRY_DELAY_SECONDS),
+        }
+    }
+
+    // Exponential backoff with jitter: half the nominal delay is fixed, the other half random
+    fn delay_for(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let nominal = self
+            .base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay.max(self.base_delay));
+
+        let half = nominal / 2;
+        let jitter_ms = rand::thread_rng().gen_range(0..=half.as_millis() as u64);
+        half + Duration::from_millis(jitter_ms)
+    }
+}
+
+fn status_code_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // Matches "Status: 503" (CLI) and "HTTP 503" (controller API client)
+    RE.get_or_init(|| Regex::new(r"(?i)(?:status:?\s*|http\s+)(\d{3})\b").unwrap())
+}
+
+// HTTP status reported in a CLI or controller API error message, if any
+pub fn status_code(message: &str) -> Option<u16> {
+    status_code_regex()
+        .captures(message)
+        .and_then(|c| c[1].parse::<u16>().ok())
+}
+
+// Decide whether an error message describes a transient failure worth retrying
+pub fn classify_error(message: &str) -> ErrorClass {
+    let lower = message.to_lowercase();
+
+    if lower.contains("operation cancelled") {
+        return ErrorClass::Fatal;
+    }
+
+    if let Some(code) = status_code(message) {
+        return match code {
+            408 | 429 | 500..=599 => ErrorClass::Retryable,
+            400..=499 => ErrorClass::Fatal,
+            _ => classify_by_text(&lower),
+        };
+    }
+
+    classify_by_text(&lower)
+}
+
+fn classify_by_text(lower: &str) -> ErrorClass {
+    const FATAL: &[&str] = &[
+        "permissiondenied",
+        "permission denied",
+        "unauthenticated",
+        "unauthorized",
+        "forbidden",
+        "invalid token",
+        "token expired",
+        "no authentication token",
+    ];
+    const RETRYABLE: &[&str] = &[
+        "timed out",
+        "timeout",
+        "connection refused",
+        "connection reset",
+        "connection closed",
+        "broken pipe",
+        "no such host",
+        "dial tcp",
+        "error performing client request",
+        "error sending request",
+        "unexpected eof",
+        "bad gateway",
+        "unavailable",
+    ];
+
+    if FATAL.iter().any(|p| lower.contains(p)) {
+        ErrorClass::Fatal
+    } else if RETRYABLE.iter().any(|p| lower.contains(p)) {
+        ErrorClass::Retryable
+    } else {
+        ErrorClass::Fatal
+    }
+}
+
+// For operations that must not run twice, such as authorizing a session: only failures
+// showing the request never reached the server are worth retrying. A timeout or a 5xx
+// may come after the server already acted on it.
+pub fn classify_unsent_error(message: &str) -> ErrorClass {
+    const UNSENT: &[&str] = &[
+        "connection refused",
+        "no such host",
+        "dial tcp",
+        "network is unreachable",
+        "no route to host",
+        "dns error",
+        "failed to lookup address",
+    ];
+
+    let lower = message.to_lowercase();
+    if !lower.contains("operation cancelled") && UNSENT.iter().any(|p| lower.contains(p)) {
+        ErrorClass::Retryable
+    } else {
+        ErrorClass::Fatal
+    }
+}
+
+// Run `operation` until it succeeds, fails fatally, or the policy runs out of attempts.
+// The returned error reports how many attempts were made when there was more than one.
+pub async fn with_retry<T, F, Fut>(
+    policy: &RetryPolicy,
+    operation_name: &str,
+    cancel: Option<&CancellationToken>,
+    operation: F,
+) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    with_retry_classified(policy, operation_name, cancel, classify_error, operation).await
+}
+
+// Like `with_retry`, with the caller deciding which errors are retryable
+pub async fn with_retry_classified<T, F, Fut>(
+    policy: &RetryPolicy,
+    operation_name: &str,
+    cancel: Option<&CancellationToken>,
+    classify: fn(&str) -> ErrorClass,
+    mut operation: F,
+) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, String>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let error = match operation().await {
+            Ok(value) => {
+                if attempt > 1 {
+                    info!("{} succeeded on attempt {}/{}", operation_name, attempt, max_attempts);
+                }
+                return Ok(value);
+            }
+            Err(e) => e,
+        };
+
+        let class = classify(&error);
+        if class == ErrorClass::Fatal || attempt >= max_attempts {
+            if attempt > 1 {
+                return Err(format!("{} (failed after {} attempts)", error, attempt));
+            }
+            return Err(error);
+        }
+
+        let delay = policy.delay_for(attempt);
+        warn!(
+            "{} failed on attempt {}/{} with retryable error, retrying in {}ms: {}",
+            operation_name,
+            attempt,
+            max_attempts,
+            delay.as_millis(),
+            error
+        );
+
+        match cancel {
+            Some(token) => {
+                tokio::select! {
+                    _ = tokio::time::sleep(delay) => {}
+                    _ = token.cancelled() => {
+                        return Err(format!("Operation cancelled: '{}'", operation_name));
+                    }
+                }
+            }
+            None => tokio::time::sleep(delay).await,
+        }
+
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn config(retry_attempts: u32, retry_delay_seconds: u32) -> ConnectionConfig {
+        serde_json::from_value(serde_json::json!({
+            "auto_connect_single_target": false,
+            "connection_timeout_seconds": 30,
+            "retry_attempts": retry_attempts,
+            "retry_delay_seconds": retry_delay_seconds,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn classifies_errors_by_status_and_text() {
+        let cases = [
+            ("Error from controller: status: 403, kind: PermissionDenied", ErrorClass::Fatal),
+            ("Error from controller when performing list on targets: Status:      503", ErrorClass::Retryable),
+            ("Controller API returned HTTP 504 (Unknown): gateway timeout", ErrorClass::Retryable),
+            ("Controller API returned HTTP 401 (Unauthenticated): timeout", ErrorClass::Fatal),
+            ("dial tcp 10.0.0.1:9200: connect: connection refused", ErrorClass::Retryable),
+            ("Error reading auth token: invalid token", ErrorClass::Fatal),
+            ("Operation cancelled: 'boundary targets list' (connection refused)", ErrorClass::Fatal),
+            ("Failed to parse targets JSON: expected value", ErrorClass::Fatal),
+        ];
+        for (message, class) in cases {
+            assert_eq!(classify_error(message), class, "{}", message);
+        }
+    }
+
+    #[test]
+    fn retries_authorizations_only_when_unsent() {
+        assert_eq!(classify_unsent_error("dial tcp 10.0.0.1:9200: connect: connection refused"), ErrorClass::Retryable);
+        assert_eq!(classify_unsent_error("Status: 503, Kind: Unavailable"), ErrorClass::Fatal);
+        assert_eq!(classify_unsent_error("Operation timed out after 30s: 'boundary targets authorize-session'"), ErrorClass::Fatal);
+    }
+
+    #[test]
+    fn policy_follows_the_config() {
+        let policy = RetryPolicy::from_config(&config(3, 2));
+        assert_eq!(policy.max_attempts, 4);
+        assert_eq!(policy.base_delay, Duration::from_secs(2));
+        assert_eq!(RetryPolicy::from_config(&config(0, 2)).max_attempts, 1);
+    }
+
+    #[test]
+    fn delays_grow_and_are_capped() {
+        let policy = RetryPolicy::from_config(&config(10, 1));
+
+        // Between half and all of 1s, 2s, 4s...
+        for attempt in 1..=4 {
+            let nominal = Duration::from_secs(1 << (attempt - 1));
+            let delay = policy.delay_for(attempt);
+            assert!(delay >= nominal / 2 && delay <= nominal, "attempt {}: {:?}", attempt, delay);
+        }
+        for attempt in [6, 10, 40] {
+            let delay = policy.delay_for(attempt);
+            assert!(delay >= policy.max_delay / 2 && delay <= policy.max_delay, "attempt {}: {:?}", attempt, delay);
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_how_many_attempts_failed() {
+        let policy = RetryPolicy { max_attempts: 3, base_delay: Duration::ZERO, max_delay: Duration::ZERO };
+        let calls = AtomicU32::new(0);
+
+        let error = with_retry(&policy, "list targets", None, || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>("Status: 503".to_string())
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(error, "Status: 503 (failed after 3 attempts)");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn stops_waiting_when_cancelled() {
+        let policy = RetryPolicy { max_attempts: 3, base_delay: Duration::from_secs(60), max_delay: Duration::from_secs(60) };
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let error = with_retry(&policy, "list targets", Some(&cancel), || async { Err::<(), _>("connection refused".to_string()) })
+            .await
+            .unwrap_err();
+
+        assert_eq!(error, "Operation cancelled: 'list targets'");
+    }
+}
@@ -0,0 +1,319 @@
+// Fuzzy, ranked target search over the discovered target list
+//
+// Every whitespace-separated term of the query must match at least one searchable field,
+// either as a substring or as a compact subsequence ("pgprd" finds "postgres-prod").
+// Matches in the name weigh more than matches in the description, and favorites and
+// recently used targets are boosted so the targets people actually use come first. The
+// matched character positions are returned so the frontend can highlight them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::preferences::{RecentTarget, RECENT_TARGETS_LIMIT};
+use crate::BoundaryTarget;
+
+const FAVORITE_BOOST: u32 = 150;
+const RECENT_BOOST: u32 = 100; // For the most recent target, decreasing down the list
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchField {
+    Name,
+    Alias,
+    Id,
+    Address,
+    ScopeName,
+    Description,
+}
+
+impl SearchField {
+    const ALL: [SearchField; 6] = [
+        SearchField::Name,
+        SearchField::Alias,
+        SearchField::Id,
+        SearchField::Address,
+        SearchField::ScopeName,
+        SearchField::Description,
+    ];
+
+    fn weight(self) -> u32 {
+        match self {
+            SearchField::Name | SearchField::Alias => 4,
+            SearchField::Id => 3,
+            SearchField::Address | SearchField::ScopeName => 2,
+            SearchField::Description => 1,
+        }
+    }
+
+    // Aliases are searched as shown: joined with ", "
+    fn chars(self, target: &BoundaryTarget) -> Option<Vec<char>> {
+        let value = match self {
+            SearchField::Name => &target.name,
+            SearchField::Alias if target.aliases.is_empty() => return None,
+            SearchField::Alias => return Some(target.aliases.join(", ").chars().collect()),
+            SearchField::Id => &target.id,
+            SearchField::Address => target.address.as_ref()?,
+            SearchField::ScopeName => target.scope_name.as_ref()?,
+            SearchField::Description => &target.description,
+        };
+        Some(value.chars().collect())
+    }
+}
+
+// Character positions (not byte offsets) matched in one field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldHighlight {
+    pub field: SearchField,
+    pub positions: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetMatch {
+    pub target: BoundaryTarget,
+    pub score: u32,
+    pub favorite: bool,
+    pub highlights: Vec<FieldHighlight>,
+}
+
+// Rank `targets` against `query`, best match first; an empty query ranks by favorites and recency
+pub fn search_targets(
+    targets: &[BoundaryTarget],
+    query: &str,
+    favorites: &[String],
+    recent: &[RecentTarget],
+    limit: Option<usize>,
+) -> Vec<TargetMatch> {
+    let terms: Vec<Vec<char>> = query.split_whitespace().map(|term| term.chars().map(fold).collect()).collect();
+
+    let mut matches: Vec<TargetMatch> = targets
+        .iter()
+        .filter_map(|target| {
+            let (score, highlights) = match_target(target, &terms)?;
+            let favorite = favorites.iter().any(|id| id == &target.id);
+            let boost = if favorite { FAVORITE_BOOST } else { 0 } + recency_boost(&target.id, recent);
+
+            Some(TargetMatch { target: target.clone(), score: score + boost, favorite, highlights })
+        })
+        .collect();
+
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.target.name.to_lowercase().cmp(&b.target.name.to_lowercase()))
+            .then_with(|| a.target.id.cmp(&b.target.id))
+    });
+    if let Some(limit) = limit {
+        matches.truncate(limit);
+    }
+    matches
+}
+
+fn recency_boost(target_id: &str, recent: &[RecentTarget]) -> u32 {
+    recent
+        .iter()
+        .position(|r| r.target_id == target_id)
+        .map(|rank| RECENT_BOOST * (RECENT_TARGETS_LIMIT.saturating_sub(rank)) as u32 / RECENT_TARGETS_LIMIT as u32)
+        .unwrap_or(0)
+}
+
+// Score and highlights when every term matches some field
+fn match_target(target: &BoundaryTarget, terms: &[Vec<char>]) -> Option<(u32, Vec<FieldHighlight>)> {
+    let fields: Vec<(SearchField, Vec<char>)> = SearchField::ALL
+        .iter()
+        .filter_map(|field| field.chars(target).map(|chars| (*field, chars)))
+        .collect();
+
+    let mut score = 0;
+    let mut highlights: Vec<FieldHighlight> = Vec::new();
+
+    for term in terms {
+        let mut best = None;
+        for (field, text) in &fields {
+            let Some((field_score, positions)) = match_field(text, term) else {
+                continue;
+            };
+            best = best.max(Some(field_score * field.weight()));
+
+            match highlights.iter_mut().find(|h| h.field == *field) {
+                Some(highlight) => highlight.positions.extend(positions),
+                None => highlights.push(FieldHighlight { field: *field, positions }),
+            }
+        }
+        score += best?;
+    }
+
+    for highlight in &mut highlights {
+        highlight.positions.sort_unstable();
+        highlight.positions.dedup();
+    }
+    Some((score, highlights))
+}
+
+// Substring matches always outrank subsequence matches within the same field
+fn match_field(text: &[char], term: &[char]) -> Option<(u32, Vec<usize>)> {
+    if term.is_empty() || term.len() > text.len() {
+        return None;
+    }
+    let folded: Vec<char> = text.iter().copied().map(fold).collect();
+
+    if let Some(start) = find_substring(text, &folded, term) {
+        let mut score = 100;
+        if term.len() == text.len() {
+            score += 100;
+        } else if start == 0 {
+            score += 50;
+        } else if is_word_start(text, start) {
+            score += 25;
+        }
+        return Some((score, (start..start + term.len()).collect()));
+    }
+
+    let positions = find_subsequence(&folded, term)?;
+    let mut score = 0;
+    for (i, &position) in positions.iter().enumerate() {
+        score += 2;
+        if i > 0 && positions[i - 1] + 1 == position {
+            score += 4;
+        }
+        if is_word_start(text, position) {
+            score += 6;
+        }
+    }
+    Some((score.min(99), positions))
+}
+
+// First occurrence, preferring one that starts a word
+fn find_substring(text: &[char], folded: &[char], term: &[char]) -> Option<usize> {
+    let mut first = None;
+    for start in 0..=folded.len() - term.len() {
+        if folded[start..start + term.len()] == *term {
+            if is_word_start(text, start) {
+                return Some(start);
+            }
+            first = first.or(Some(start));
+        }
+    }
+    first
+}
+
+// Tightest in-order match of the term's characters; spread-out matches are noise
+fn find_subsequence(folded: &[char], term: &[char]) -> Option<Vec<usize>> {
+    let max_span = term.len() * 3;
+    let mut best: Option<Vec<usize>> = None;
+
+    for start in (0..folded.len()).filter(|&i| folded[i] == term[0]) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &c in &term[1..] {
+            let Some(offset) = folded[next..].iter().position(|&f| f == c) else {
+                // Later starts have even less text left to match in
+                return best;
+            };
+            positions.push(next + offset);
+            next += offset + 1;
+        }
+
+        let span = next - start;
+        let best_span = best.as_ref().map(|b| b[b.len() - 1] + 1 - b[0]);
+        if span <= max_span && best_span.is_none_or(|best_span| span < best_span) {
+            best = Some(positions);
+        }
+    }
+    best
+}
+
+// Start of the text, after a separator, or a camelCase hump
+fn is_word_start(text: &[char], position: usize) -> bool {
+    if position == 0 {
+        return true;
+    }
+    let previous = text[position - 1];
+    !previous.is_alphanumeric() || (previous.is_lowercase() && text[position].is_uppercase())
+}
+
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn target(id: &str, name: &str, description: &str) -> BoundaryTarget {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": name,
+            "target_type": "tcp",
+            "description": description,
+            "address": null,
+            "default_port": 22,
+        }))
+        .unwrap()
+    }
+
+    fn names(matches: &[TargetMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.target.name.as_str()).collect()
+    }
+
+    #[test]
+    fn ranks_name_matches_above_fuzzy_and_description_matches() {
+        let targets = vec![
+            target("ttcp_1", "billing-api", "Talks to postgres"),
+            target("ttcp_2", "postgres-prod", "Primary database"),
+            target("ttcp_3", "pg-staging", "Postgres replica"),
+            target("ttcp_4", "web", "Frontend"),
+        ];
+
+        let matches = search_targets(&targets, "postgres", &[], &[], None);
+        assert_eq!(names(&matches), vec!["postgres-prod", "pg-staging", "billing-api"]);
+
+        let fuzzy = search_targets(&targets, "pgprd", &[], &[], None);
+        assert_eq!(names(&fuzzy), vec!["postgres-prod"]);
+        assert_eq!(fuzzy[0].highlights[0].field, SearchField::Name);
+        assert_eq!(fuzzy[0].highlights[0].positions, vec![0, 4, 9, 10, 12]);
+
+        assert!(search_targets(&targets, "postgres nothing", &[], &[], None).is_empty());
+    }
+
+    #[test]
+    fn favorites_and_recent_targets_rank_first() {
+        let targets = vec![
+            target("ttcp_1", "db-1", ""),
+            target("ttcp_2", "db-2", ""),
+            target("ttcp_3", "db-3", ""),
+        ];
+        let recent = vec![RecentTarget {
+            target_id: "ttcp_2".to_string(),
+            target_name: "db-2".to_string(),
+            last_used: Utc::now(),
+        }];
+
+        let matches = search_targets(&targets, "db", &["ttcp_3".to_string()], &recent, None);
+        assert_eq!(names(&matches), vec!["db-3", "db-2", "db-1"]);
+        assert!(matches[0].favorite);
+
+        let all = search_targets(&targets, "  ", &[], &recent, Some(2));
+        assert_eq!(names(&all), vec!["db-2", "db-1"]);
+        assert!(all[0].highlights.is_empty());
+    }
+
+    #[test]
+    fn matches_aliases_like_names() {
+        let mut db = target("ttcp_1", "postgres-01", "");
+        db.aliases = vec!["db.prod.internal".to_string(), "pg.prod.internal".to_string()];
+        let targets = vec![db, target("ttcp_2", "web", "Serves prod traffic")];
+
+        let matches = search_targets(&targets, "pg.prod", &[], &[], None);
+        assert_eq!(names(&matches), vec!["postgres-01"]);
+        assert_eq!(matches[0].highlights[0].field, SearchField::Alias);
+        assert_eq!(matches[0].highlights[0].positions, (18..25).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn highlights_use_character_positions() {
+        let targets = vec![target("ttcp_1", "Zürich-DB", "")];
+
+        let matches = search_targets(&targets, "db", &[], &[], None);
+        assert_eq!(matches[0].highlights[0].positions, vec![7, 8]);
+    }
+}
@@ -0,0 +1,680 @@
+// End-to-end discovery, authentication and connect flows against the fake Boundary CLI
+
+mod common;
+
+use common::FakeCli;
+use regis_lib::{ConnectionEvent, ConnectionType, ListenSettings, ProxyEvent, ProxyState, RetryPolicy, ScopeTargets, TargetDiscoveryOptions, TargetQuery};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const SERVER: &str = "http://127.0.0.1:9200";
+const RELEASES: &[&str] = &["0.9", "0.13", "0.19"];
+const PERMISSION_DENIED: &str = "Error from controller when performing list on targets: Status: 403, Kind: PermissionDenied";
+
+// Discovery options recording every per-scope progress report
+fn recording_discovery(concurrency: usize) -> (TargetDiscoveryOptions, Arc<Mutex<Vec<ScopeTargets>>>) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = reports.clone();
+    let discovery = TargetDiscoveryOptions::default()
+        .with_concurrency(concurrency)
+        .on_scope(move |scope| sink.lock().unwrap().push(scope.clone()));
+    (discovery, reports)
+}
+
+#[tokio::test]
+async fn discovers_auth_methods_scopes_and_targets_on_every_release() {
+    for release in RELEASES {
+        let cli = FakeCli::new(release);
+        let options = cli.options();
+
+        let auth_methods = regis_lib::discover_auth_methods(None, &cli.path, &options, SERVER).await.unwrap();
+        assert!(!auth_methods.is_empty(), "{}: no auth methods", release);
+        assert!(auth_methods.iter().all(|m| !m.id.is_empty() && !m.name.is_empty()));
+
+        regis_lib::discover_scopes(None, &cli.path, &options, SERVER).await.unwrap();
+
+        let targets = regis_lib::discover_targets(None, &cli.path, &options, SERVER, &TargetQuery::default()).await.unwrap();
+        assert!(!targets.is_empty(), "{}: no targets", release);
+        assert!(targets.iter().all(|t| !t.id.is_empty() && t.default_port.is_some()));
+
+        let invocations = cli.invocations();
+        assert_eq!(invocations.len(), 3);
+        for entry in &invocations {
+            let args: Vec<&str> = entry["args"].as_array().unwrap().iter().filter_map(|a| a.as_str()).collect();
+            assert!(args.windows(2).any(|w| w == ["-addr", SERVER]), "{:?}", args);
+            assert!(args.windows(2).any(|w| w == ["-format", "json"]), "{:?}", args);
+        }
+    }
+}
+
+#[tokio::test]
+async fn lists_all_targets_in_one_recursive_call() {
+    let cli = FakeCli::new("0.13");
+    let filter = r#""/item/type" == "tcp""#;
+
+    let targets = regis_lib::discover_all_targets(None, &cli.path, &cli.options(), SERVER, "global", Some(filter), &TargetDiscoveryOptions::default())
+        .await
+        .unwrap();
+
+    assert_eq!(targets.len(), 1);
+    assert_eq!(cli.invoked_commands(), vec!["targets list"]);
+    let args: Vec<String> = serde_json::from_value(cli.invocations()[0]["args"].clone()).unwrap();
+    assert!(args.windows(2).any(|w| w == ["-scope-id", "global"]), "{:?}", args);
+    assert!(args.contains(&"-recursive".to_string()), "{:?}", args);
+    assert!(args.windows(2).any(|w| w[0] == "-filter" && w[1] == filter), "{:?}", args);
+}
+
+#[tokio::test]
+async fn lists_each_project_when_recursive_listing_is_denied() {
+    let cli = FakeCli::new("0.13").failing("targets list -recursive", PERMISSION_DENIED);
+    let (discovery, reports) = recording_discovery(2);
+
+    let targets = regis_lib::discover_all_targets(None, &cli.path, &cli.options(), SERVER, "global", None, &discovery)
+        .await
+        .unwrap();
+
+    // Three projects in the fixture (orgs hold no targets), each answering with one target
+    assert_eq!(targets.len(), 3);
+    assert_eq!(
+        cli.invoked_commands(),
+        vec!["targets list", "scopes list", "targets list", "targets list", "targets list"]
+    );
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports.iter().map(|r| r.completed).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(reports.iter().all(|r| r.total == 3 && r.error.is_none() && r.targets.len() == 1));
+}
+
+#[tokio::test]
+async fn lists_projects_concurrently_up_to_the_limit() {
+    let delay = Duration::from_millis(300);
+    let cli = FakeCli::new("0.13").failing("targets list -recursive", PERMISSION_DENIED).delayed(delay);
+    let (discovery, _) = recording_discovery(3);
+
+    let started = Instant::now();
+    let targets = regis_lib::discover_all_targets(None, &cli.path, &cli.options(), SERVER, "global", None, &discovery)
+        .await
+        .unwrap();
+
+    // Recursive attempt + scopes list + the three projects side by side, instead of five in a row
+    assert_eq!(targets.len(), 3);
+    assert!(started.elapsed() < delay * 5, "took {:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn reports_projects_that_fail_without_dropping_the_others() {
+    let cli = FakeCli::new("0.13")
+        .failing("targets list -recursive", PERMISSION_DENIED)
+        .failing("targets list p_2345678901", "Error from controller: Status: 500, Kind: Internal");
+    let (discovery, reports) = recording_discovery(1);
+
+    let targets = regis_lib::discover_all_targets(None, &cli.path, &cli.options(), SERVER, "global", None, &discovery)
+        .await
+        .unwrap();
+
+    assert_eq!(targets.len(), 2);
+
+    let reports = reports.lock().unwrap();
+    let failed: Vec<&ScopeTargets> = reports.iter().filter(|r| r.error.is_some()).collect();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].scope_id, "p_2345678901");
+    assert!(failed[0].error.as_deref().unwrap().contains("Internal"));
+}
+
+#[tokio::test]
+async fn fails_when_no_project_can_be_listed() {
+    let cli = FakeCli::new("0.13").failing("targets list", PERMISSION_DENIED);
+
+    let error = regis_lib::discover_all_targets(None, &cli.path, &cli.options(), SERVER, "global", None, &TargetDiscoveryOptions::default())
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("PermissionDenied"), "{}", error);
+}
+
+#[tokio::test]
+async fn builds_the_scope_tree_from_a_recursive_listing() {
+    let cli = FakeCli::new("0.13");
+
+    let tree = regis_lib::discover_scope_tree(None, &cli.path, &cli.options(), SERVER).await.unwrap();
+
+    let args = cli.invocations()[0]["args"].to_string();
+    assert!(args.contains(r#""-scope-id","global","-recursive""#), "{}", args);
+
+    assert_eq!(tree.scope.id, "global");
+    let top: Vec<&str> = tree.children.iter().map(|c| c.scope.name.as_str()).collect();
+    // Sorted by name; the project whose org is hidden hangs off global
+    assert_eq!(top, vec!["Engineering", "Finance", "Shared"]);
+    assert_eq!(tree.children[0].children[0].scope.name, "Servers");
+    assert_eq!(tree.children[0].children[0].scope.parent_scope_id.as_deref(), Some("o_1234567890"));
+    assert_eq!(tree.children[1].children[0].scope.id, "p_2345678901");
+    assert!(tree.children[2].children.is_empty());
+
+    let empty = FakeCli::new("0.19");
+    let tree = regis_lib::discover_scope_tree(None, &empty.path, &empty.options(), SERVER).await.unwrap();
+    assert!(tree.children.is_empty());
+}
+
+#[tokio::test]
+async fn reads_target_details_on_every_release() {
+    let expected = [("0.9", "ttcp_1234567890"), ("0.13", "ttcp_1234567890"), ("0.19", "tssh_abcdefghij")];
+
+    for (release, target_id) in expected {
+        let cli = FakeCli::new(release);
+
+        let target = regis_lib::get_target_details(None, &cli.path, &cli.options(), SERVER, target_id).await.unwrap();
+
+        assert_eq!(target.id, target_id);
+        assert!(target.session_max_seconds.is_some(), "{}: {:?}", release, target);
+        assert!(target.authorized_actions.iter().any(|a| a == "authorize-session"), "{}", release);
+        assert!(target.egress_worker_filter.is_some(), "{}", release);
+        assert_eq!(cli.invoked_commands(), vec!["targets read"]);
+    }
+
+    // Legacy field names map onto the current ones
+    let cli = FakeCli::new("0.9");
+    let target = regis_lib::get_target_details(None, &cli.path, &cli.options(), SERVER, "ttcp_1234567890").await.unwrap();
+    assert_eq!(target.brokered_credential_source_ids, vec!["clvlt_1234567890"]);
+    assert_eq!(target.scope_name.as_deref(), Some("Servers"));
+
+    let cli = FakeCli::new("0.19");
+    let target = regis_lib::get_target_details(None, &cli.path, &cli.options(), SERVER, "tssh_abcdefghij").await.unwrap();
+    assert_eq!(target.session_connection_limit, Some(1));
+    assert_eq!(target.default_client_port, Some(2222));
+    assert_eq!(target.injected_application_credential_source_ids, vec!["clvsclt_abcdefghij"]);
+    assert!(target.brokered_credential_source_ids.is_empty());
+    assert_eq!(target.aliases, vec!["linux.prod.internal"]);
+}
+
+#[tokio::test]
+async fn resolves_the_hosts_of_a_target() {
+    let cli = FakeCli::new("0.13");
+
+    let host_sets = regis_lib::discover_target_hosts(None, &cli.path, &cli.options(), SERVER, "ttcp_1234567890")
+        .await
+        .unwrap();
+
+    assert_eq!(host_sets.len(), 1);
+    assert_eq!(host_sets[0].name, "Windows jump boxes");
+    // Hosts follow the host set's order and exclude catalog hosts outside the set
+    let hosts: Vec<(&str, Option<&str>)> = host_sets[0]
+        .hosts
+        .iter()
+        .map(|h| (h.name.as_str(), h.address.as_deref()))
+        .collect();
+    assert_eq!(hosts, vec![("jump-02", Some("10.0.0.6")), ("jump-01", Some("10.0.0.5"))]);
+    assert_eq!(cli.invoked_commands(), vec!["targets read", "host-sets read", "hosts list"]);
+
+    let args = cli.invocations()[2]["args"].to_string();
+    assert!(args.contains(r#""-host-catalog-id","hcst_1234567890""#), "{}", args);
+}
+
+#[tokio::test]
+async fn authorizes_a_specific_host() {
+    let cli = FakeCli::new("0.13");
+
+    regis_lib::authorize_session(None, &cli.path, &cli.options(), SERVER, "ttcp_1234567890", Some("hst_1234567891"))
+        .await
+        .unwrap();
+
+    let args = cli.invocations()[0]["args"].to_string();
+    assert!(args.contains(r#""-host-id","hst_1234567891""#), "{}", args);
+}
+
+#[tokio::test]
+async fn resolves_aliases_to_targets() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options();
+
+    // Alias lookups ignore case, and an alias may name the host to connect to
+    let (target_id, host_id) = regis_lib::resolve_target_reference(None, &cli.path, &options, SERVER, "rdp.prod.internal")
+        .await
+        .unwrap();
+    assert_eq!(target_id, "ttcp_abcdefghij");
+    assert_eq!(host_id.as_deref(), Some("hst_abcdefghij"));
+
+    let args = cli.invocations()[0]["args"].to_string();
+    assert!(args.contains(r#""aliases","list","-scope-id","global","-recursive""#), "{}", args);
+
+    // Target ids are used as they are
+    let (target_id, host_id) = regis_lib::resolve_target_reference(None, &cli.path, &options, SERVER, "tssh_abcdefghij")
+        .await
+        .unwrap();
+    assert_eq!((target_id.as_str(), host_id), ("tssh_abcdefghij", None));
+    assert_eq!(cli.invocations().len(), 1);
+    assert!(!regis_lib::is_target_id("db.prod.internal"));
+    assert!(!regis_lib::is_target_id("test_db"));
+    assert!(!regis_lib::is_target_id("tools_vm"));
+    assert!(!regis_lib::is_target_id("ttcp_12345"));
+    assert!(regis_lib::is_target_id("trdp_AbCdEf0123"));
+
+    let unassigned = regis_lib::resolve_alias(None, &cli.path, &options, SERVER, "spare.prod.internal").await.unwrap_err();
+    assert!(unassigned.contains("does not point to a target"), "{}", unassigned);
+    let unknown = regis_lib::resolve_alias(None, &cli.path, &options, SERVER, "nope.prod.internal").await.unwrap_err();
+    assert!(unknown.contains("No alias named"), "{}", unknown);
+}
+
+#[tokio::test]
+async fn lists_aliases_alongside_targets() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options();
+
+    let mut targets = regis_lib::discover_all_targets(None, &cli.path, &options, SERVER, "global", None, &TargetDiscoveryOptions::default())
+        .await
+        .unwrap();
+    let aliases = regis_lib::discover_aliases(None, &cli.path, &options, SERVER).await.unwrap();
+    regis_lib::attach_aliases(&mut targets, &aliases);
+
+    let rdp = targets.iter().find(|t| t.id == "ttcp_abcdefghij").unwrap();
+    assert_eq!(rdp.aliases, vec!["RDP.prod.internal"]);
+
+    // Releases before aliases existed report an error the caller can ignore
+    let old = FakeCli::new("0.13");
+    assert!(regis_lib::discover_aliases(None, &old.path, &old.options(), SERVER).await.is_err());
+}
+
+#[tokio::test]
+async fn refuses_targets_without_authorize_session() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options();
+
+    let denied = regis_lib::check_session_permission(None, &cli.path, &options, SERVER, "tssh_readonly01", None)
+        .await
+        .unwrap_err();
+    assert!(denied.contains("not permitted to connect to target 'audit-ssh'"), "{}", denied);
+
+    regis_lib::check_session_permission(None, &cli.path, &options, SERVER, "tssh_abcdefghij", None).await.unwrap();
+    assert_eq!(cli.invoked_commands(), vec!["targets read", "targets read"]);
+
+    // A known connectable target needs no lookup; a stale refusal is checked again
+    let mut known = regis_lib::get_target_details(None, &cli.path, &options, SERVER, "tssh_abcdefghij").await.unwrap();
+    let before = cli.invocations().len();
+    regis_lib::check_session_permission(None, &cli.path, &options, SERVER, &known.id, Some(&known)).await.unwrap();
+    assert_eq!(cli.invocations().len(), before);
+
+    known.authorized_actions = vec!["read".to_string()];
+    regis_lib::check_session_permission(None, &cli.path, &options, SERVER, &known.id, Some(&known)).await.unwrap();
+    assert_eq!(cli.invocations().len(), before + 1);
+
+    // When the target can't be read, authorization goes ahead and reports its own error
+    let unreadable = FakeCli::new("0.19").failing("targets read", PERMISSION_DENIED);
+    regis_lib::check_session_permission(None, &unreadable.path, &unreadable.options(), SERVER, "tssh_readonly01", None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn authenticates_with_oidc() {
+    for release in RELEASES {
+        let cli = FakeCli::new(release);
+
+        let token = regis_lib::trigger_reauthentication(&cli.path, &cli.options(), SERVER, "amoidc_1234567890", "dev")
+            .await
+            .unwrap();
+
+        assert!(token.access_token.starts_with("at_"), "{}: {:?}", release, token);
+        assert!(token.user_id.starts_with("u_"));
+        assert_eq!(token.server_id, "dev");
+        assert!(token.expires_at.is_some());
+    }
+}
+
+#[tokio::test]
+async fn authorizes_and_connects_to_a_target() {
+    let cli = FakeCli::new("0.13");
+    let options = cli.options();
+
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap();
+    assert_eq!(authorization.session_id, "s_1234567890");
+    assert_eq!(authorization.authorization_token, "FAKE-AUTHZ-TOKEN-0.13");
+
+    // The proxy keeps running; the connection is reported as soon as it listens
+    let started = Instant::now();
+    let process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::RDP, "windows-rdp", &ListenSettings::default())
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+    assert_eq!(process.connection.local_address, "127.0.0.1");
+    assert_eq!(process.connection.local_port, 55389);
+    assert_eq!(process.connection.session_id, "s_1234567890");
+    assert!(process.pid().is_some());
+    assert!(!process.has_exited());
+
+    let connect = &cli.invocations()[1];
+    let args: Vec<&str> = connect["args"].as_array().unwrap().iter().filter_map(|a| a.as_str()).collect();
+    assert_eq!(args, vec!["connect", "rdp", "-authz-token", "FAKE-AUTHZ-TOKEN-0.13", "-format", "json"]);
+
+    process.terminate().await.unwrap();
+}
+
+#[tokio::test]
+async fn terminates_connections_and_cancels_their_sessions() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_abcdefghij", None)
+        .await
+        .unwrap();
+    let process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::TCP, "postgres", &ListenSettings::default())
+        .await
+        .unwrap();
+    assert_eq!(process.connection.server_id, "dev");
+
+    // The controller answers "canceling" first, then confirms the session terminated
+    let termination = regis_lib::terminate_connection(None, &cli.path, &options, SERVER, process).await;
+    assert!(termination.is_complete(), "{:?}", termination);
+    assert_eq!(termination.session_id, "s_abcdefghij");
+    assert!(termination.errors.is_empty());
+
+    let commands = cli.invoked_commands();
+    assert_eq!(commands[2..], ["sessions cancel", "sessions read"]);
+}
+
+#[tokio::test]
+async fn reports_sessions_the_controller_refuses_to_cancel() {
+    let cli = FakeCli::new("0.13").failing(
+        "sessions cancel",
+        "Error from controller when performing cancel on session: Status: 403, Kind: PermissionDenied",
+    );
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap();
+    let process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::RDP, "windows-rdp", &ListenSettings::default())
+        .await
+        .unwrap();
+
+    // The proxy is gone even though the session lives on
+    let termination = regis_lib::terminate_connection(None, &cli.path, &options, SERVER, process).await;
+    assert!(!termination.is_complete());
+    assert!(termination.proxy_stopped);
+    assert_eq!(termination.session_status.as_deref(), Some("active"));
+    assert_eq!(termination.errors.len(), 1);
+    assert!(termination.errors[0].contains("Failed to cancel session s_1234567890"), "{:?}", termination.errors);
+    assert!(termination.errors[0].contains("PermissionDenied"), "{:?}", termination.errors);
+}
+
+#[tokio::test]
+async fn listens_where_the_settings_say() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_abcdefghij", None)
+        .await
+        .unwrap();
+    let listen = ListenSettings { listen_addr: Some("127.0.0.2".to_string()), listen_port: Some(13389) };
+
+    let process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::RDP, "windows-rdp", &listen)
+        .await
+        .unwrap();
+    assert_eq!(process.connection.local_address, "127.0.0.2");
+    assert_eq!(process.connection.local_port, 13389);
+    assert!(process.connection.fixed_port);
+
+    let connect = &cli.invocations()[1];
+    let args: Vec<&str> = connect["args"].as_array().unwrap().iter().filter_map(|a| a.as_str()).collect();
+    assert_eq!(args[6..], ["-listen-addr", "127.0.0.2", "-listen-port", "13389"]);
+
+    process.terminate().await.unwrap();
+}
+
+#[tokio::test]
+async fn stops_proxies_that_listen_elsewhere() {
+    let cli = FakeCli::new("0.19").listening_on(13390);
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_abcdefghij", None)
+        .await
+        .unwrap();
+    let listen = ListenSettings { listen_addr: None, listen_port: Some(13389) };
+
+    let error = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::TCP, "postgres", &listen)
+        .await
+        .unwrap_err();
+    assert!(error.starts_with("Local port 13389 is already in use"), "{}", error);
+    assert!(error.contains("127.0.0.1:13390"), "{}", error);
+}
+
+#[tokio::test]
+async fn refuses_fixed_ports_that_are_taken() {
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = taken.local_addr().unwrap().port();
+
+    let listen = ListenSettings { listen_addr: None, listen_port: Some(port) };
+    let error = regis_lib::check_listen_port(&listen).await.unwrap_err();
+    assert!(error.starts_with(&format!("Local port 127.0.0.1:{} is already in use", port)), "{}", error);
+
+    drop(taken);
+    regis_lib::check_listen_port(&listen).await.unwrap();
+    regis_lib::check_listen_port(&ListenSettings::default()).await.unwrap();
+}
+
+#[tokio::test]
+async fn notices_when_the_proxy_stops() {
+    let cli = FakeCli::new("0.19").proxying_for(Duration::from_millis(100));
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap();
+
+    let mut process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::TCP, "postgres", &ListenSettings::default())
+        .await
+        .unwrap();
+    assert_eq!(process.connection.connection_limit, 1);
+    assert_eq!(process.connection.expiration_time.as_deref(), Some("2025-03-01T18:05:00Z"));
+
+    // The proxy reports why the session ended before it exits
+    let mut events = process.take_events().unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap();
+    assert_eq!(event, Some(ProxyEvent::SessionTerminated { termination_reason: "timed out".to_string() }));
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(process.has_exited());
+}
+
+#[tokio::test]
+async fn reports_the_life_of_a_connection() {
+    let cli = FakeCli::new("0.19").proxying_for(Duration::from_millis(200));
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_abcdefghij", None)
+        .await
+        .unwrap();
+    let mut process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::TCP, "postgres", &ListenSettings::default())
+        .await
+        .unwrap();
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    let follower = regis_lib::follow_connection(&mut process, Duration::from_secs(300), move |event| sink.lock().unwrap().push(event)).unwrap();
+    assert!(regis_lib::follow_connection(&mut process, Duration::from_secs(300), |_| {}).is_none());
+
+    tokio::time::timeout(Duration::from_secs(5), follower).await.unwrap().unwrap();
+    assert!(process.has_exited());
+
+    // The fixture's session expired long ago, so the expiry warning comes right away
+    let events = events.lock().unwrap().clone();
+    let statuses: Vec<Option<&str>> = events.iter().map(|event| event.status()).collect();
+    assert_eq!(statuses, vec![Some("active"), Some("expiring"), Some("expired"), Some("exited")]);
+    assert_eq!(
+        events[0],
+        ConnectionEvent::ProxyReady {
+            local_address: "127.0.0.1".to_string(),
+            local_port: 55390,
+            expiration_time: Some("2025-03-01T18:05:00Z".to_string()),
+            connection_limit: 1,
+        }
+    );
+    assert_eq!(events[1], ConnectionEvent::SessionExpiring { seconds_left: 0 });
+    assert_eq!(events[2], ConnectionEvent::SessionExpired);
+    assert_eq!(events[3], ConnectionEvent::ProxyExited { exit_code: Some(0) });
+}
+
+#[tokio::test]
+async fn reports_terminated_proxies_as_exited() {
+    let cli = FakeCli::new("0.13");
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap();
+    let mut process = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::RDP, "windows-rdp", &ListenSettings::default())
+        .await
+        .unwrap();
+
+    let (sender, mut events) = tokio::sync::mpsc::unbounded_channel();
+    regis_lib::follow_connection(&mut process, Duration::ZERO, move |event| {
+        let _ = sender.send(event);
+    });
+    let mut state = process.watch_state();
+
+    process.terminate().await.unwrap();
+    assert!(matches!(*state.borrow_and_update(), ProxyState::Exited(_)));
+
+    let mut last = None;
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(5), events.recv()).await {
+        last = Some(event);
+    }
+    assert!(matches!(last, Some(ConnectionEvent::ProxyExited { .. })), "{:?}", last);
+}
+
+#[tokio::test]
+async fn reports_proxies_that_exit_before_listening() {
+    let cli = FakeCli::new("0.19").failing("connect", "Error: session has been canceled");
+    let options = cli.options();
+    let authorization = regis_lib::authorize_session(None, &cli.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap();
+
+    let error = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::SSH, "linux-ssh", &ListenSettings::default())
+        .await
+        .unwrap_err();
+    assert!(error.starts_with("Failed to establish ssh connection"), "{}", error);
+    assert!(error.contains("session has been canceled"), "{}", error);
+}
+
+#[tokio::test]
+async fn stops_proxies_that_never_listen() {
+    let authorization = {
+        let cli = FakeCli::new("0.19");
+        regis_lib::authorize_session(None, &cli.path, &cli.options(), SERVER, "ttcp_1234567890", None)
+            .await
+            .unwrap()
+    };
+    let cli = FakeCli::new("0.19").delayed(Duration::from_secs(5));
+    let options = cli.options().with_timeout(Duration::from_millis(300));
+
+    let started = Instant::now();
+    let error = regis_lib::establish_connection(&cli.path, &options, "dev", &authorization, ConnectionType::TCP, "postgres", &ListenSettings::default())
+        .await
+        .unwrap_err();
+    assert!(error.contains("timed out"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(3), "{:?}", started.elapsed());
+}
+
+#[tokio::test]
+async fn passes_the_token_through_the_environment_only() {
+    let cli = FakeCli::new("0.19");
+    let options = cli.options().with_token("at_abcdefghij_s.SecretValue");
+
+    regis_lib::discover_scopes(None, &cli.path, &options, SERVER).await.unwrap();
+
+    let entry = &cli.invocations()[0];
+    assert_eq!(entry["token_from_env"], true);
+    let args = entry["args"].to_string();
+    assert!(args.contains("env://BOUNDARY_TOKEN"), "{}", args);
+    assert!(!args.contains("SecretValue"), "{}", args);
+}
+
+#[tokio::test]
+async fn reads_session_status() {
+    let active = FakeCli::new("0.13");
+    let health = regis_lib::check_session_health(None, &active.path, &active.options(), SERVER, "s_1234567890")
+        .await
+        .unwrap();
+    assert_eq!(health.status, "healthy");
+
+    let terminated = FakeCli::new("0.19");
+    let health = regis_lib::check_session_health(None, &terminated.path, &terminated.options(), SERVER, "s_abcdefghij")
+        .await
+        .unwrap();
+    assert_eq!(health.status, "session_status_terminated");
+}
+
+#[tokio::test]
+async fn reports_cli_errors_without_retrying_fatal_ones() {
+    let cli = FakeCli::new("0.13").failing(
+        "targets list",
+        "Error from controller when performing list on targets: Status: 403, Kind: PermissionDenied",
+    );
+    let options = cli.options().with_retry(RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(10),
+    });
+
+    let error = regis_lib::discover_targets(None, &cli.path, &options, SERVER, &TargetQuery::default()).await.unwrap_err();
+
+    assert!(error.contains("PermissionDenied"), "{}", error);
+    assert_eq!(cli.invocations().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_transient_cli_errors() {
+    let cli = FakeCli::new("0.13").failing(
+        "scopes list",
+        "Error from controller when performing list on scopes: Status: 503, Kind: Unavailable",
+    );
+    let options = cli.options().with_retry(RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(10),
+    });
+
+    let error = regis_lib::discover_scopes(None, &cli.path, &options, SERVER).await.unwrap_err();
+
+    assert!(error.contains("failed after 3 attempts"), "{}", error);
+    assert_eq!(cli.invocations().len(), 3);
+}
+
+#[tokio::test]
+async fn retries_authorizations_only_when_the_controller_never_saw_them() {
+    let retry = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(10),
+    };
+
+    // A 503 may come after the session was created: asking again could create another
+    let unavailable = FakeCli::new("0.13").failing(
+        "targets authorize-session",
+        "Error from controller when performing authorize-session on target: Status: 503, Kind: Unavailable",
+    );
+    let options = unavailable.options().with_retry(retry);
+    let error = regis_lib::authorize_session(None, &unavailable.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap_err();
+    assert!(error.contains("Status: 503"), "{}", error);
+    assert_eq!(unavailable.invocations().len(), 1);
+
+    let unreachable = FakeCli::new("0.13").failing(
+        "targets authorize-session",
+        "Error trying to authorize a session: dial tcp 127.0.0.1:9200: connect: connection refused",
+    );
+    let options = unreachable.options().with_retry(retry);
+    let error = regis_lib::authorize_session(None, &unreachable.path, &options, SERVER, "ttcp_1234567890", None)
+        .await
+        .unwrap_err();
+    assert!(error.contains("failed after 3 attempts"), "{}", error);
+    assert_eq!(unreachable.invocations().len(), 3);
+}
+
+#[tokio::test]
+async fn kills_the_cli_when_the_deadline_expires() {
+    let cli = FakeCli::new("0.13").delayed(Duration::from_secs(10));
+    let options = cli.options().with_timeout(Duration::from_millis(300));
+
+    let started = std::time::Instant::now();
+    let error = regis_lib::discover_auth_methods(None, &cli.path, &options, SERVER).await.unwrap_err();
+
+    assert!(error.contains("timed out"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,311 @@
+// Shared harness for the offline integration tests
+//
+// FakeCli wires BoundaryCommandOptions to the fake `boundary` example
+// (tests/support/fake_boundary.rs); FakeController serves the same fixtures over the
+// controller REST API. Both record what Regis sent so tests can assert on it.
+
+#![allow(dead_code)]
+
+use regis_lib::BoundaryCommandOptions;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+pub fn fixture_dir(release: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("boundary")
+        .join(release)
+}
+
+// Unique scratch path per test so tests can run in parallel
+fn scratch_path(name: &str) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    std::env::temp_dir().join(format!(
+        "regis-test-{}-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst),
+        name
+    ))
+}
+
+// Examples are built next to the test binaries: target/<profile>/examples/
+pub fn fake_boundary_path() -> String {
+    let exe = std::env::current_exe().expect("test executable path");
+    let profile_dir = exe.parent().and_then(Path::parent).expect("target profile directory");
+    let name = if cfg!(windows) { "fake-boundary.exe" } else { "fake-boundary" };
+    let path = profile_dir.join("examples").join(name);
+
+    assert!(
+        path.is_file(),
+        "{} not found; build it with `cargo build --example fake-boundary` (cargo test does this)",
+        path.display()
+    );
+    path.to_string_lossy().to_string()
+}
+
+pub struct FakeCli {
+    pub path: String,
+    pub release: String,
+    log: PathBuf,
+    env: HashMap<String, String>,
+}
+
+impl FakeCli {
+    pub fn new(release: &str) -> Self {
+        let log = scratch_path("fake-boundary.log");
+        let mut env = HashMap::new();
+        env.insert("FAKE_BOUNDARY_FIXTURES".to_string(), fixture_dir(release).to_string_lossy().to_string());
+        env.insert("FAKE_BOUNDARY_LOG".to_string(), log.to_string_lossy().to_string());
+
+        FakeCli {
+            path: fake_boundary_path(),
+            release: release.to_string(),
+            log,
+            env,
+        }
+    }
+
+    // Make invocations containing every word of `command` (e.g. "targets list -recursive")
+    // exit 1 with `stderr`; may be called several times
+    pub fn failing(mut self, command: &str, stderr: &str) -> Self {
+        let rule = format!("{}={}", command, stderr);
+        self.env
+            .entry("FAKE_BOUNDARY_FAIL".to_string())
+            .and_modify(|rules| {
+                rules.push('\n');
+                rules.push_str(&rule);
+            })
+            .or_insert(rule);
+        self
+    }
+
+    pub fn delayed(mut self, delay: Duration) -> Self {
+        self.env.insert("FAKE_BOUNDARY_DELAY_MS".to_string(), delay.as_millis().to_string());
+        self
+    }
+
+    // How long `connect` keeps its proxy running after reporting the listener
+    pub fn proxying_for(mut self, session: Duration) -> Self {
+        self.env.insert("FAKE_BOUNDARY_SESSION_MS".to_string(), session.as_millis().to_string());
+        self
+    }
+
+    // Port `connect` listens on, ignoring the one it is asked for
+    pub fn listening_on(mut self, port: u16) -> Self {
+        self.env.insert("FAKE_BOUNDARY_LISTEN_PORT".to_string(), port.to_string());
+        self
+    }
+
+    pub fn options(&self) -> BoundaryCommandOptions {
+        let mut options = BoundaryCommandOptions::default().with_timeout(Duration::from_secs(10));
+        options.env = self.env.clone();
+        options
+    }
+
+    // Every invocation so far, as {"args": [...], "token_from_env": bool}
+    pub fn invocations(&self) -> Vec<Value> {
+        std::fs::read_to_string(&self.log)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    pub fn invoked_commands(&self) -> Vec<String> {
+        self.invocations()
+            .iter()
+            .map(|entry| {
+                entry["args"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .take_while(|a| !a.starts_with('-'))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+}
+
+impl Drop for FakeCli {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.log);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub authorization: Option<String>,
+    pub body: String,
+}
+
+// Minimal HTTP/1.1 controller answering /v1/... from a fixture directory
+pub struct FakeController {
+    pub url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    failures: Arc<Mutex<HashMap<String, u16>>>,
+    delays: Arc<Mutex<HashMap<String, Duration>>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl FakeController {
+    pub async fn start(release: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind fake controller");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let fixtures = fixture_dir(release);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let failures: Arc<Mutex<HashMap<String, u16>>> = Arc::new(Mutex::new(HashMap::new()));
+        let delays: Arc<Mutex<HashMap<String, Duration>>> = Arc::new(Mutex::new(HashMap::new()));
+
+        let task = {
+            let requests = requests.clone();
+            let failures = failures.clone();
+            let delays = delays.clone();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let fixtures = fixtures.clone();
+                    let requests = requests.clone();
+                    let failures = failures.clone();
+                    let delays = delays.clone();
+                    tokio::spawn(async move {
+                        let _ = serve_connection(stream, &fixtures, &requests, &failures, &delays).await;
+                    });
+                }
+            })
+        };
+
+        FakeController { url, requests, failures, delays, task }
+    }
+
+    // Answer requests whose path contains `path_part` (e.g. "/v1/targets" or
+    // ":authorize-session") with an error status
+    pub fn fail(&self, path_part: &str, status: u16) {
+        self.failures.lock().unwrap().insert(path_part.to_string(), status);
+    }
+
+    // Hold back the answer to requests whose path contains `path_part`
+    pub fn delay(&self, path_part: &str, delay: Duration) {
+        self.delays.lock().unwrap().insert(path_part.to_string(), delay);
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for FakeController {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn serve_connection(
+    stream: tokio::net::TcpStream,
+    fixtures: &Path,
+    requests: &Mutex<Vec<RecordedRequest>>,
+    failures: &Mutex<HashMap<String, u16>>,
+    delays: &Mutex<HashMap<String, Duration>>,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.trim().split_once(':') {
+            match name.to_ascii_lowercase().as_str() {
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                "authorization" => authorization = Some(value.trim().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+
+    requests.lock().unwrap().push(RecordedRequest {
+        method: method.clone(),
+        path: path.clone(),
+        authorization,
+        body: String::from_utf8_lossy(&body).to_string(),
+    });
+
+    let failure = failures
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(part, _)| path.contains(part.as_str()))
+        .map(|(_, status)| *status);
+
+    let delay = delays
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(part, _)| path.contains(part.as_str()))
+        .map(|(_, delay)| *delay);
+    if let Some(delay) = delay {
+        tokio::time::sleep(delay).await;
+    }
+
+    let (status, body) = match failure {
+        Some(status) => (status, error_body("Internal", "injected failure")),
+        None => route(&method, &path, fixtures),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await
+}
+
+fn route(method: &str, path: &str, fixtures: &Path) -> (u16, String) {
+    let route = path.split('?').next().unwrap_or_default();
+
+    let fixture = match (method, route) {
+        ("GET", "/v1/auth-methods") => "auth-methods",
+        ("GET", "/v1/scopes") => "scopes",
+        ("GET", "/v1/targets") => "targets",
+        ("POST", r) if r.starts_with("/v1/targets/") && r.ends_with(":authorize-session") => "authorize-session",
+        ("GET", r) if r.starts_with("/v1/targets/") => "target",
+        ("GET", r) if r.starts_with("/v1/host-sets/") => "host-set",
+        ("GET", "/v1/hosts") => "hosts",
+        ("GET", "/v1/aliases") => "aliases",
+        ("POST", r) if r.starts_with("/v1/sessions/") && r.ends_with(":cancel") => "cancel-session",
+        ("GET", r) if r.starts_with("/v1/sessions/") => "session",
+        _ => return (404, error_body("NotFound", "no such route")),
+    };
+
+    match std::fs::read_to_string(fixtures.join(format!("{}.json", fixture))) {
+        Ok(body) => (200, body),
+        Err(_) => (404, error_body("NotFound", "no fixture")),
+    }
+}
+
+fn error_body(kind: &str, message: &str) -> String {
+    serde_json::json!({ "kind": kind, "message": message }).to_string()
+}
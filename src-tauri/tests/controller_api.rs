@@ -0,0 +1,316 @@
+// Discovery and authorization through the controller REST API, with CLI fallback
+
+mod common;
+
+use common::{FakeCli, FakeController};
+use regis_lib::{BoundaryApiClient, TargetDiscoveryOptions, TargetQuery};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+fn api_client(controller: &FakeController, token: Option<&str>) -> BoundaryApiClient {
+    BoundaryApiClient::new(&controller.url, token.map(str::to_string), true, 5).unwrap()
+}
+
+#[tokio::test]
+async fn discovers_through_the_controller_without_the_cli() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_abcdefghij_s.SecretValue"));
+
+    let auth_methods = regis_lib::discover_auth_methods(Some(&api), &cli.path, &cli.options(), &controller.url)
+        .await
+        .unwrap();
+    let query = TargetQuery {
+        scope_id: Some("p_abcdefghij".to_string()),
+        ..Default::default()
+    };
+    let targets = regis_lib::discover_targets(Some(&api), &cli.path, &cli.options(), &controller.url, &query)
+        .await
+        .unwrap();
+
+    assert_eq!(auth_methods[0].id, "amoidc_abcdefghij");
+    assert_eq!(targets.len(), 2);
+    assert!(cli.invocations().is_empty());
+
+    let requests = controller.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[1].path.starts_with("/v1/targets?scope_id=p_abcdefghij"), "{}", requests[1].path);
+    assert_eq!(requests[1].authorization.as_deref(), Some("Bearer at_abcdefghij_s.SecretValue"));
+}
+
+#[tokio::test]
+async fn reuses_clients_with_a_new_token() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let shared = api_client(&controller, None);
+    let query = TargetQuery {
+        scope_id: Some("p_abcdefghij".to_string()),
+        ..Default::default()
+    };
+
+    let api = shared.with_token(Some("at_bcdefghijk_s.RenewedSecret".to_string()));
+    regis_lib::discover_targets(Some(&api), &cli.path, &cli.options(), &controller.url, &query)
+        .await
+        .unwrap();
+    assert_eq!(controller.requests()[0].authorization.as_deref(), Some("Bearer at_bcdefghijk_s.RenewedSecret"));
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn lists_scopes_recursively_through_the_controller() {
+    let controller = FakeController::start("0.13").await;
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let tree = regis_lib::discover_scope_tree(Some(&api), &cli.path, &cli.options(), &controller.url)
+        .await
+        .unwrap();
+
+    assert_eq!(tree.children.len(), 3);
+    assert_eq!(controller.requests()[0].path, "/v1/scopes?scope_id=global&recursive=true");
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn lists_targets_recursively_with_a_filter_through_the_controller() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_abcdefghij_s.SecretValue"));
+
+    let targets = regis_lib::discover_all_targets(
+        Some(&api),
+        &cli.path,
+        &cli.options(),
+        &controller.url,
+        "o_abcdefghij",
+        Some(r#""/item/name" matches "rdp""#),
+        &TargetDiscoveryOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(targets.len(), 2);
+    assert_eq!(controller.requests().len(), 1);
+    assert_eq!(
+        controller.requests()[0].path,
+        "/v1/targets?scope_id=o_abcdefghij&recursive=true&filter=%22%2Fitem%2Fname%22+matches+%22rdp%22"
+    );
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn falls_back_to_the_cli_when_the_controller_is_unreachable() {
+    // Nothing listens on a port we just released
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+    let cli = FakeCli::new("0.13");
+    let api = BoundaryApiClient::new(&url, Some("at_1234567890_s.SecretValue".to_string()), true, 5).unwrap();
+
+    let targets = regis_lib::discover_targets(Some(&api), &cli.path, &cli.options(), &url, &TargetQuery::default())
+        .await
+        .unwrap();
+
+    assert_eq!(targets[0].id, "ttcp_1234567890");
+    assert_eq!(cli.invoked_commands(), vec!["targets list"]);
+}
+
+#[tokio::test]
+async fn reports_controller_errors_without_asking_the_cli() {
+    let controller = FakeController::start("0.13").await;
+    controller.fail("/v1/targets", 503);
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let error = regis_lib::discover_targets(Some(&api), &cli.path, &cli.options(), &controller.url, &TargetQuery::default())
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("HTTP 503"), "{}", error);
+    assert_eq!(controller.requests().len(), 1);
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn never_authorizes_twice_when_the_controller_refuses() {
+    let controller = FakeController::start("0.13").await;
+    controller.fail(":authorize-session", 403);
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let error = regis_lib::authorize_session(Some(&api), &cli.path, &cli.options(), &controller.url, "ttcp_1234567890", None)
+        .await
+        .unwrap_err();
+
+    assert!(error.contains("HTTP 403"), "{}", error);
+    assert_eq!(controller.requests().len(), 1);
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn reads_target_details_through_the_controller() {
+    let controller = FakeController::start("0.13").await;
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let target = regis_lib::get_target_details(Some(&api), &cli.path, &cli.options(), &controller.url, "ttcp_1234567890")
+        .await
+        .unwrap();
+
+    assert_eq!(target.session_max_seconds, Some(28800));
+    assert_eq!(target.session_connection_limit, Some(-1));
+    assert_eq!(target.host_source_ids, vec!["hsst_1234567890"]);
+    assert_eq!(target.brokered_credential_source_ids.len(), 2);
+    assert_eq!(target.scope_id.as_deref(), Some("p_1234567890"));
+    assert_eq!(controller.requests()[0].path, "/v1/targets/ttcp_1234567890");
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn resolves_target_hosts_through_the_controller() {
+    let controller = FakeController::start("0.13").await;
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let host_sets = regis_lib::discover_target_hosts(Some(&api), &cli.path, &cli.options(), &controller.url, "ttcp_1234567890")
+        .await
+        .unwrap();
+
+    assert_eq!(host_sets[0].hosts.len(), 2);
+    let paths: Vec<String> = controller.requests().into_iter().map(|r| r.path).collect();
+    assert_eq!(
+        paths,
+        vec!["/v1/targets/ttcp_1234567890", "/v1/host-sets/hsst_1234567890", "/v1/hosts?host_catalog_id=hcst_1234567890"]
+    );
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn lists_aliases_through_the_controller() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let aliases = regis_lib::discover_aliases(Some(&api), &cli.path, &cli.options(), &controller.url).await.unwrap();
+
+    assert_eq!(aliases.len(), 3);
+    assert_eq!(aliases[0].destination_id.as_deref(), Some("tssh_abcdefghij"));
+    assert_eq!(aliases[1].host_id.as_deref(), Some("hst_abcdefghij"));
+    assert_eq!(controller.requests()[0].path, "/v1/aliases?scope_id=global&recursive=true");
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn authorizes_sessions_through_the_controller() {
+    let controller = FakeController::start("0.13").await;
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let authorization = regis_lib::authorize_session(
+        Some(&api),
+        &cli.path,
+        &cli.options(),
+        &controller.url,
+        "ttcp_1234567890",
+        Some("hst_1234567890"),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(authorization.authorization_token, "FAKE-AUTHZ-TOKEN-0.13");
+    let request = &controller.requests()[0];
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/v1/targets/ttcp_1234567890:authorize-session");
+    assert!(request.body.contains("hst_1234567890"), "{}", request.body);
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn reads_sessions_through_the_controller() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_abcdefghij_s.SecretValue"));
+
+    let health = regis_lib::check_session_health(Some(&api), &cli.path, &cli.options(), &controller.url, "s_abcdefghij")
+        .await
+        .unwrap();
+
+    assert_eq!(health.status, "session_status_terminated");
+    assert_eq!(controller.requests()[0].path, "/v1/sessions/s_abcdefghij");
+}
+
+#[tokio::test]
+async fn cancels_sessions_through_the_controller() {
+    let controller = FakeController::start("0.19").await;
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_abcdefghij_s.SecretValue"));
+
+    let session = regis_lib::cancel_session(Some(&api), &cli.path, &cli.options(), &controller.url, "s_abcdefghij")
+        .await
+        .unwrap();
+    assert_eq!(session.status, "terminated");
+
+    // Cancelling sends the version just read, then the result is confirmed with another read
+    let requests = controller.requests();
+    let calls: Vec<(&str, &str)> = requests.iter().map(|r| (r.method.as_str(), r.path.as_str())).collect();
+    assert_eq!(
+        calls,
+        vec![
+            ("GET", "/v1/sessions/s_abcdefghij"),
+            ("POST", "/v1/sessions/s_abcdefghij:cancel"),
+            ("GET", "/v1/sessions/s_abcdefghij"),
+        ]
+    );
+    assert_eq!(requests[1].body, r#"{"version":3}"#);
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn cancels_controller_requests_in_flight() {
+    let controller = FakeController::start("0.19").await;
+    controller.delay("/v1/targets", Duration::from_secs(10));
+    let cli = FakeCli::new("0.19");
+    let api = api_client(&controller, Some("at_abcdefghij_s.SecretValue"));
+
+    let cancel = CancellationToken::new();
+    let options = cli.options().with_cancel(cancel.clone());
+    let canceller = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        cancel.cancel();
+    });
+
+    let started = Instant::now();
+    let error = regis_lib::discover_targets(Some(&api), &cli.path, &options, &controller.url, &TargetQuery::default())
+        .await
+        .unwrap_err();
+    canceller.await.unwrap();
+
+    assert!(error.contains("Operation cancelled"), "{}", error);
+    assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+    assert_eq!(controller.requests().len(), 1);
+    assert!(cli.invocations().is_empty());
+}
+
+#[tokio::test]
+async fn lists_each_project_when_the_controller_refuses_a_recursive_listing() {
+    let controller = FakeController::start("0.13").await;
+    controller.fail("/v1/targets?scope_id=global&recursive=true", 403);
+    let cli = FakeCli::new("0.13");
+    let api = api_client(&controller, Some("at_1234567890_s.SecretValue"));
+
+    let targets = regis_lib::discover_all_targets(
+        Some(&api),
+        &cli.path,
+        &cli.options(),
+        &controller.url,
+        "global",
+        None,
+        &TargetDiscoveryOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert!(!targets.is_empty());
+    assert!(controller.requests().len() > 1);
+    assert!(cli.invocations().is_empty());
+}
@@ -0,0 +1,160 @@
+// Fake `boundary` CLI for offline integration tests
+//
+// Answers the subcommands Regis runs from fixture files instead of a real cluster.
+// Behaviour is driven by environment variables, which tests pass per invocation
+// through BoundaryCommandOptions::env:
+//
+//   FAKE_BOUNDARY_FIXTURES  fixture directory, e.g. tests/fixtures/boundary/0.13
+//   FAKE_BOUNDARY_LOG       append every invocation as one JSON object per line
+//   FAKE_BOUNDARY_FAIL      "<words>=<stderr>": exit 1 when every word appears in the arguments,
+//                           e.g. "targets list" or "targets list -recursive"; one rule per line
+//   FAKE_BOUNDARY_DELAY_MS  sleep before answering, to exercise deadlines and cancellation
+//   FAKE_BOUNDARY_VERSION   version reported by `boundary version` (default 0.19.3)
+//   FAKE_BOUNDARY_SESSION_MS  how long `connect` keeps proxying after printing its listener
+//                           (default 30000), like the real proxy does until it is stopped;
+//                           the session then ends with a termination record
+//   FAKE_BOUNDARY_LISTEN_PORT  port `connect` reports listening on, whatever -listen-port asks
+//
+// Fixtures are looked up most specific first: `targets list -scope-id p_1` answers from
+// targets-p_1.json when it exists, otherwise from targets.json.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    log_invocation(&args);
+
+    if let Some(delay) = env("FAKE_BOUNDARY_DELAY_MS").and_then(|d| d.parse().ok()) {
+        std::thread::sleep(Duration::from_millis(delay));
+    }
+
+    // Subcommand words come before the first flag: "targets list -scope-id p_1"
+    let command: Vec<&str> = args.iter().map(String::as_str).take_while(|a| !a.starts_with('-')).collect();
+    let command_name = command.join(" ");
+
+    for (failing, stderr) in env("FAKE_BOUNDARY_FAIL").unwrap_or_default().lines().filter_map(|f| f.split_once('=')) {
+        if failing.split_whitespace().all(|word| args.iter().any(|a| a == word)) {
+            eprintln!("{}", stderr);
+            return ExitCode::from(1);
+        }
+    }
+
+    let json_output = flag(&args, "-format").as_deref() == Some("json");
+
+    let (fixture, qualifier) = match command.as_slice() {
+        ["version"] => return version(json_output),
+        ["auth-methods", "list"] => ("auth-methods", flag(&args, "-scope-id")),
+        ["scopes", "list"] => ("scopes", flag(&args, "-scope-id")),
+        ["targets", "list"] => ("targets", flag(&args, "-scope-id")),
+        ["targets", "read"] => ("target", flag(&args, "-id")),
+        ["host-sets", "read"] => ("host-set", flag(&args, "-id")),
+        ["hosts", "list"] => ("hosts", flag(&args, "-host-catalog-id")),
+        ["aliases", "list"] => ("aliases", None),
+        ["targets", "authorize-session"] => ("authorize-session", flag(&args, "-id")),
+        ["sessions", "read"] => ("session", flag(&args, "-id")),
+        ["sessions", "cancel"] => ("cancel-session", flag(&args, "-id")),
+        ["authenticate", _] => ("authenticate", flag(&args, "-auth-method-id")),
+        ["connect", _] => ("connect", None),
+        _ => {
+            eprintln!("Error: unknown command \"{}\"", command_name);
+            return ExitCode::from(1);
+        }
+    };
+
+    match read_fixture(fixture, qualifier.as_deref()) {
+        Some(content) => {
+            let content = if fixture == "connect" { listening_as_asked(content, &args) } else { content };
+            print!("{}", content);
+            if fixture == "connect" {
+                let _ = std::io::stdout().flush();
+                let session = env("FAKE_BOUNDARY_SESSION_MS").and_then(|d| d.parse().ok()).unwrap_or(30_000);
+                std::thread::sleep(Duration::from_millis(session));
+                println!("{{\"termination_reason\": \"timed out\"}}");
+            }
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("fake-boundary: no {}.json fixture for \"{}\"", fixture, args.join(" "));
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+// Value of a "-flag value" or "-flag=value" argument
+fn flag(args: &[String], name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    args.iter().enumerate().find_map(|(i, arg)| {
+        if arg == name {
+            args.get(i + 1).cloned()
+        } else {
+            arg.strip_prefix(&prefix).map(str::to_string)
+        }
+    })
+}
+
+fn version(json_output: bool) -> ExitCode {
+    let version = env("FAKE_BOUNDARY_VERSION").unwrap_or_else(|| "0.19.3".to_string());
+    if json_output {
+        println!("{{\"revision\": \"fake\", \"version\": \"{}\"}}", version);
+    } else {
+        println!("\nVersion information:\n  Git Revision:        fake\n  Version Number:      {}\n", version);
+    }
+    ExitCode::SUCCESS
+}
+
+// Like the real proxy, `connect` listens on -listen-addr/-listen-port when given
+fn listening_as_asked(startup: String, args: &[String]) -> String {
+    let Ok(mut record) = serde_json::from_str::<serde_json::Value>(&startup) else {
+        return startup;
+    };
+    if let Some(addr) = flag(args, "-listen-addr") {
+        record["address"] = addr.into();
+    }
+    let port = env("FAKE_BOUNDARY_LISTEN_PORT").or_else(|| flag(args, "-listen-port"));
+    if let Some(port) = port.and_then(|p| p.parse::<u16>().ok()) {
+        record["port"] = port.into();
+    }
+    format!("{}\n", record)
+}
+
+fn read_fixture(name: &str, qualifier: Option<&str>) -> Option<String> {
+    let dir = PathBuf::from(env("FAKE_BOUNDARY_FIXTURES")?);
+    let mut candidates = Vec::new();
+    if let Some(qualifier) = qualifier {
+        candidates.push(dir.join(format!("{}-{}.json", name, qualifier)));
+    }
+    candidates.push(dir.join(format!("{}.json", name)));
+
+    candidates.into_iter().find_map(|path| std::fs::read_to_string(path).ok())
+}
+
+// Record the arguments and whether a token reached us through the environment
+fn log_invocation(args: &[String]) {
+    let Some(path) = env("FAKE_BOUNDARY_LOG") else {
+        return;
+    };
+
+    let token_source = flag(args, "-token");
+    let token_from_env = token_source
+        .as_deref()
+        .and_then(|source| source.strip_prefix("env://"))
+        .map(|var| std::env::var(var).is_ok())
+        .unwrap_or(false);
+
+    let entry = serde_json::json!({
+        "args": args,
+        "token_from_env": token_from_env,
+    });
+
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", entry);
+    }
+}